@@ -30,6 +30,34 @@ mod m20220101_000027_links_not_null;
 mod m20220101_000028_normalize_blocked_domains;
 mod m20220101_000029_passkey_cred_id_unique;
 mod m20220101_000030_domain_abuse_controls;
+mod m20220101_000031_create_webhooks;
+mod m20220101_000032_create_integrations;
+mod m20220101_000033_create_link_url_history;
+mod m20220101_000034_add_link_start_notify;
+mod m20220101_000035_create_link_code_seq;
+mod m20220101_000036_add_click_source;
+mod m20220101_000037_add_link_use_interstitial;
+mod m20220101_000038_create_email_queue;
+mod m20220101_000039_add_link_redirect_rate_limit;
+mod m20220101_000040_add_user_default_folder;
+mod m20220101_000041_add_link_favicon_url;
+mod m20220101_000042_add_click_event_ip_hash;
+mod m20220101_000043_create_webhook_deliveries;
+mod m20220101_000044_add_org_email_branding;
+mod m20220101_000045_add_link_disabled_by_admin;
+mod m20220101_000046_add_org_code_prefix;
+mod m20220101_000047_add_link_hide_click_count;
+mod m20220101_000048_add_link_ttl_after_first_click;
+mod m20220101_000049_add_link_is_reserved;
+mod m20220101_000050_add_click_is_preview;
+mod m20220101_000051_add_user_timezone;
+mod m20220101_000052_add_link_health;
+mod m20220101_000053_add_org_qr_version;
+mod m20220101_000054_add_link_creation_token;
+mod m20220101_000055_add_click_sample_rate;
+mod m20220101_000056_add_user_deletion_scheduled_at;
+mod m20220101_000057_add_link_expired_page;
+mod m20220101_000058_widen_user_timezone;
 
 pub struct Migrator;
 
@@ -67,6 +95,34 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000028_normalize_blocked_domains::Migration),
             Box::new(m20220101_000029_passkey_cred_id_unique::Migration),
             Box::new(m20220101_000030_domain_abuse_controls::Migration),
+            Box::new(m20220101_000031_create_webhooks::Migration),
+            Box::new(m20220101_000032_create_integrations::Migration),
+            Box::new(m20220101_000033_create_link_url_history::Migration),
+            Box::new(m20220101_000034_add_link_start_notify::Migration),
+            Box::new(m20220101_000035_create_link_code_seq::Migration),
+            Box::new(m20220101_000036_add_click_source::Migration),
+            Box::new(m20220101_000037_add_link_use_interstitial::Migration),
+            Box::new(m20220101_000038_create_email_queue::Migration),
+            Box::new(m20220101_000039_add_link_redirect_rate_limit::Migration),
+            Box::new(m20220101_000040_add_user_default_folder::Migration),
+            Box::new(m20220101_000041_add_link_favicon_url::Migration),
+            Box::new(m20220101_000042_add_click_event_ip_hash::Migration),
+            Box::new(m20220101_000043_create_webhook_deliveries::Migration),
+            Box::new(m20220101_000044_add_org_email_branding::Migration),
+            Box::new(m20220101_000045_add_link_disabled_by_admin::Migration),
+            Box::new(m20220101_000046_add_org_code_prefix::Migration),
+            Box::new(m20220101_000047_add_link_hide_click_count::Migration),
+            Box::new(m20220101_000048_add_link_ttl_after_first_click::Migration),
+            Box::new(m20220101_000049_add_link_is_reserved::Migration),
+            Box::new(m20220101_000050_add_click_is_preview::Migration),
+            Box::new(m20220101_000051_add_user_timezone::Migration),
+            Box::new(m20220101_000052_add_link_health::Migration),
+            Box::new(m20220101_000053_add_org_qr_version::Migration),
+            Box::new(m20220101_000054_add_link_creation_token::Migration),
+            Box::new(m20220101_000055_add_click_sample_rate::Migration),
+            Box::new(m20220101_000056_add_user_deletion_scheduled_at::Migration),
+            Box::new(m20220101_000057_add_link_expired_page::Migration),
+            Box::new(m20220101_000058_widen_user_timezone::Migration),
         ]
     }
 }
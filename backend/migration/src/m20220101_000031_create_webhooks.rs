@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Webhooks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Webhooks::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Webhooks::UserId).integer().not_null())
+                    .col(ColumnDef::new(Webhooks::Url).text().not_null())
+                    // Stored in the clear: it is the HMAC key for outgoing
+                    // payloads, so it must be recoverable (unlike API keys).
+                    .col(ColumnDef::new(Webhooks::Secret).string().not_null())
+                    // Comma-separated event names, e.g. "link.milestone,user.registered".
+                    .col(ColumnDef::new(Webhooks::Events).text().not_null())
+                    .col(
+                        ColumnDef::new(Webhooks::IsActive)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Webhooks::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-webhook-user_id")
+                            .from(Webhooks::Table, Webhooks::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx-webhooks-user_id")
+                    .table(Webhooks::Table)
+                    .col(Webhooks::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Webhooks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Webhooks {
+    Table,
+    Id,
+    UserId,
+    Url,
+    Secret,
+    Events,
+    IsActive,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
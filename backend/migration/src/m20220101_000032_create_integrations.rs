@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Integrations::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Integrations::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Integrations::UserId).integer().not_null())
+                    // Set for org-wide integrations; NULL for personal ones.
+                    .col(ColumnDef::new(Integrations::OrgId).integer().null())
+                    // "slack" | "discord"
+                    .col(ColumnDef::new(Integrations::Platform).string().not_null())
+                    .col(ColumnDef::new(Integrations::WebhookUrl).text().not_null())
+                    // Comma-separated event names, e.g. "link.created,link.milestone".
+                    .col(ColumnDef::new(Integrations::Events).text().not_null())
+                    .col(
+                        ColumnDef::new(Integrations::IsActive)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Integrations::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-integration-user_id")
+                            .from(Integrations::Table, Integrations::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-integration-org_id")
+                            .from(Integrations::Table, Integrations::OrgId)
+                            .to(Organizations::Table, Organizations::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx-integrations-user_id")
+                    .table(Integrations::Table)
+                    .col(Integrations::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx-integrations-org_id")
+                    .table(Integrations::Table)
+                    .col(Integrations::OrgId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Integrations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Integrations {
+    Table,
+    Id,
+    UserId,
+    OrgId,
+    Platform,
+    WebhookUrl,
+    Events,
+    IsActive,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Organizations {
+    Table,
+    Id,
+}
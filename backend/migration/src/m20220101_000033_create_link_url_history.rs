@@ -0,0 +1,92 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LinkUrlHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LinkUrlHistory::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LinkUrlHistory::LinkId).integer().not_null())
+                    .col(ColumnDef::new(LinkUrlHistory::OldUrl).text().not_null())
+                    .col(ColumnDef::new(LinkUrlHistory::NewUrl).text().not_null())
+                    // Who made the change; kept (as NULL) if that account is removed.
+                    .col(ColumnDef::new(LinkUrlHistory::ChangedBy).integer().null())
+                    .col(
+                        ColumnDef::new(LinkUrlHistory::ChangedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-link_url_history-link_id")
+                            .from(LinkUrlHistory::Table, LinkUrlHistory::LinkId)
+                            .to(Links::Table, Links::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-link_url_history-changed_by")
+                            .from(LinkUrlHistory::Table, LinkUrlHistory::ChangedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx-link_url_history-link_id")
+                    .table(LinkUrlHistory::Table)
+                    .col(LinkUrlHistory::LinkId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LinkUrlHistory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LinkUrlHistory {
+    Table,
+    Id,
+    LinkId,
+    OldUrl,
+    NewUrl,
+    ChangedBy,
+    ChangedAt,
+}
+
+#[derive(DeriveIden)]
+enum Links {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Opt-in email to the owner when a scheduled link goes live.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Links::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Links::NotifyOnStart)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Set once the activation sweep has handled the link's `starts_at`, so
+        // it is processed exactly once per schedule.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Links::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Links::StartNotified)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Links already live when this ships must not all fire at once.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE links SET start_notified = TRUE \
+                 WHERE starts_at IS NOT NULL AND starts_at <= NOW()",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for col in [Links::NotifyOnStart, Links::StartNotified] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Links::Table)
+                        .drop_column(col)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Links {
+    Table,
+    NotifyOnStart,
+    StartNotified,
+}
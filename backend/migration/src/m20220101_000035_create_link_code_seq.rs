@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Counter behind SHORT_CODE_MODE=sequential (base62-encoded into codes).
+        // Starts at 62^4 ("10000"): shorter codes fall below MIN_ALIAS_LENGTH
+        // and the 4-character floor nginx proxies to the backend.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE SEQUENCE IF NOT EXISTS link_code_seq START 14776336 MINVALUE 14776336",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP SEQUENCE IF EXISTS link_code_seq")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Channel tag captured from the short URL's query string (`?src=...`).
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClickEvents::Table)
+                    .add_column_if_not_exists(ColumnDef::new(ClickEvents::Source).string_len(64))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClickEvents::Table)
+                    .drop_column(ClickEvents::Source)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClickEvents {
+    Table,
+    Source,
+}
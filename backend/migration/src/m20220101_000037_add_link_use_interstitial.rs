@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serve a countdown landing page instead of an immediate redirect.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Links::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Links::UseInterstitial)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Links::Table)
+                    .drop_column(Links::UseInterstitial)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Links {
+    Table,
+    UseInterstitial,
+}
@@ -0,0 +1,83 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Outbox for transactional email: rows are deleted once sent, and kept
+        // with `failed_at` set once retries are exhausted.
+        manager
+            .create_table(
+                Table::create()
+                    .table(EmailQueue::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EmailQueue::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EmailQueue::Recipient).string().not_null())
+                    .col(ColumnDef::new(EmailQueue::Subject).text().not_null())
+                    .col(ColumnDef::new(EmailQueue::HtmlBody).text().not_null())
+                    .col(ColumnDef::new(EmailQueue::ReplyTo).string().null())
+                    .col(
+                        ColumnDef::new(EmailQueue::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(EmailQueue::NextAttemptAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(EmailQueue::LastError).text().null())
+                    .col(ColumnDef::new(EmailQueue::FailedAt).timestamp().null())
+                    .col(
+                        ColumnDef::new(EmailQueue::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx-email_queue-next_attempt_at")
+                    .table(EmailQueue::Table)
+                    .col(EmailQueue::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EmailQueue::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EmailQueue {
+    Table,
+    Id,
+    Recipient,
+    Subject,
+    HtmlBody,
+    ReplyTo,
+    Attempts,
+    NextAttemptAt,
+    LastError,
+    FailedAt,
+    CreatedAt,
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Optional per-link redirect budget, in requests per second.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Links::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Links::RedirectRateLimit).integer())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Links::Table)
+                    .drop_column(Links::RedirectRateLimit)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Links {
+    Table,
+    RedirectRateLimit,
+}
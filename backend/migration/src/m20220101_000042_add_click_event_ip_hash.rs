@@ -0,0 +1,37 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keyed hash of the visitor IP, so unique-visitor counts still work
+        // when STORE_RAW_IP=false leaves ip_address empty.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClickEvents::Table)
+                    .add_column_if_not_exists(ColumnDef::new(ClickEvents::IpHash).string_len(64))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClickEvents::Table)
+                    .drop_column(ClickEvents::IpHash)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClickEvents {
+    Table,
+    IpHash,
+}
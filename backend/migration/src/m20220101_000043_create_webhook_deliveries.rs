@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDeliveries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::WebhookId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WebhookDeliveries::Event).string().not_null())
+                    // The exact JSON envelope sent, so a retry replays it verbatim.
+                    .col(ColumnDef::new(WebhookDeliveries::Payload).text().not_null())
+                    // NULL when the request never got a response (DNS, TLS, timeout).
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::StatusCode)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::ResponseSnippet)
+                            .text()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(WebhookDeliveries::Error).text().null())
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::Success)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-webhook_deliveries-webhook_id")
+                            .from(WebhookDeliveries::Table, WebhookDeliveries::WebhookId)
+                            .to(Webhooks::Table, Webhooks::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx-webhook_deliveries-webhook_id")
+                    .table(WebhookDeliveries::Table)
+                    .col(WebhookDeliveries::WebhookId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WebhookDeliveries::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WebhookDeliveries {
+    Table,
+    Id,
+    WebhookId,
+    Event,
+    Payload,
+    StatusCode,
+    ResponseSnippet,
+    Error,
+    Success,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Webhooks {
+    Table,
+    Id,
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Name and logo shown in emails sent on an organization's behalf
+        // (invitations). Unset falls back to the org name / instance branding.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Organizations::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Organizations::BrandName).string_len(100),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Organizations::BrandLogoUrl).string_len(2048),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for col in [Organizations::BrandName, Organizations::BrandLogoUrl] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Organizations::Table)
+                        .drop_column(col)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Organizations {
+    Table,
+    BrandName,
+    BrandLogoUrl,
+}
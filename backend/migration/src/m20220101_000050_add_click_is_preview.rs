@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Marks events recorded for a `+` preview (COUNT_PREVIEW_AS_CLICK)
+        // rather than a redirect.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClickEvents::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(ClickEvents::IsPreview)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClickEvents::Table)
+                    .drop_column(ClickEvents::IsPreview)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClickEvents {
+    Table,
+    IsPreview,
+}
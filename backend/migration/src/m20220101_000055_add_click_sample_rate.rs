@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // How many clicks an event stands for: 1, or N when only 1-in-N events
+        // of a hot link are stored (CLICK_SAMPLING_THRESHOLD).
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClickEvents::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(ClickEvents::SampleRate)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClickEvents::Table)
+                    .drop_column(ClickEvents::SampleRate)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClickEvents {
+    Table,
+    SampleRate,
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Room for IANA zone names such as `America/Argentina/Buenos_Aires`.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .modify_column(ColumnDef::new(Users::Timezone).string_len(64).null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Zone names don't fit the old width; only offsets are kept.
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE users SET timezone = NULL WHERE length(timezone) > 6")
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .modify_column(ColumnDef::new(Users::Timezone).string_len(6).null())
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Timezone,
+}
@@ -9,6 +9,8 @@ pub struct Model {
     pub link_id: i32,
     pub created_at: DateTime,
     pub ip_address: Option<String>,
+    /// Keyed SHA-256 of the full visitor IP; set even when `ip_address` isn't.
+    pub ip_hash: Option<String>,
     pub user_agent: Option<String>,
     pub referer: Option<String>,
     pub country: Option<String>,
@@ -21,6 +23,15 @@ pub struct Model {
     pub device: Option<String>,
     pub browser: Option<String>,
     pub os: Option<String>,
+    /// Channel tag from the short URL's query string (e.g. `?src=twitter`).
+    pub source: Option<String>,
+    /// Recorded for a `+` preview (COUNT_PREVIEW_AS_CLICK), not a redirect.
+    #[sea_orm(default_value = "false")]
+    pub is_preview: bool,
+    /// Clicks this event stands for: 1, or N when the link was hot enough to
+    /// store only 1-in-N events. Sum it to extrapolate sampled analytics.
+    #[sea_orm(default_value = "1")]
+    pub sample_rate: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,27 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "email_queue")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub recipient: String,
+    pub subject: String,
+    pub html_body: String,
+    pub reply_to: Option<String>,
+    /// Delivery attempts started so far.
+    pub attempts: i32,
+    /// Not picked up before this time (retry backoff, or the lease of an
+    /// in-flight attempt).
+    pub next_attempt_at: DateTime,
+    pub last_error: Option<String>,
+    /// Set when retries are exhausted; the row is then left for inspection.
+    pub failed_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "link_url_history")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub link_id: i32,
+    pub old_url: String,
+    pub new_url: String,
+    pub changed_by: Option<i32>,
+    pub changed_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::links::Entity",
+        from = "Column::LinkId",
+        to = "super::links::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Link,
+}
+
+impl Related<super::links::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Link.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
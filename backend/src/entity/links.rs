@@ -38,6 +38,46 @@ pub struct Model {
     pub bio_visible: bool,
     pub bio_position: Option<i32>,
     pub bio_label: Option<String>,
+    // Scheduled activation: email the owner when `starts_at` passes (opt-in).
+    // `start_notified` marks the current schedule as already handled.
+    #[sea_orm(default_value = "false")]
+    pub notify_on_start: bool,
+    #[sea_orm(default_value = "false")]
+    pub start_notified: bool,
+    // Landing interstitial: serve a countdown page linking to the destination
+    // instead of an immediate redirect (also forced instance-wide by
+    // USE_INTERSTITIAL).
+    #[sea_orm(default_value = "false")]
+    pub use_interstitial: bool,
+    // Redirects allowed per second across all visitors; excess gets a 429.
+    // None = unlimited.
+    pub redirect_rate_limit: Option<i32>,
+    // Destination favicon for dashboards; filled in after create, None when it
+    // couldn't be fetched.
+    pub favicon_url: Option<String>,
+    // Taken down by an admin: answers 410 until re-enabled. The owner can't
+    // clear it, and it is separate from their own soft delete.
+    #[sea_orm(default_value = "false")]
+    pub disabled_by_admin: bool,
+    // Public preview and bio pages show a bucketed count ("100+") instead of
+    // the exact one; the owner's own views are unaffected.
+    #[sea_orm(default_value = "false")]
+    pub hide_click_count: bool,
+    // Valid for this many seconds once opened: the first click stamps
+    // `expires_at` (unless one is already set).
+    pub ttl_after_first_click_secs: Option<i32>,
+    /// Code held by `POST /links/reserve`; no destination until the owner
+    /// sets one, and never served while set.
+    #[sea_orm(default_value = "false")]
+    pub is_reserved: bool,
+    // Last destination health check: when it ran and the HTTP status it got
+    // (0 = no response). Cleared when the destination changes.
+    pub last_checked: Option<DateTime>,
+    pub last_status: Option<i32>,
+    /// sha256 of the creation token of an anonymous link, cleared once claimed.
+    pub creation_token_hash: Option<String>,
+    /// Sanitized page shown once the link has expired or hit its click cap.
+    pub expired_page_html: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -104,6 +144,18 @@ impl Related<super::link_tags::Entity> for Entity {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+/// [`Model::inactive_reason`] for a link an admin has disabled.
+pub const DISABLED_BY_ADMIN_REASON: &str = "This link has been disabled for violating our policies";
+
+/// [`Model::inactive_reason`] for a reserved code with no destination yet.
+pub const RESERVED_REASON: &str = "This link has not been set up yet";
+
+/// [`Model::inactive_reason`] for a link whose `starts_at` is still ahead.
+pub const SCHEDULED_REASON: &str = "Link is scheduled to activate later";
+
+/// [`Model::inactive_reason`] for a link past its `expires_at`.
+pub const EXPIRED_REASON: &str = "Link has expired";
+
 // Helper methods
 impl Model {
     /// Check if link is deleted (soft delete)
@@ -118,6 +170,10 @@ impl Model {
             return false;
         }
 
+        if self.disabled_by_admin || self.is_reserved {
+            return false;
+        }
+
         // A consumed one-time link is permanently inactive.
         if self.burned_at.is_some() {
             return false;
@@ -153,6 +209,14 @@ impl Model {
     pub fn inactive_reason(&self) -> Option<&'static str> {
         let now = chrono::Utc::now().naive_utc();
 
+        if self.disabled_by_admin {
+            return Some(DISABLED_BY_ADMIN_REASON);
+        }
+
+        if self.is_reserved {
+            return Some(RESERVED_REASON);
+        }
+
         // Burn state takes priority so a consumed one-time link shows the
         // dedicated message rather than the generic max-clicks one.
         if self.burned_at.is_some()
@@ -167,13 +231,13 @@ impl Model {
 
         if let Some(starts_at) = self.starts_at {
             if now < starts_at {
-                return Some("Link is scheduled to activate later");
+                return Some(SCHEDULED_REASON);
             }
         }
 
         if let Some(expires_at) = self.expires_at {
             if now > expires_at {
-                return Some("Link has expired");
+                return Some(EXPIRED_REASON);
             }
         }
 
@@ -215,6 +279,19 @@ mod burn_tests {
             bio_visible: false,
             bio_position: None,
             bio_label: None,
+            notify_on_start: false,
+            start_notified: false,
+            use_interstitial: false,
+            redirect_rate_limit: None,
+            favicon_url: None,
+            disabled_by_admin: false,
+            hide_click_count: false,
+            ttl_after_first_click_secs: None,
+            is_reserved: false,
+            last_checked: None,
+            last_status: None,
+            creation_token_hash: None,
+            expired_page_html: None,
         }
     }
 
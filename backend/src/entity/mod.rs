@@ -4,8 +4,11 @@ pub mod blocked_domains;
 pub mod blocked_email_domains;
 pub mod blocked_links;
 pub mod click_events;
+pub mod email_queue;
 pub mod folders;
+pub mod integrations;
 pub mod link_tags;
+pub mod link_url_history;
 pub mod links;
 pub mod org_members;
 pub mod organizations;
@@ -13,3 +16,5 @@ pub mod passkeys;
 pub mod routing_rules;
 pub mod tags;
 pub mod users;
+pub mod webhook_deliveries;
+pub mod webhooks;
@@ -11,6 +11,14 @@ pub struct Model {
     pub slug: String,
     pub owner_id: i32,
     pub created_at: DateTime,
+    /// Name shown in emails sent for this org; falls back to `name`.
+    pub brand_name: Option<String>,
+    /// Logo shown in emails sent for this org; none means instance branding.
+    pub brand_logo_url: Option<String>,
+    /// Prepended to codes generated for the org's links (`acme-x1Y2z3`).
+    pub code_prefix: Option<String>,
+    /// Part of the cache key of the org's QR images; bumped to invalidate them.
+    pub qr_version: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
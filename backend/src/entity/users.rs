@@ -34,6 +34,14 @@ pub struct Model {
     #[sea_orm(default_value = "false")]
     pub bio_enabled: bool,
     pub bio_theme: Option<String>,
+    /// Personal folder new links land in when created without a folder.
+    pub default_folder_id: Option<i32>,
+    /// IANA zone or UTC offset (`+HH:MM`) timestamps are shown in for
+    /// `?tz=profile`.
+    pub timezone: Option<String>,
+    /// Self-deletion waiting out ACCOUNT_DELETION_GRACE_DAYS: the account is
+    /// deleted at this time unless its owner signs in first.
+    pub deletion_scheduled_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One attempt to deliver an event to a webhook.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "webhook_deliveries")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event: String,
+    pub payload: String,
+    pub status_code: Option<i32>,
+    pub response_snippet: Option<String>,
+    pub error: Option<String>,
+    pub success: bool,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::webhooks::Entity",
+        from = "Column::WebhookId",
+        to = "super::webhooks::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Webhook,
+}
+
+impl Related<super::webhooks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhook.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
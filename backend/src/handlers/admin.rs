@@ -13,9 +13,10 @@ use std::collections::HashMap;
 use utoipa::{IntoParams, ToSchema};
 
 use crate::entity::{
-    api_keys, blocked_domains, blocked_email_domains, blocked_links, click_events, links,
-    org_members, organizations, passkeys, users,
+    api_keys, audit_log, blocked_domains, blocked_email_domains, blocked_links, click_events,
+    links, org_members, organizations, passkeys, users,
 };
+use crate::utils::backup::BackupError;
 use crate::utils::decode_jwt;
 use crate::utils::email_domain_policy::is_reserved_email_domain;
 use crate::utils::url_policy::{domain_matches, normalize_domain_input, normalize_hostname};
@@ -30,7 +31,7 @@ fn clamp_pagination(page: Option<u64>, per_page: Option<u64>) -> (u64, u64) {
 
 /// Escape LIKE/ILIKE wildcards in user-supplied search text and wrap it for a
 /// substring match.
-fn ilike_pattern(search: &str) -> String {
+pub(crate) fn ilike_pattern(search: &str) -> String {
     let escaped = search
         .replace('\\', "\\\\")
         .replace('%', "\\%")
@@ -115,6 +116,18 @@ async fn require_admin(
         )
     })?;
 
+    // Impersonation tokens act as the target user, never as an admin; this
+    // also stops an impersonated session from minting further tokens.
+    if claims.impersonated_by.is_some() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(AdminResponse {
+                success: false,
+                message: "Admin access is not available while impersonating".to_string(),
+            }),
+        ));
+    }
+
     // Check if user is admin. Exclude soft-deleted users so a deleted admin's
     // still-valid token cannot keep authorizing /admin/* actions.
     let user = users::Entity::find_by_id(claims.user_id)
@@ -1031,6 +1044,7 @@ pub async fn enable_user(
     responses(
         (status = 200, description = "Backup created successfully", body = BackupResponse),
         (status = 403, description = "Admin access required"),
+        (status = 409, description = "Another backup is already running", body = BackupResponse),
         (status = 500, description = "Backup failed"),
     ),
     tag = "Admin",
@@ -1063,6 +1077,15 @@ pub async fn create_backup(State(state): State<AppState>, headers: HeaderMap) ->
             }),
         )
             .into_response(),
+        Err(BackupError::InProgress) => (
+            StatusCode::CONFLICT,
+            Json(BackupResponse {
+                success: false,
+                filename: None,
+                message: BackupError::InProgress.to_string(),
+            }),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(BackupResponse {
@@ -1169,6 +1192,131 @@ pub async fn cleanup_backups(
     }
 }
 
+#[derive(Deserialize, ToSchema, Default)]
+pub struct ImpersonateRequest {
+    /// Why support needs the session; stored in the audit log.
+    pub reason: Option<String>,
+    /// Required to impersonate another admin.
+    pub confirm_admin: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImpersonateResponse {
+    /// Bearer token that authenticates as the target user until `expires_at`.
+    pub token: String,
+    pub user_id: i32,
+    pub email: String,
+    pub impersonated_by: i32,
+    pub expires_at: String,
+}
+
+/// Mint a short-lived token to act as a user for support (admin only, audited)
+#[utoipa::path(
+    post,
+    path = "/admin/users/{user_id}/impersonate",
+    params(
+        ("user_id" = i32, Path, description = "User ID to impersonate")
+    ),
+    request_body = ImpersonateRequest,
+    responses(
+        (status = 200, description = "Impersonation token issued", body = ImpersonateResponse),
+        (status = 400, description = "Cannot impersonate yourself, or target is an admin and confirm_admin is not set"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "User not found"),
+    ),
+    tag = "Admin",
+    security(("bearer_auth" = []))
+)]
+pub async fn impersonate_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user_id): Path<i32>,
+    payload: Option<Json<ImpersonateRequest>>,
+) -> impl IntoResponse {
+    let admin_id = match require_admin(&state, &headers).await {
+        Ok(id) => id,
+        Err(e) => return e.into_response(),
+    };
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let fail = |status: StatusCode, message: &str| {
+        (
+            status,
+            Json(AdminResponse {
+                success: false,
+                message: message.to_string(),
+            }),
+        )
+            .into_response()
+    };
+
+    if user_id == admin_id {
+        return fail(StatusCode::BAD_REQUEST, "You cannot impersonate yourself");
+    }
+
+    let target = users::Entity::find_by_id(user_id)
+        .filter(users::Column::DeletedAt.is_null())
+        .filter(users::Column::DisabledAt.is_null())
+        .one(&state.db)
+        .await
+        .unwrap_or(None);
+    let Some(target) = target else {
+        return fail(StatusCode::NOT_FOUND, "User not found");
+    };
+
+    if target.is_admin && payload.confirm_admin != Some(true) {
+        return fail(
+            StatusCode::BAD_REQUEST,
+            "Target is an admin; set confirm_admin to impersonate them",
+        );
+    }
+
+    let (token, expires_at) = match crate::utils::create_impersonation_jwt(
+        target.id,
+        &target.email,
+        target.token_version,
+        admin_id,
+    ) {
+        Ok(issued) => issued,
+        Err(_) => return fail(StatusCode::INTERNAL_SERVER_ERROR, "Failed to create token"),
+    };
+
+    let audit = audit_log::ActiveModel {
+        org_id: Set(None),
+        user_id: Set(Some(admin_id)),
+        action: Set("impersonate".to_string()),
+        resource_type: Set("user".to_string()),
+        resource_id: Set(Some(target.id)),
+        details: Set(Some(serde_json::json!({
+            "reason": payload.reason,
+            "target_is_admin": target.is_admin,
+            "expires_at": expires_at.to_rfc3339(),
+        }))),
+        ip_address: Set(crate::utils::rate_limiter::client_ip_from_headers(&headers)),
+        created_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    // No unaudited impersonation: refuse the token if the record can't be kept.
+    if audit.insert(&state.db).await.is_err() {
+        return fail(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to record audit entry",
+        );
+    }
+    tracing::warn!("Admin {} is impersonating user {}", admin_id, target.id);
+
+    (
+        StatusCode::OK,
+        Json(ImpersonateResponse {
+            token,
+            user_id: target.id,
+            email: target.email,
+            impersonated_by: admin_id,
+            expires_at: expires_at.to_rfc3339(),
+        }),
+    )
+        .into_response()
+}
+
 /// Make a user an admin (admin only)
 #[utoipa::path(
     post,
@@ -2243,6 +2391,12 @@ pub async fn get_all_users(
         return e.into_response();
     }
 
+    (StatusCode::OK, Json(list_users(&state.db, &query).await)).into_response()
+}
+
+/// One page of users matching the `/admin/users` filters, with per-user
+/// aggregates.
+async fn list_users(db: &DatabaseConnection, query: &AdminUsersQuery) -> AdminUsersListResponse {
     let (page, per_page) = clamp_pagination(query.page, query.per_page);
 
     let mut finder = users::Entity::find();
@@ -2292,7 +2446,7 @@ pub async fn get_all_users(
         _ => finder.order_by_desc(users::Column::CreatedAt),
     };
 
-    let paginator = finder.paginate(&state.db, per_page);
+    let paginator = finder.paginate(db, per_page);
     let total = paginator.num_items().await.unwrap_or(0);
     let users_page = paginator.fetch_page(page - 1).await.unwrap_or_default();
 
@@ -2315,7 +2469,7 @@ pub async fn get_all_users(
             .filter(links::Column::DeletedAt.is_null())
             .group_by(links::Column::UserId)
             .into_tuple()
-            .all(&state.db)
+            .all(db)
             .await
             .unwrap_or_default();
         for (user_id, count, clicks) in rows {
@@ -2331,7 +2485,7 @@ pub async fn get_all_users(
             .filter(api_keys::Column::UserId.is_in(user_ids.clone()))
             .group_by(api_keys::Column::UserId)
             .into_tuple()
-            .all(&state.db)
+            .all(db)
             .await
             .unwrap_or_default();
         api_key_counts.extend(rows);
@@ -2343,7 +2497,7 @@ pub async fn get_all_users(
             .filter(passkeys::Column::UserId.is_in(user_ids.clone()))
             .group_by(passkeys::Column::UserId)
             .into_tuple()
-            .all(&state.db)
+            .all(db)
             .await
             .unwrap_or_default();
         passkey_counts.extend(rows);
@@ -2355,7 +2509,7 @@ pub async fn get_all_users(
             .filter(organizations::Column::OwnerId.is_in(user_ids.clone()))
             .group_by(organizations::Column::OwnerId)
             .into_tuple()
-            .all(&state.db)
+            .all(db)
             .await
             .unwrap_or_default();
         orgs_owned_counts.extend(rows);
@@ -2387,16 +2541,12 @@ pub async fn get_all_users(
         })
         .collect();
 
-    (
-        StatusCode::OK,
-        Json(AdminUsersListResponse {
-            users: responses,
-            total,
-            page,
-            per_page,
-        }),
-    )
-        .into_response()
+    AdminUsersListResponse {
+        users: responses,
+        total,
+        page,
+        per_page,
+    }
 }
 
 // ==================== ADMIN: ALL LINKS ====================
@@ -2409,7 +2559,7 @@ pub struct AdminLinksQuery {
     pub per_page: Option<u64>,
     /// Substring match on code, destination URL, title, or owner email
     pub search: Option<String>,
-    /// Filter: all (default) | live | deleted
+    /// Filter: all (default) | live | deleted | disabled
     pub status: Option<String>,
     /// Only links belonging to this user
     pub user_id: Option<i32>,
@@ -2445,6 +2595,8 @@ pub struct AdminLinkResponse {
     pub safe_link_interstitial: bool,
     pub bio_visible: bool,
     pub has_password: bool,
+    /// Taken down via `POST /admin/links/{id}/disable`.
+    pub disabled_by_admin: bool,
     pub is_active: bool,
     pub inactive_reason: Option<String>,
     /// True when the destination trips an abuse heuristic (dangerous file type
@@ -2454,6 +2606,41 @@ pub struct AdminLinkResponse {
     pub suspicion_reason: Option<String>,
 }
 
+impl AdminLinkResponse {
+    fn new(link: links::Model, owner: Option<users::Model>) -> Self {
+        let is_active = link.is_active();
+        let inactive_reason = link.inactive_reason().map(str::to_string);
+        let suspicion_reason = crate::utils::url_policy::suspicion_reason(&link.original_url);
+        Self {
+            id: link.id,
+            code: link.code,
+            original_url: link.original_url,
+            title: link.title,
+            user_id: link.user_id,
+            user_email: owner.map(|u| u.email),
+            org_id: link.org_id,
+            folder_id: link.folder_id,
+            click_count: link.click_count,
+            max_clicks: link.max_clicks,
+            created_at: link.created_at.to_string(),
+            starts_at: link.starts_at.map(|d| d.to_string()),
+            expires_at: link.expires_at.map(|d| d.to_string()),
+            deleted_at: link.deleted_at.map(|d| d.to_string()),
+            burned_at: link.burned_at.map(|d| d.to_string()),
+            is_pinned: link.is_pinned,
+            burn_after_reading: link.burn_after_reading,
+            safe_link_interstitial: link.safe_link_interstitial,
+            bio_visible: link.bio_visible,
+            has_password: link.password_hash.is_some(),
+            disabled_by_admin: link.disabled_by_admin,
+            is_active,
+            inactive_reason,
+            suspicious: suspicion_reason.is_some(),
+            suspicion_reason,
+        }
+    }
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct AdminLinksListResponse {
     pub links: Vec<AdminLinkResponse>,
@@ -2483,6 +2670,11 @@ pub async fn get_all_links(
         return e.into_response();
     }
 
+    (StatusCode::OK, Json(list_links(&state.db, &query).await)).into_response()
+}
+
+/// One page of links matching the `/admin/links` filters, with their owners.
+async fn list_links(db: &DatabaseConnection, query: &AdminLinksQuery) -> AdminLinksListResponse {
     let (page, per_page) = clamp_pagination(query.page, query.per_page);
 
     // Join the owner so search can match their email and the response can
@@ -2492,6 +2684,7 @@ pub async fn get_all_links(
     match query.status.as_deref() {
         Some("live") => finder = finder.filter(links::Column::DeletedAt.is_null()),
         Some("deleted") => finder = finder.filter(links::Column::DeletedAt.is_not_null()),
+        Some("disabled") => finder = finder.filter(links::Column::DisabledByAdmin.eq(true)),
         _ => {}
     }
 
@@ -2527,52 +2720,277 @@ pub async fn get_all_links(
         (_, true) => finder.order_by_desc(links::Column::CreatedAt),
     };
 
-    let paginator = finder.paginate(&state.db, per_page);
+    let paginator = finder.paginate(db, per_page);
     let total = paginator.num_items().await.unwrap_or(0);
     let rows = paginator.fetch_page(page - 1).await.unwrap_or_default();
 
     let responses: Vec<AdminLinkResponse> = rows
         .into_iter()
-        .map(|(link, owner)| {
-            let is_active = link.is_active();
-            let inactive_reason = link.inactive_reason().map(str::to_string);
-            let suspicion_reason = crate::utils::url_policy::suspicion_reason(&link.original_url);
-            AdminLinkResponse {
+        .map(|(link, owner)| AdminLinkResponse::new(link, owner))
+        .collect();
+
+    AdminLinksListResponse {
+        links: responses,
+        total,
+        page,
+        per_page,
+    }
+}
+
+// ==================== ADMIN: SEARCH ====================
+
+#[derive(Deserialize, IntoParams)]
+pub struct AdminSearchQuery {
+    /// Text matched the same way as the `search` filter of `/admin/users` and
+    /// `/admin/links`
+    pub q: Option<String>,
+    /// 1-based page of matching users (default 1)
+    pub users_page: Option<u64>,
+    /// 1-based page of matching links (default 1)
+    pub links_page: Option<u64>,
+    /// Items per page for each type, 1-100 (default 25)
+    pub per_page: Option<u64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AdminSearchResponse {
+    pub users: AdminUsersListResponse,
+    pub links: AdminLinksListResponse,
+}
+
+/// Search users and links from one box (admin only)
+#[utoipa::path(
+    get,
+    path = "/admin/search",
+    params(AdminSearchQuery),
+    responses(
+        (status = 200, description = "Matching users and links, each paginated", body = AdminSearchResponse),
+        (status = 400, description = "Empty query"),
+        (status = 403, description = "Admin access required"),
+    ),
+    tag = "Admin",
+    security(("bearer_auth" = []))
+)]
+pub async fn admin_search(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AdminSearchQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_admin(&state, &headers).await {
+        return e.into_response();
+    }
+
+    let Some(q) = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(AdminResponse {
+                success: false,
+                message: "Search query is required".to_string(),
+            }),
+        )
+            .into_response();
+    };
+
+    let users_query = AdminUsersQuery {
+        page: query.users_page,
+        per_page: query.per_page,
+        search: Some(q.to_string()),
+        status: None,
+        order: None,
+    };
+    let links_query = AdminLinksQuery {
+        page: query.links_page,
+        per_page: query.per_page,
+        search: Some(q.to_string()),
+        status: None,
+        user_id: None,
+        sort: None,
+        order: None,
+        suspicious: None,
+    };
+    let (users, links) = tokio::join!(
+        list_users(&state.db, &users_query),
+        list_links(&state.db, &links_query)
+    );
+
+    (StatusCode::OK, Json(AdminSearchResponse { users, links })).into_response()
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct AdminExpiredLinksQuery {
+    /// 1-based page number (default 1)
+    pub page: Option<u64>,
+    /// Items per page, 1-100 (default 25)
+    pub per_page: Option<u64>,
+}
+
+/// List live links whose expiry has passed, most recently expired first (admin only)
+#[utoipa::path(
+    get,
+    path = "/admin/links/expired",
+    params(AdminExpiredLinksQuery),
+    responses(
+        (status = 200, description = "Paginated list of expired links", body = AdminLinksListResponse),
+        (status = 403, description = "Admin access required"),
+    ),
+    tag = "Admin",
+    security(("bearer_auth" = []))
+)]
+pub async fn get_expired_links(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AdminExpiredLinksQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_admin(&state, &headers).await {
+        return e.into_response();
+    }
+
+    let (page, per_page) = clamp_pagination(query.page, query.per_page);
+    let paginator = crate::utils::link_expiry::expired_links_query(Utc::now().naive_utc())
+        .find_also_related(users::Entity)
+        .paginate(&state.db, per_page);
+    let total = paginator.num_items().await.unwrap_or(0);
+    let rows = paginator.fetch_page(page - 1).await.unwrap_or_default();
+
+    (
+        StatusCode::OK,
+        Json(AdminLinksListResponse {
+            links: rows
+                .into_iter()
+                .map(|(link, owner)| AdminLinkResponse::new(link, owner))
+                .collect(),
+            total,
+            page,
+            per_page,
+        }),
+    )
+        .into_response()
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct AdminTopLinksQuery {
+    /// Number of links, 1-100 (default 10)
+    pub limit: Option<u64>,
+    /// Only count clicks from this time on (RFC 3339). Without it links are
+    /// ranked by their lifetime click count.
+    pub since: Option<chrono::DateTime<Utc>>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AdminTopLinkResponse {
+    pub id: i32,
+    pub code: String,
+    pub original_url: String,
+    pub destination_host: Option<String>,
+    pub user_id: Option<i32>,
+    pub owner_email: Option<String>,
+    /// Clicks in the requested window, or lifetime clicks without `since`.
+    /// Windowed counts are estimated from stored events for sampled links.
+    pub clicks: i64,
+    pub click_count: i32,
+    pub disabled_by_admin: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AdminTopLinksResponse {
+    pub since: Option<String>,
+    pub links: Vec<AdminTopLinkResponse>,
+}
+
+/// Most-clicked live links across all users, for abuse monitoring (admin only)
+#[utoipa::path(
+    get,
+    path = "/admin/links/top",
+    params(AdminTopLinksQuery),
+    responses(
+        (status = 200, description = "Links with the most clicks, most clicked first", body = AdminTopLinksResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 403, description = "Admin access required"),
+    ),
+    tag = "Admin",
+    security(("bearer_auth" = []))
+)]
+pub async fn get_top_links(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AdminTopLinksQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_admin(&state, &headers).await {
+        return e.into_response();
+    }
+
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let ranked: Vec<(i32, i64)> = match query.since {
+        // Sampled events stand for `sample_rate` clicks each.
+        Some(since) => state
+            .db
+            .query_all(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "SELECT ce.link_id, SUM(ce.sample_rate)::bigint AS clicks \
+                 FROM click_events ce JOIN links l ON l.id = ce.link_id \
+                 WHERE ce.created_at >= $1 AND l.deleted_at IS NULL \
+                 GROUP BY ce.link_id ORDER BY clicks DESC, ce.link_id LIMIT $2",
+                [since.naive_utc().into(), (limit as i64).into()],
+            ))
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|row| {
+                Some((
+                    row.try_get::<i32>("", "link_id").ok()?,
+                    row.try_get::<i64>("", "clicks").ok()?,
+                ))
+            })
+            .collect(),
+        None => links::Entity::find()
+            .filter(links::Column::DeletedAt.is_null())
+            .order_by_desc(links::Column::ClickCount)
+            .order_by_asc(links::Column::Id)
+            .limit(limit)
+            .all(&state.db)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|link| (link.id, link.click_count as i64))
+            .collect(),
+    };
+
+    let mut rows: HashMap<i32, (links::Model, Option<users::Model>)> = links::Entity::find()
+        .filter(links::Column::Id.is_in(ranked.iter().map(|(id, _)| *id)))
+        .find_also_related(users::Entity)
+        .all(&state.db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(link, owner)| (link.id, (link, owner)))
+        .collect();
+
+    let links = ranked
+        .into_iter()
+        .filter_map(|(id, clicks)| {
+            let (link, owner) = rows.remove(&id)?;
+            let destination_host = url::Url::parse(&link.original_url)
+                .ok()
+                .and_then(|u| u.host_str().and_then(normalize_hostname));
+            Some(AdminTopLinkResponse {
                 id: link.id,
                 code: link.code,
                 original_url: link.original_url,
-                title: link.title,
+                destination_host,
                 user_id: link.user_id,
-                user_email: owner.map(|u| u.email),
-                org_id: link.org_id,
-                folder_id: link.folder_id,
+                owner_email: owner.map(|u| u.email),
+                clicks,
                 click_count: link.click_count,
-                max_clicks: link.max_clicks,
-                created_at: link.created_at.to_string(),
-                starts_at: link.starts_at.map(|d| d.to_string()),
-                expires_at: link.expires_at.map(|d| d.to_string()),
-                deleted_at: link.deleted_at.map(|d| d.to_string()),
-                burned_at: link.burned_at.map(|d| d.to_string()),
-                is_pinned: link.is_pinned,
-                burn_after_reading: link.burn_after_reading,
-                safe_link_interstitial: link.safe_link_interstitial,
-                bio_visible: link.bio_visible,
-                has_password: link.password_hash.is_some(),
-                is_active,
-                inactive_reason,
-                suspicious: suspicion_reason.is_some(),
-                suspicion_reason,
-            }
+                disabled_by_admin: link.disabled_by_admin,
+            })
         })
         .collect();
 
     (
         StatusCode::OK,
-        Json(AdminLinksListResponse {
-            links: responses,
-            total,
-            page,
-            per_page,
+        Json(AdminTopLinksResponse {
+            since: query.since.map(|d| d.to_rfc3339()),
+            links,
         }),
     )
         .into_response()
@@ -2737,6 +3155,135 @@ pub async fn admin_restore_link(
         .into_response()
 }
 
+/// Disable a link without deleting it (admin only). It answers 410 with a
+/// policy reason until re-enabled; the owner still sees it in their dashboard.
+#[utoipa::path(
+    post,
+    path = "/admin/links/{link_id}/disable",
+    params(
+        ("link_id" = i32, Path, description = "Link ID to disable")
+    ),
+    responses(
+        (status = 200, description = "Link disabled", body = AdminResponse),
+        (status = 400, description = "Link is already disabled"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Link not found"),
+    ),
+    tag = "Admin",
+    security(("bearer_auth" = []))
+)]
+pub async fn admin_disable_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(link_id): Path<i32>,
+) -> impl IntoResponse {
+    set_link_disabled(&state, &headers, link_id, true).await
+}
+
+/// Re-enable a link previously disabled by an admin (admin only)
+#[utoipa::path(
+    post,
+    path = "/admin/links/{link_id}/enable",
+    params(
+        ("link_id" = i32, Path, description = "Link ID to enable")
+    ),
+    responses(
+        (status = 200, description = "Link enabled", body = AdminResponse),
+        (status = 400, description = "Link is not disabled"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Link not found"),
+    ),
+    tag = "Admin",
+    security(("bearer_auth" = []))
+)]
+pub async fn admin_enable_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(link_id): Path<i32>,
+) -> impl IntoResponse {
+    set_link_disabled(&state, &headers, link_id, false).await
+}
+
+async fn set_link_disabled(
+    state: &AppState,
+    headers: &HeaderMap,
+    link_id: i32,
+    disabled: bool,
+) -> axum::response::Response {
+    let admin_id = match require_admin(state, headers).await {
+        Ok(id) => id,
+        Err(e) => return e.into_response(),
+    };
+    let fail = |status: StatusCode, message: &str| {
+        (
+            status,
+            Json(AdminResponse {
+                success: false,
+                message: message.to_string(),
+            }),
+        )
+            .into_response()
+    };
+
+    let link = links::Entity::find_by_id(link_id)
+        .one(&state.db)
+        .await
+        .unwrap_or(None);
+    let Some(link) = link else {
+        return fail(StatusCode::NOT_FOUND, "Link not found");
+    };
+    if link.disabled_by_admin == disabled {
+        let message = if disabled {
+            "Link is already disabled"
+        } else {
+            "Link is not disabled"
+        };
+        return fail(StatusCode::BAD_REQUEST, message);
+    }
+
+    let code = link.code.clone();
+    let mut active: links::ActiveModel = link.into();
+    active.disabled_by_admin = Set(disabled);
+    if active.update(&state.db).await.is_err() {
+        return fail(StatusCode::INTERNAL_SERVER_ERROR, "Failed to update link");
+    }
+
+    // A cached redirect would keep serving the link until its TTL runs out.
+    if let Some(cache) = &state.redis_cache {
+        let _ = cache.invalidate_link(&code).await;
+    }
+
+    let action = if disabled {
+        "link_disabled"
+    } else {
+        "link_enabled"
+    };
+    let audit = audit_log::ActiveModel {
+        org_id: Set(None),
+        user_id: Set(Some(admin_id)),
+        action: Set(action.to_string()),
+        resource_type: Set("link".to_string()),
+        resource_id: Set(Some(link_id)),
+        details: Set(Some(serde_json::json!({ "code": code }))),
+        ip_address: Set(crate::utils::rate_limiter::client_ip_from_headers(headers)),
+        created_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    if let Err(e) = audit.insert(&state.db).await {
+        tracing::error!("Failed to audit {} of link {}: {}", action, link_id, e);
+    }
+
+    let verb = if disabled { "disabled" } else { "enabled" };
+    (
+        StatusCode::OK,
+        Json(AdminResponse {
+            success: true,
+            message: format!("Link {} {}", code, verb),
+        }),
+    )
+        .into_response()
+}
+
 #[derive(Deserialize, ToSchema)]
 pub struct BulkLinkIdsRequest {
     /// Link IDs to act on.
@@ -2758,7 +3305,7 @@ pub struct BulkLinkActionResponse {
     request_body = BulkLinkIdsRequest,
     responses(
         (status = 200, description = "Links deleted", body = BulkLinkActionResponse),
-        (status = 400, description = "No IDs provided"),
+        (status = 400, description = "No IDs, too many, or invalid/duplicate IDs"),
         (status = 403, description = "Admin access required"),
     ),
     tag = "Admin",
@@ -2772,12 +3319,15 @@ pub async fn admin_bulk_delete_links(
     if let Err(e) = require_admin(&state, &headers).await {
         return e.into_response();
     }
-    if payload.ids.is_empty() {
+    if let Err(message) = crate::handlers::links::validate_bulk_ids(
+        &payload.ids,
+        crate::handlers::links::max_bulk_size(),
+    ) {
         return (
             StatusCode::BAD_REQUEST,
             Json(AdminResponse {
                 success: false,
-                message: "No link IDs provided".to_string(),
+                message,
             }),
         )
             .into_response();
@@ -2838,7 +3388,7 @@ pub async fn admin_bulk_delete_links(
     request_body = BulkLinkIdsRequest,
     responses(
         (status = 200, description = "Links restored", body = BulkLinkActionResponse),
-        (status = 400, description = "No IDs provided"),
+        (status = 400, description = "No IDs, too many, or invalid/duplicate IDs"),
         (status = 403, description = "Admin access required"),
     ),
     tag = "Admin",
@@ -2852,12 +3402,15 @@ pub async fn admin_bulk_restore_links(
     if let Err(e) = require_admin(&state, &headers).await {
         return e.into_response();
     }
-    if payload.ids.is_empty() {
+    if let Err(message) = crate::handlers::links::validate_bulk_ids(
+        &payload.ids,
+        crate::handlers::links::max_bulk_size(),
+    ) {
         return (
             StatusCode::BAD_REQUEST,
             Json(AdminResponse {
                 success: false,
-                message: "No link IDs provided".to_string(),
+                message,
             }),
         )
             .into_response();
@@ -11,6 +11,7 @@ use utoipa::ToSchema;
 
 use crate::entity::{click_events, links};
 use crate::handlers::links::get_user_id_from_header;
+pub use crate::utils::timezone::parse_utc_offset;
 use crate::AppState;
 
 /// Aggregated geo bucket value: (latitude, longitude, city, country, hit count).
@@ -21,6 +22,10 @@ type GeoAggregate = (f64, f64, Option<String>, Option<String>, i64);
 #[derive(Deserialize, ToSchema, utoipa::IntoParams)]
 pub struct AnalyticsQuery {
     pub days: Option<i64>,
+    /// `profile` for the timezone saved on your profile, a timezone such as
+    /// `Europe/Paris` or a UTC offset such as `+02:00`; applies to click
+    /// timestamps (default UTC)
+    pub tz: Option<String>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -32,12 +37,17 @@ pub struct LinkStatsResponse {
     pub unique_visitors: i32,
     pub clicks_by_day: Vec<DayStats>,
     pub clicks_by_country: Vec<CountryStats>,
+    /// Empty unless GEO_PRECISION is `city`
     pub clicks_by_city: Vec<CityStats>,
     pub clicks_by_device: Vec<DeviceStats>,
     pub clicks_by_browser: Vec<BrowserStats>,
     pub clicks_by_os: Vec<OsStats>,
     pub clicks_by_referer: Vec<RefererStats>,
+    /// Clicks split by the short URL's source query param (`?src=...`),
+    /// most clicks first.
+    pub clicks_by_source: Vec<SourceStats>,
     pub recent_clicks: Vec<RecentClick>,
+    /// Empty unless GEO_PRECISION is `city`
     pub geo_data: Vec<GeoPoint>,
 }
 
@@ -90,6 +100,14 @@ pub struct RefererStats {
     pub percentage: f64,
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct SourceStats {
+    /// Source tag, or "Untagged" for clicks without one
+    pub source: String,
+    pub count: i64,
+    pub percentage: f64,
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct RecentClick {
     pub id: i32,
@@ -100,6 +118,71 @@ pub struct RecentClick {
     pub browser: Option<String>,
     pub os: Option<String>,
     pub referer: Option<String>,
+    pub source: Option<String>,
+    /// Recorded for a link preview rather than a redirect
+    pub is_preview: bool,
+    /// Clicks this event stands for (more than 1 when sampled)
+    pub sample_rate: i32,
+}
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+pub struct ClickEventsQuery {
+    /// Page size (default 50, max 500)
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ClickEventsPage {
+    /// Total click events recorded for the link
+    pub total: u64,
+    /// Clicks those events stand for once sampling is accounted for
+    pub total_clicks: i64,
+    pub limit: u64,
+    pub offset: u64,
+    /// Newest first
+    pub clicks: Vec<RecentClick>,
+}
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+pub struct HeatmapQuery {
+    /// UTC offset to bucket clicks in, e.g. `+02:00`, `-0530` or `UTC` (default UTC)
+    pub tz: Option<String>,
+    /// How far back to look (default 30 days)
+    pub days: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct HeatmapResponse {
+    pub link_id: i32,
+    /// The offset the buckets were computed in, as `+HH:MM`
+    pub tz: String,
+    pub days: i64,
+    pub total: i64,
+    /// `matrix[weekday][hour]`, weekday 0 = Monday, hour 0-23 local to `tz`
+    pub matrix: Vec<Vec<i64>>,
+}
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+pub struct CompareQuery {
+    /// `day`, `week` or `month` (default `week`)
+    pub period: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PeriodComparisonResponse {
+    pub link_id: i32,
+    pub period: String,
+    /// Start of the current period; it runs up to now
+    pub current_start: chrono::NaiveDateTime,
+    /// Start of the previous period, which ends at `current_start`
+    pub previous_start: chrono::NaiveDateTime,
+    pub current_clicks: i64,
+    pub previous_clicks: i64,
+    /// `current_clicks - previous_clicks`
+    pub change: i64,
+    /// Percentage change from the previous period, `null` when it had no clicks
+    pub change_percent: Option<f64>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -135,6 +218,25 @@ pub struct TopLink {
 
 // ============= Handlers =============
 
+/// Owner of the link, or a member of the org that owns it.
+async fn can_view_link(db: &DatabaseConnection, link: &links::Model, user_id: i32) -> bool {
+    if link.user_id == Some(user_id) {
+        return true;
+    }
+    let Some(org_id) = link.org_id else {
+        return false;
+    };
+    use crate::entity::org_members;
+    org_members::Entity::find()
+        .filter(org_members::Column::OrgId.eq(org_id))
+        .filter(org_members::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .is_some()
+}
+
 /// Get detailed stats for a specific link
 #[utoipa::path(
     get,
@@ -145,6 +247,7 @@ pub struct TopLink {
     ),
     responses(
         (status = 200, description = "Link statistics", body = LinkStatsResponse),
+        (status = 400, description = "Invalid timezone"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
         (status = 404, description = "Not found"),
@@ -183,25 +286,7 @@ pub async fn get_link_stats(
         }
     };
 
-    // Check ownership - must own the link directly, or be member of the org that owns it
-    let has_access = if link.user_id == Some(user_id) {
-        true
-    } else if let Some(org_id) = link.org_id {
-        // Check if user is member of this organization
-        use crate::entity::org_members;
-        org_members::Entity::find()
-            .filter(org_members::Column::OrgId.eq(org_id))
-            .filter(org_members::Column::UserId.eq(user_id))
-            .one(&state.db)
-            .await
-            .ok()
-            .flatten()
-            .is_some()
-    } else {
-        false
-    };
-
-    if !has_access {
+    if !can_view_link(&state.db, &link, user_id).await {
         return (
             StatusCode::FORBIDDEN,
             Json(serde_json::json!({"error": "Access denied"})),
@@ -209,6 +294,18 @@ pub async fn get_link_stats(
             .into_response();
     }
 
+    let tz = match crate::utils::timezone::resolve_tz(&state.db, user_id, query.tz.as_deref()).await
+    {
+        Ok(tz) => tz,
+        Err(error) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": error })),
+            )
+                .into_response()
+        }
+    };
+
     // Get time range
     let days = query.days.unwrap_or(30);
     let start_date = chrono::Utc::now().naive_utc() - chrono::Duration::days(days);
@@ -222,20 +319,23 @@ pub async fn get_link_stats(
         .await
         .unwrap_or_default();
 
-    let total_clicks = events.len() as i32;
+    // A sampled event stands for `sample_rate` clicks.
+    let total_clicks: i32 = events.iter().map(|e| e.sample_rate).sum();
     // Prevent division by zero - use 1 as minimum for percentage calculations
     let total_for_percentage = total_clicks.max(1) as f64;
 
-    // Unique visitors (by IP)
-    let unique_ips: std::collections::HashSet<_> =
-        events.iter().filter_map(|e| e.ip_address.clone()).collect();
+    // Unique visitors (by IP, or its hash when STORE_RAW_IP=false)
+    let unique_ips: std::collections::HashSet<_> = events
+        .iter()
+        .filter_map(|e| e.ip_address.clone().or_else(|| e.ip_hash.clone()))
+        .collect();
     let unique_visitors = unique_ips.len() as i32;
 
     // Clicks by day
     let mut clicks_by_day_map: HashMap<String, i64> = HashMap::new();
     for event in &events {
         let date = event.created_at.format("%Y-%m-%d").to_string();
-        *clicks_by_day_map.entry(date).or_insert(0) += 1;
+        *clicks_by_day_map.entry(date).or_insert(0) += event.sample_rate as i64;
     }
     let mut clicks_by_day: Vec<DayStats> = clicks_by_day_map
         .into_iter()
@@ -250,7 +350,7 @@ pub async fn get_link_stats(
             .country
             .clone()
             .unwrap_or_else(|| "Unknown".to_string());
-        *country_map.entry(country).or_insert(0) += 1;
+        *country_map.entry(country).or_insert(0) += event.sample_rate as i64;
     }
     let clicks_by_country: Vec<CountryStats> = country_map
         .into_iter()
@@ -261,12 +361,18 @@ pub async fn get_link_stats(
         })
         .collect();
 
+    // City buckets and map points only exist at GEO_PRECISION=city; below
+    // it, events recorded before the setting changed aren't broken down
+    // further than new ones can be.
+    let city_level =
+        crate::utils::geoip::geo_precision() == crate::utils::geoip::GeoPrecision::City;
+
     // Clicks by city
     let mut city_map: HashMap<String, (i64, Option<String>)> = HashMap::new();
-    for event in &events {
+    for event in events.iter().filter(|_| city_level) {
         let city = event.city.clone().unwrap_or_else(|| "Unknown".to_string());
         let entry = city_map.entry(city).or_insert((0, event.country.clone()));
-        entry.0 += 1;
+        entry.0 += event.sample_rate as i64;
     }
     let clicks_by_city: Vec<CityStats> = city_map
         .into_iter()
@@ -285,7 +391,7 @@ pub async fn get_link_stats(
             .device
             .clone()
             .unwrap_or_else(|| "Unknown".to_string());
-        *device_map.entry(device).or_insert(0) += 1;
+        *device_map.entry(device).or_insert(0) += event.sample_rate as i64;
     }
     let clicks_by_device: Vec<DeviceStats> = device_map
         .into_iter()
@@ -303,7 +409,7 @@ pub async fn get_link_stats(
             .browser
             .clone()
             .unwrap_or_else(|| "Unknown".to_string());
-        *browser_map.entry(browser).or_insert(0) += 1;
+        *browser_map.entry(browser).or_insert(0) += event.sample_rate as i64;
     }
     let clicks_by_browser: Vec<BrowserStats> = browser_map
         .into_iter()
@@ -318,7 +424,7 @@ pub async fn get_link_stats(
     let mut os_map: HashMap<String, i64> = HashMap::new();
     for event in &events {
         let os = event.os.clone().unwrap_or_else(|| "Unknown".to_string());
-        *os_map.entry(os).or_insert(0) += 1;
+        *os_map.entry(os).or_insert(0) += event.sample_rate as i64;
     }
     let clicks_by_os: Vec<OsStats> = os_map
         .into_iter()
@@ -337,7 +443,7 @@ pub async fn get_link_stats(
             .clone()
             .map(|r| extract_domain(&r).unwrap_or_else(|| r.clone()))
             .unwrap_or_else(|| "Direct".to_string());
-        *referer_map.entry(referer).or_insert(0) += 1;
+        *referer_map.entry(referer).or_insert(0) += event.sample_rate as i64;
     }
     let clicks_by_referer: Vec<RefererStats> = referer_map
         .into_iter()
@@ -348,25 +454,47 @@ pub async fn get_link_stats(
         })
         .collect();
 
+    // Clicks by source tag
+    let mut source_map: HashMap<String, i64> = HashMap::new();
+    for event in &events {
+        let source = event
+            .source
+            .clone()
+            .unwrap_or_else(|| "Untagged".to_string());
+        *source_map.entry(source).or_insert(0) += event.sample_rate as i64;
+    }
+    let mut clicks_by_source: Vec<SourceStats> = source_map
+        .into_iter()
+        .map(|(source, count)| SourceStats {
+            source,
+            count,
+            percentage: (count as f64 / total_for_percentage) * 100.0,
+        })
+        .collect();
+    clicks_by_source.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.source.cmp(&b.source)));
+
     // Recent clicks (last 100)
     let recent_clicks: Vec<RecentClick> = events
         .iter()
         .take(100)
         .map(|e| RecentClick {
             id: e.id,
-            timestamp: e.created_at.to_string(),
+            timestamp: crate::utils::timezone::format_timestamp(e.created_at, tz),
             country: e.country.clone(),
-            city: e.city.clone(),
+            city: e.city.clone().filter(|_| city_level),
             device: e.device.clone(),
             browser: e.browser.clone(),
             os: e.os.clone(),
             referer: e.referer.clone(),
+            source: e.source.clone(),
+            is_preview: e.is_preview,
+            sample_rate: e.sample_rate,
         })
         .collect();
 
     // Geo data for map
     let mut geo_map: HashMap<(i64, i64), GeoAggregate> = HashMap::new();
-    for event in &events {
+    for event in events.iter().filter(|_| city_level) {
         if let (Some(lat), Some(lon)) = (event.latitude, event.longitude) {
             // Round to 2 decimal places for clustering
             let key = ((lat * 100.0) as i64, (lon * 100.0) as i64);
@@ -377,7 +505,7 @@ pub async fn get_link_stats(
                 event.country.clone(),
                 0,
             ));
-            entry.4 += 1;
+            entry.4 += event.sample_rate as i64;
         }
     }
     let geo_data: Vec<GeoPoint> = geo_map
@@ -404,6 +532,7 @@ pub async fn get_link_stats(
         clicks_by_browser,
         clicks_by_os,
         clicks_by_referer,
+        clicks_by_source,
         recent_clicks,
         geo_data,
     };
@@ -464,13 +593,18 @@ pub async fn get_dashboard_stats(
         .await
         .unwrap_or_default();
 
-    // Calculate time-based stats
-    let clicks_today = events
-        .iter()
-        .filter(|e| e.created_at >= today_start)
-        .count() as i64;
-    let clicks_this_week = events.iter().filter(|e| e.created_at >= week_start).count() as i64;
-    let clicks_this_month = events.len() as i64;
+    // Calculate time-based stats, sampled events standing for `sample_rate`
+    // clicks each
+    let clicks_since = |start: chrono::NaiveDateTime| -> i64 {
+        events
+            .iter()
+            .filter(|e| e.created_at >= start)
+            .map(|e| e.sample_rate as i64)
+            .sum()
+    };
+    let clicks_today = clicks_since(today_start);
+    let clicks_this_week = clicks_since(week_start);
+    let clicks_this_month = clicks_since(month_start);
 
     // Top links
     let mut top_links: Vec<TopLink> = user_links
@@ -489,7 +623,7 @@ pub async fn get_dashboard_stats(
     let mut clicks_by_day_map: HashMap<String, i64> = HashMap::new();
     for event in &events {
         let date = event.created_at.format("%Y-%m-%d").to_string();
-        *clicks_by_day_map.entry(date).or_insert(0) += 1;
+        *clicks_by_day_map.entry(date).or_insert(0) += event.sample_rate as i64;
     }
     let mut clicks_by_day: Vec<DayStats> = clicks_by_day_map
         .into_iter()
@@ -504,9 +638,9 @@ pub async fn get_dashboard_stats(
             .country
             .clone()
             .unwrap_or_else(|| "Unknown".to_string());
-        *country_map.entry(country).or_insert(0) += 1;
+        *country_map.entry(country).or_insert(0) += event.sample_rate as i64;
     }
-    let total_for_percentage = events.len().max(1) as f64;
+    let total_for_percentage = clicks_this_month.max(1) as f64;
     let mut top_countries: Vec<CountryStats> = country_map
         .into_iter()
         .map(|(country, count)| CountryStats {
@@ -525,7 +659,7 @@ pub async fn get_dashboard_stats(
             .browser
             .clone()
             .unwrap_or_else(|| "Unknown".to_string());
-        *browser_map.entry(browser).or_insert(0) += 1;
+        *browser_map.entry(browser).or_insert(0) += event.sample_rate as i64;
     }
     let mut top_browsers: Vec<BrowserStats> = browser_map
         .into_iter()
@@ -598,24 +732,7 @@ pub async fn get_realtime_clicks(
         }
     };
 
-    // Ownership: must own the link, or be a member of the org that owns it.
-    let has_access = if link.user_id == Some(user_id) {
-        true
-    } else if let Some(org_id) = link.org_id {
-        use crate::entity::org_members;
-        org_members::Entity::find()
-            .filter(org_members::Column::OrgId.eq(org_id))
-            .filter(org_members::Column::UserId.eq(user_id))
-            .one(&state.db)
-            .await
-            .ok()
-            .flatten()
-            .is_some()
-    } else {
-        false
-    };
-
-    if !has_access {
+    if !can_view_link(&state.db, &link, user_id).await {
         return (
             StatusCode::FORBIDDEN,
             Json(serde_json::json!({"error": "Access denied"})),
@@ -634,6 +751,360 @@ pub async fn get_realtime_clicks(
         .into_response()
 }
 
+/// Page through a link's raw click events, newest first
+#[utoipa::path(
+    get,
+    path = "/links/{id}/clicks",
+    params(
+        ("id" = i32, Path, description = "Link ID"),
+        ClickEventsQuery
+    ),
+    responses(
+        (status = 200, description = "Click events, newest first", body = ClickEventsPage),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Link not found"),
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_click_events(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+    Query(query): Query<ClickEventsQuery>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_from_header(&state.db, &headers).await {
+        Some(id) => id,
+        None => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+                .into_response()
+        }
+    };
+
+    let link = match links::Entity::find_by_id(id)
+        .filter(links::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await
+    {
+        Ok(Some(link)) => link,
+        _ => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Link not found"})),
+            )
+                .into_response()
+        }
+    };
+
+    if !can_view_link(&state.db, &link, user_id).await {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({"error": "Access denied"})),
+        )
+            .into_response();
+    }
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let offset = query.offset.unwrap_or(0);
+
+    let base = click_events::Entity::find().filter(click_events::Column::LinkId.eq(id));
+    let total = base.clone().count(&state.db).await.unwrap_or(0);
+    let total_clicks = clicks_represented(base.clone(), &state.db).await;
+    let rows = base
+        .order_by_desc(click_events::Column::CreatedAt)
+        .order_by_desc(click_events::Column::Id)
+        .limit(limit)
+        .offset(offset)
+        .all(&state.db)
+        .await
+        .unwrap_or_default();
+
+    let city_level =
+        crate::utils::geoip::geo_precision() == crate::utils::geoip::GeoPrecision::City;
+    let clicks = rows
+        .into_iter()
+        .map(|c| RecentClick {
+            id: c.id,
+            timestamp: c.created_at.to_string(),
+            country: c.country,
+            city: c.city.filter(|_| city_level),
+            device: c.device,
+            browser: c.browser,
+            os: c.os,
+            referer: c.referer,
+            source: c.source,
+            is_preview: c.is_preview,
+            sample_rate: c.sample_rate,
+        })
+        .collect();
+
+    (
+        StatusCode::OK,
+        Json(ClickEventsPage {
+            total,
+            total_clicks,
+            limit,
+            offset,
+            clicks,
+        }),
+    )
+        .into_response()
+}
+
+/// Clicks bucketed by weekday and hour of day
+#[utoipa::path(
+    get,
+    path = "/links/{id}/heatmap",
+    params(
+        ("id" = i32, Path, description = "Link ID"),
+        HeatmapQuery
+    ),
+    responses(
+        (status = 200, description = "7x24 click counts", body = HeatmapResponse),
+        (status = 400, description = "Invalid timezone offset"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Link not found"),
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_click_heatmap(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+    Query(query): Query<HeatmapQuery>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_from_header(&state.db, &headers).await {
+        Some(id) => id,
+        None => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+                .into_response()
+        }
+    };
+
+    let offset = match query.tz.as_deref().map(parse_utc_offset) {
+        None => chrono::FixedOffset::east_opt(0).expect("zero offset"),
+        Some(Some(offset)) => offset,
+        Some(None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": "tz must be a UTC offset such as +02:00 or -0530"
+                })),
+            )
+                .into_response()
+        }
+    };
+
+    let link = match links::Entity::find_by_id(id)
+        .filter(links::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await
+    {
+        Ok(Some(link)) => link,
+        _ => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Link not found"})),
+            )
+                .into_response()
+        }
+    };
+
+    if !can_view_link(&state.db, &link, user_id).await {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({"error": "Access denied"})),
+        )
+            .into_response();
+    }
+
+    let days = query.days.unwrap_or(30).max(1);
+    let start_date = chrono::Utc::now().naive_utc() - chrono::Duration::days(days);
+    let timestamps: Vec<(chrono::NaiveDateTime, i32)> = click_events::Entity::find()
+        .select_only()
+        .column(click_events::Column::CreatedAt)
+        .column(click_events::Column::SampleRate)
+        .filter(click_events::Column::LinkId.eq(id))
+        .filter(click_events::Column::CreatedAt.gte(start_date))
+        .into_tuple()
+        .all(&state.db)
+        .await
+        .unwrap_or_default();
+
+    let matrix = heatmap_buckets(&timestamps, offset);
+    (
+        StatusCode::OK,
+        Json(HeatmapResponse {
+            link_id: link.id,
+            tz: offset.to_string(),
+            days,
+            total: timestamps.iter().map(|(_, rate)| *rate as i64).sum(),
+            matrix: matrix.iter().map(|hours| hours.to_vec()).collect(),
+        }),
+    )
+        .into_response()
+}
+
+/// Clicks in the current period against the one before it
+#[utoipa::path(
+    get,
+    path = "/links/{id}/compare",
+    params(
+        ("id" = i32, Path, description = "Link ID"),
+        CompareQuery
+    ),
+    responses(
+        (status = 200, description = "Click totals for both periods", body = PeriodComparisonResponse),
+        (status = 400, description = "Unknown period"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Link not found"),
+    ),
+    tag = "Analytics"
+)]
+pub async fn compare_link_periods(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+    Query(query): Query<CompareQuery>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_from_header(&state.db, &headers).await {
+        Some(id) => id,
+        None => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+                .into_response()
+        }
+    };
+
+    let period = query.period.as_deref().unwrap_or("week");
+    let Some(length) = period_length(period) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "period must be day, week or month"})),
+        )
+            .into_response();
+    };
+
+    let link = match links::Entity::find_by_id(id)
+        .filter(links::Column::DeletedAt.is_null())
+        .one(&state.db)
+        .await
+    {
+        Ok(Some(link)) => link,
+        _ => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Link not found"})),
+            )
+                .into_response()
+        }
+    };
+
+    if !can_view_link(&state.db, &link, user_id).await {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({"error": "Access denied"})),
+        )
+            .into_response();
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let current_start = now - length;
+    let previous_start = current_start - length;
+    let since = |from: chrono::NaiveDateTime| {
+        click_events::Entity::find()
+            .filter(click_events::Column::LinkId.eq(id))
+            .filter(click_events::Column::CreatedAt.gte(from))
+    };
+    let current_clicks = clicks_represented(since(current_start), &state.db).await;
+    let previous_clicks = clicks_represented(
+        since(previous_start).filter(click_events::Column::CreatedAt.lt(current_start)),
+        &state.db,
+    )
+    .await;
+
+    (
+        StatusCode::OK,
+        Json(PeriodComparisonResponse {
+            link_id: link.id,
+            period: period.to_string(),
+            current_start,
+            previous_start,
+            current_clicks,
+            previous_clicks,
+            change: current_clicks - previous_clicks,
+            change_percent: percent_change(current_clicks, previous_clicks),
+        }),
+    )
+        .into_response()
+}
+
+/// Clicks the matching events stand for: each sampled event counts as
+/// `sample_rate` clicks.
+async fn clicks_represented(query: Select<click_events::Entity>, db: &DatabaseConnection) -> i64 {
+    query
+        .select_only()
+        .column_as(
+            sea_query::Expr::col(click_events::Column::SampleRate).sum(),
+            "clicks",
+        )
+        .into_tuple::<Option<i64>>()
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .flatten()
+        .unwrap_or(0)
+}
+
+/// Length of a comparison period: rolling 24 hours, 7 days or 30 days.
+pub fn period_length(period: &str) -> Option<chrono::Duration> {
+    match period {
+        "day" => Some(chrono::Duration::days(1)),
+        "week" => Some(chrono::Duration::days(7)),
+        "month" => Some(chrono::Duration::days(30)),
+        _ => None,
+    }
+}
+
+/// Change from `previous` to `current` in percent, rounded to one decimal.
+/// Undefined when there is nothing to compare against.
+pub fn percent_change(current: i64, previous: i64) -> Option<f64> {
+    if previous == 0 {
+        return None;
+    }
+    let percent = (current - previous) as f64 / previous as f64 * 100.0;
+    Some((percent * 10.0).round() / 10.0)
+}
+
+/// Count UTC timestamps, each standing for its sample rate's worth of
+/// clicks, into `[weekday][hour]` buckets local to `offset`, weekday 0 being
+/// Monday.
+pub fn heatmap_buckets(
+    timestamps: &[(chrono::NaiveDateTime, i32)],
+    offset: chrono::FixedOffset,
+) -> [[i64; 24]; 7] {
+    use chrono::{Datelike, Timelike};
+
+    let mut matrix = [[0i64; 24]; 7];
+    for (ts, sample_rate) in timestamps {
+        let local = ts.and_utc().with_timezone(&offset);
+        matrix[local.weekday().num_days_from_monday() as usize][local.hour() as usize] +=
+            *sample_rate as i64;
+    }
+    matrix
+}
+
 // Helper function to extract domain from URL
 fn extract_domain(url: &str) -> Option<String> {
     url::Url::parse(url)
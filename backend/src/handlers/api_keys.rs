@@ -76,6 +76,15 @@ pub async fn create_api_key(
         Some(auth) => auth,
         None => return (StatusCode::UNAUTHORIZED, "Unauthorized").into_response(),
     };
+    // An impersonated session must not leave a credential behind that
+    // outlives it.
+    if auth.impersonated_by.is_some() {
+        return (
+            StatusCode::FORBIDDEN,
+            "API keys cannot be created while impersonating",
+        )
+            .into_response();
+    }
 
     let txn = match state.db.begin().await {
         Ok(txn) => txn,
@@ -5,10 +5,16 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
-use crate::entity::{api_keys, passkeys, users};
-use crate::utils::email::generate_token;
+use crate::entity::{org_members, organizations, users};
+use crate::utils::email::{
+    email_verification_ttl_hours, generate_token, password_reset_ttl_minutes,
+};
 use crate::utils::email_domain_policy::{ensure_email_domain_allowed, normalize_email};
-use crate::utils::jwt::{create_jwt, hash_password, verify_password};
+use crate::utils::jwt::{
+    check_password, create_jwt, dummy_password_hash, hash_password, verify_password, PasswordCheck,
+};
+use crate::utils::validation::ValidationErrorResponse;
+use crate::utils::webhook;
 use crate::AppState;
 use axum::http::HeaderMap;
 
@@ -55,6 +61,45 @@ pub struct AuthResponse {
     pub email: String,
     pub email_verified: bool,
     pub is_admin: bool,
+    /// Organizations the user belongs to, so the client needn't fetch them
+    /// separately after signing in.
+    pub organizations: Vec<OrgMembershipSummary>,
+    /// True when this sign-in cancelled the account's scheduled deletion
+    /// (ACCOUNT_DELETION_GRACE_DAYS).
+    pub deletion_cancelled: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrgMembershipSummary {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    /// owner | admin | editor | viewer
+    pub role: String,
+}
+
+/// The user's organization memberships, by organization name.
+pub(crate) async fn user_organizations(
+    db: &DatabaseConnection,
+    user_id: i32,
+) -> Vec<OrgMembershipSummary> {
+    org_members::Entity::find()
+        .filter(org_members::Column::UserId.eq(user_id))
+        .find_also_related(organizations::Entity)
+        .order_by_asc(organizations::Column::Name)
+        .all(db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(member, org)| {
+            org.map(|org| OrgMembershipSummary {
+                id: org.id,
+                name: org.name,
+                slug: org.slug,
+                role: member.role,
+            })
+        })
+        .collect()
 }
 
 #[derive(Serialize, ToSchema)]
@@ -74,7 +119,7 @@ pub struct ErrorResponse {
     request_body = RegisterRequest,
     responses(
         (status = 201, description = "User registered successfully", body = AuthResponse),
-        (status = 400, description = "Invalid request"),
+        (status = 400, description = "Invalid request; failed checks are listed per field", body = ValidationErrorResponse),
         (status = 409, description = "Email already exists"),
     ),
     tag = "Authentication"
@@ -84,13 +129,7 @@ pub async fn register(
     Json(payload): Json<RegisterRequest>,
 ) -> impl IntoResponse {
     if let Err(e) = payload.validate() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: e.to_string(),
-            }),
-        )
-            .into_response();
+        return ValidationErrorResponse::from(&e).into_response();
     }
 
     let email = normalize_email(&payload.email);
@@ -119,26 +158,57 @@ pub async fn register(
 
     // Generate verification token
     let verification_token = generate_token();
-    let verification_expires = Utc::now() + Duration::hours(24);
+    let verification_expires = Utc::now() + Duration::hours(email_verification_ttl_hours());
 
     // Check if this is the first user - make them admin
     let user_count = users::Entity::find().count(&state.db).await.unwrap_or(0);
     let is_first_user = user_count == 0;
 
-    let new_user = users::ActiveModel {
-        email: Set(email.clone()),
-        password_hash: Set(hashed_password),
-        email_verified: Set(false),
-        verification_token: Set(Some(verification_token.clone())),
-        verification_token_expires: Set(Some(verification_expires.naive_utc())),
-        is_admin: Set(is_first_user), // First user is automatically admin
-        ..Default::default()
+    // A soft-deleted account frees its email: the old row comes back as a
+    // blank, unverified account instead of tripping the unique constraint.
+    let reactivated = match reactivate_deleted_user(
+        &state,
+        &email,
+        &hashed_password,
+        &verification_token,
+        verification_expires.naive_utc(),
+    )
+    .await
+    {
+        Ok(reactivated) => reactivated,
+        Err(e) => {
+            tracing::error!("Failed to reactivate deleted account: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Database error".to_string(),
+                }),
+            )
+                .into_response();
+        }
     };
 
-    let result = users::Entity::insert(new_user).exec(&state.db).await;
+    let result = match reactivated {
+        Some(user) => Ok((user.id, user.token_version, false)),
+        None => {
+            let new_user = users::ActiveModel {
+                email: Set(email.clone()),
+                password_hash: Set(hashed_password),
+                email_verified: Set(false),
+                verification_token: Set(Some(verification_token.clone())),
+                verification_token_expires: Set(Some(verification_expires.naive_utc())),
+                is_admin: Set(is_first_user), // First user is automatically admin
+                ..Default::default()
+            };
+            users::Entity::insert(new_user)
+                .exec(&state.db)
+                .await
+                .map(|res| (res.last_insert_id, 0, is_first_user))
+        }
+    };
 
     match result {
-        Ok(user_res) => {
+        Ok((user_id, token_version, is_admin)) => {
             // Send verification email if email service is configured
             if let Some(email_service) = &state.email_service {
                 if email_service.is_configured() {
@@ -151,7 +221,17 @@ pub async fn register(
                 }
             }
 
-            let token = match create_jwt(user_res.last_insert_id, &email, 0) {
+            webhook::dispatch(
+                state.db.clone(),
+                webhook::Audience::Admins,
+                webhook::EVENT_USER_REGISTERED,
+                serde_json::json!({
+                    "user_id": user_id,
+                    "email": email,
+                }),
+            );
+
+            let token = match create_jwt(user_id, &email, token_version) {
                 Ok(t) => t,
                 Err(e) => {
                     tracing::error!("Failed to create JWT: {}", e);
@@ -168,10 +248,12 @@ pub async fn register(
                 StatusCode::CREATED,
                 Json(AuthResponse {
                     token,
-                    user_id: user_res.last_insert_id,
+                    user_id,
                     email,
                     email_verified: false,
-                    is_admin: is_first_user,
+                    is_admin,
+                    organizations: Vec::new(),
+                    deletion_cancelled: false,
                 }),
             )
                 .into_response()
@@ -205,6 +287,129 @@ pub async fn register(
     }
 }
 
+/// Bring a soft-deleted account back for a fresh registration of the same
+/// email. Everything the old account left behind that the new holder could
+/// reach — profile, admin flag, org memberships, personal folders, tags,
+/// webhooks and integrations — is wiped; its links stay deleted.
+///
+/// Returns `None` when there is nothing to reactivate, so the caller falls
+/// through to a normal insert (and its duplicate-email conflict): no deleted
+/// row, an admin-disabled account, or one that still owns an organization
+/// with other members.
+async fn reactivate_deleted_user(
+    state: &AppState,
+    email: &str,
+    password_hash: &str,
+    verification_token: &str,
+    verification_expires: chrono::NaiveDateTime,
+) -> Result<Option<users::Model>, DbErr> {
+    use crate::entity::{folders, integrations, links, tags, webhooks};
+
+    let txn = state.db.begin().await?;
+    let user = users::Entity::find()
+        .filter(users::Column::Email.eq(email))
+        .filter(users::Column::DeletedAt.is_not_null())
+        .filter(users::Column::DisabledAt.is_null())
+        .lock_exclusive()
+        .one(&txn)
+        .await?;
+    let Some(user) = user else {
+        txn.rollback().await?;
+        return Ok(None);
+    };
+    let Some(next_token_version) = user.token_version.checked_add(1) else {
+        txn.rollback().await?;
+        return Ok(None);
+    };
+
+    let split = crate::handlers::organizations::split_owned_orgs(&txn, user.id).await?;
+    if !split.blocking.is_empty() {
+        txn.rollback().await?;
+        return Ok(None);
+    }
+    let solo_ids: Vec<i32> = split.solo.iter().map(|org| org.id).collect();
+    let cached_codes: Vec<String> = links::Entity::find()
+        .filter(links::Column::OrgId.is_in(solo_ids.clone()))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|link| link.code)
+        .collect();
+    for org_id in solo_ids {
+        crate::handlers::organizations::purge_organization(&txn, org_id).await?;
+    }
+
+    org_members::Entity::delete_many()
+        .filter(org_members::Column::UserId.eq(user.id))
+        .exec(&txn)
+        .await?;
+    webhooks::Entity::delete_many()
+        .filter(webhooks::Column::UserId.eq(user.id))
+        .exec(&txn)
+        .await?;
+    integrations::Entity::delete_many()
+        .filter(integrations::Column::UserId.eq(user.id))
+        .filter(integrations::Column::OrgId.is_null())
+        .exec(&txn)
+        .await?;
+    folders::Entity::delete_many()
+        .filter(folders::Column::UserId.eq(user.id))
+        .filter(folders::Column::OrgId.is_null())
+        .exec(&txn)
+        .await?;
+    tags::Entity::delete_many()
+        .filter(tags::Column::UserId.eq(user.id))
+        .filter(tags::Column::OrgId.is_null())
+        .exec(&txn)
+        .await?;
+
+    let mut active: users::ActiveModel = user.into();
+    active.password_hash = Set(password_hash.to_string());
+    active.email_verified = Set(false);
+    active.verification_token = Set(Some(verification_token.to_string()));
+    active.verification_token_expires = Set(Some(verification_expires));
+    active.password_reset_token = Set(None);
+    active.password_reset_expires = Set(None);
+    active.is_admin = Set(false);
+    active.deleted_at = Set(None);
+    active.token_version = Set(next_token_version);
+    active.display_name = Set(None);
+    active.bio = Set(None);
+    active.website = Set(None);
+    active.avatar_url = Set(None);
+    active.location = Set(None);
+    active.bio_username = Set(None);
+    active.bio_enabled = Set(false);
+    active.bio_theme = Set(None);
+    active.default_folder_id = Set(None);
+    let user = active.update(&txn).await?;
+    txn.commit().await?;
+
+    crate::handlers::links::invalidate_cached_codes(state, &cached_codes).await;
+    Ok(Some(user))
+}
+
+/// Replace a hash made before PASSWORD_PEPPER was set with a peppered one,
+/// once `password` has been checked against it. Skipped if the password
+/// changed meanwhile; on failure the old hash stays and keeps working.
+async fn rehash_with_pepper(db: &DatabaseConnection, user: &users::Model, password: &str) {
+    let Ok(hashed) = hash_password(password) else {
+        return;
+    };
+    if let Err(e) = users::Entity::update_many()
+        .col_expr(
+            users::Column::PasswordHash,
+            sea_orm::sea_query::Expr::value(hashed),
+        )
+        .filter(users::Column::Id.eq(user.id))
+        .filter(users::Column::PasswordHash.eq(user.password_hash.as_str()))
+        .exec(db)
+        .await
+    {
+        tracing::warn!("Failed to rehash password of user {}: {}", user.id, e);
+    }
+}
+
 /// Login with email and password
 #[utoipa::path(
     post,
@@ -244,7 +449,39 @@ pub async fn login(
         .unwrap_or(None);
 
     if let Some(user) = user {
-        if verify_password(&payload.password, &user.password_hash).unwrap_or(false) {
+        let check = check_password(&payload.password, &user.password_hash)
+            .unwrap_or(PasswordCheck::Mismatch);
+        if check != PasswordCheck::Mismatch {
+            if check == PasswordCheck::MatchNeedsRehash {
+                rehash_with_pepper(&state.db, &user, &payload.password).await;
+            }
+            // Signing in during the grace period keeps the account.
+            let deletion_cancelled = user.deletion_scheduled_at.is_some();
+            if deletion_cancelled {
+                match crate::utils::account_deletion::cancel_scheduled_deletion(&state.db, user.id)
+                    .await
+                {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        return (
+                            StatusCode::UNAUTHORIZED,
+                            Json(ErrorResponse {
+                                error: "Invalid credentials".to_string(),
+                            }),
+                        )
+                            .into_response();
+                    }
+                    Err(_) => {
+                        return (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(ErrorResponse {
+                                error: "Failed to create session".to_string(),
+                            }),
+                        )
+                            .into_response();
+                    }
+                }
+            }
             let token = match create_jwt(user.id, &user.email, user.token_version) {
                 Ok(t) => t,
                 Err(e) => {
@@ -258,6 +495,7 @@ pub async fn login(
                         .into_response();
                 }
             };
+            let organizations = user_organizations(&state.db, user.id).await;
             return (
                 StatusCode::OK,
                 Json(AuthResponse {
@@ -266,6 +504,8 @@ pub async fn login(
                     email: user.email,
                     email_verified: user.email_verified,
                     is_admin: user.is_admin,
+                    organizations,
+                    deletion_cancelled,
                 }),
             )
                 .into_response();
@@ -285,15 +525,6 @@ pub async fn login(
         .into_response()
 }
 
-/// A lazily-computed bcrypt hash used to equalize login timing when the account
-/// does not exist, mitigating user enumeration via response time.
-fn dummy_password_hash() -> &'static str {
-    use once_cell::sync::Lazy;
-    static DUMMY: Lazy<String> =
-        Lazy::new(|| crate::utils::jwt::hash_password("not-a-real-password").unwrap_or_default());
-    DUMMY.as_str()
-}
-
 /// Verify email with token
 #[utoipa::path(
     post,
@@ -434,7 +665,7 @@ pub async fn resend_verification(
 
         // Generate new token
         let verification_token = generate_token();
-        let verification_expires = Utc::now() + Duration::hours(24);
+        let verification_expires = Utc::now() + Duration::hours(email_verification_ttl_hours());
 
         let mut active_user: users::ActiveModel = user.clone().into();
         active_user.verification_token = Set(Some(verification_token.clone()));
@@ -519,7 +750,7 @@ pub async fn forgot_password(
 
     if let Some(user) = user {
         let reset_token = generate_token();
-        let reset_expires = Utc::now() + Duration::hours(1);
+        let reset_expires = Utc::now() + Duration::minutes(password_reset_ttl_minutes());
 
         let mut active_user: users::ActiveModel = user.clone().into();
         active_user.password_reset_token = Set(Some(reset_token.clone()));
@@ -574,13 +805,7 @@ pub async fn reset_password(
     Json(payload): Json<ResetPasswordRequest>,
 ) -> impl IntoResponse {
     if let Err(e) = payload.validate() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: e.to_string(),
-            }),
-        )
-            .into_response();
+        return ValidationErrorResponse::from(&e).into_response();
     }
 
     let txn = match state.db.begin().await {
@@ -731,13 +956,7 @@ pub async fn change_password(
     Json(payload): Json<ChangePasswordRequest>,
 ) -> impl IntoResponse {
     if let Err(e) = payload.validate() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: e.to_string(),
-            }),
-        )
-            .into_response();
+        return ValidationErrorResponse::from(&e).into_response();
     }
 
     let auth = match crate::handlers::links::get_jwt_auth_from_header(&state.db, &headers).await {
@@ -752,6 +971,15 @@ pub async fn change_password(
                 .into_response()
         }
     };
+    if auth.impersonated_by.is_some() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: "Changing the password is not available while impersonating".to_string(),
+            }),
+        )
+            .into_response();
+    }
 
     let txn = match state.db.begin().await {
         Ok(txn) => txn,
@@ -906,7 +1134,7 @@ pub struct DeleteAccountRequest {
     path = "/auth/delete-account",
     request_body = DeleteAccountRequest,
     responses(
-        (status = 200, description = "Account deleted successfully", body = MessageResponse),
+        (status = 200, description = "Account deleted, or scheduled for deletion when ACCOUNT_DELETION_GRACE_DAYS is set", body = MessageResponse),
         (status = 400, description = "Invalid request or wrong password"),
         (status = 403, description = "Account deletion is disabled"),
         (status = 401, description = "Unauthorized"),
@@ -946,6 +1174,15 @@ pub async fn delete_account(
                 .into_response()
         }
     };
+    if auth.impersonated_by.is_some() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: "Account deletion is not available while impersonating".to_string(),
+            }),
+        )
+            .into_response();
+    }
     let user_id = auth.user_id;
 
     let user = users::Entity::find_by_id(user_id)
@@ -1060,18 +1297,10 @@ pub async fn delete_account(
                 .into_response();
         }
 
-        use crate::entity::links;
-        use sea_orm::sea_query::Expr;
-
-        let cached_codes = match links::Entity::find()
-            .filter(links::Column::UserId.eq(user_id))
-            .filter(links::Column::OrgId.is_null())
-            .filter(links::Column::DeletedAt.is_null())
-            .all(&txn)
-            .await
-        {
-            Ok(links) => links.into_iter().map(|link| link.code).collect::<Vec<_>>(),
-            Err(_) => {
+        let now = Utc::now().naive_utc();
+        if let Some(days) = crate::utils::account_deletion::account_deletion_grace_days() {
+            let scheduled_at = now + Duration::days(days);
+            let Some(next_token_version) = user.token_version.checked_add(1) else {
                 let _ = txn.rollback().await;
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -1080,14 +1309,12 @@ pub async fn delete_account(
                     }),
                 )
                     .into_response();
-            }
-        };
-
-        let deleted_at = Utc::now().naive_utc();
-        let next_token_version = match user.token_version.checked_add(1) {
-            Some(version) => version,
-            None => {
-                let _ = txn.rollback().await;
+            };
+            let mut active_user: users::ActiveModel = user.into();
+            active_user.deletion_scheduled_at = Set(Some(scheduled_at));
+            // Sign out everywhere; signing back in cancels the deletion.
+            active_user.token_version = Set(next_token_version);
+            if active_user.update(&txn).await.is_err() || txn.commit().await.is_err() {
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(ErrorResponse {
@@ -1096,52 +1323,33 @@ pub async fn delete_account(
                 )
                     .into_response();
             }
-        };
-
-        let result = async {
-            let mut active_user: users::ActiveModel = user.into();
-            active_user.deleted_at = Set(Some(deleted_at));
-            active_user.token_version = Set(next_token_version);
-            // Free the bio username for reuse and take the public page down.
-            active_user.bio_username = Set(None);
-            active_user.bio_enabled = Set(false);
-            active_user.update(&txn).await?;
-
-            // Organization links belong to the team, not their creator.
-            links::Entity::update_many()
-                .col_expr(links::Column::DeletedAt, Expr::value(deleted_at))
-                .filter(links::Column::UserId.eq(user_id))
-                .filter(links::Column::OrgId.is_null())
-                .filter(links::Column::DeletedAt.is_null())
-                .exec(&txn)
-                .await?;
-
-            crate::utils::privacy::purge_click_pii_for_user(&txn, user_id).await?;
-
-            api_keys::Entity::delete_many()
-                .filter(api_keys::Column::UserId.eq(user_id))
-                .exec(&txn)
-                .await?;
-            passkeys::Entity::delete_many()
-                .filter(passkeys::Column::UserId.eq(user_id))
-                .exec(&txn)
-                .await?;
-
-            Ok::<(), sea_orm::DbErr>(())
-        }
-        .await;
-
-        if result.is_err() {
-            let _ = txn.rollback().await;
             return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to delete account".to_string(),
+                StatusCode::OK,
+                Json(MessageResponse {
+                    message: format!(
+                        "Account scheduled for deletion on {}. Sign in before then to cancel.",
+                        scheduled_at.format("%Y-%m-%d %H:%M UTC")
+                    ),
                 }),
             )
                 .into_response();
         }
 
+        let cached_codes =
+            match crate::utils::account_deletion::delete_account_data(&txn, user, now).await {
+                Ok(codes) => codes,
+                Err(_) => {
+                    let _ = txn.rollback().await;
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse {
+                            error: "Failed to delete account".to_string(),
+                        }),
+                    )
+                        .into_response();
+                }
+            };
+
         if txn.commit().await.is_err() {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -1180,6 +1388,7 @@ pub struct AppSettingsResponse {
     pub passkeys_enabled: bool,
     pub min_alias_length: usize,
     pub max_alias_length: usize,
+    pub max_url_length: usize,
     pub url_sanitization_enabled: bool,
     pub qr_branding_enabled: bool,
     pub burn_after_reading_enabled: bool,
@@ -1187,6 +1396,9 @@ pub struct AppSettingsResponse {
     pub conditional_routing_enabled: bool,
     pub link_in_bio_enabled: bool,
     pub api_keys_enabled: bool,
+    pub anonymous_links_enabled: bool,
+    /// Days until links created without an account expire; null = never.
+    pub anonymous_link_expiry_days: Option<i64>,
 }
 
 /// Get app settings
@@ -1213,15 +1425,10 @@ pub async fn get_app_settings() -> impl IntoResponse {
         .ok()
         .and_then(|v| v.parse().ok());
 
-    let min_alias_length = std::env::var("MIN_ALIAS_LENGTH")
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(5);
-
-    let max_alias_length = std::env::var("MAX_ALIAS_LENGTH")
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(50);
+    // Same helpers the alias validator uses, so the advertised bounds are the
+    // enforced ones.
+    let min_alias_length = crate::handlers::links::get_min_alias_length();
+    let max_alias_length = crate::handlers::links::get_max_alias_length();
 
     let url_sanitization_enabled = std::env::var("ENABLE_URL_SANITIZATION")
         .unwrap_or_else(|_| "true".to_string())
@@ -1279,6 +1486,9 @@ pub async fn get_app_settings() -> impl IntoResponse {
             conditional_routing_enabled,
             link_in_bio_enabled,
             api_keys_enabled,
+            max_url_length: crate::handlers::links::get_max_url_length(),
+            anonymous_links_enabled: crate::handlers::links::anonymous_links_allowed(),
+            anonymous_link_expiry_days: crate::handlers::links::get_anonymous_link_expiry_days(),
         }),
     )
 }
@@ -1302,6 +1512,15 @@ pub struct UserProfileResponse {
     pub bio_username: Option<String>,
     pub bio_enabled: bool,
     pub bio_theme: Option<String>,
+    /// Folder new links go into when created without one.
+    pub default_folder_id: Option<i32>,
+    /// Timezone applied to timestamps requested with `?tz=profile`: an IANA
+    /// name such as `Europe/Paris` or a UTC offset.
+    pub timezone: Option<String>,
+    pub organizations: Vec<OrgMembershipSummary>,
+    /// Admin user id when this session is a support impersonation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impersonated_by: Option<i32>,
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -1311,6 +1530,13 @@ pub struct UpdateProfileRequest {
     pub website: Option<String>,
     pub avatar_url: Option<String>,
     pub location: Option<String>,
+    /// One of your personal folders; new links without a folder go there.
+    pub default_folder_id: Option<i32>,
+    /// Stop filing new links into a default folder.
+    pub remove_default_folder: Option<bool>,
+    /// IANA timezone such as `Europe/Paris`, or a UTC offset such as `+02:00`
+    /// or `-0530`; an empty string clears it.
+    pub timezone: Option<String>,
 }
 
 /// Get current user profile
@@ -1328,8 +1554,8 @@ pub async fn get_current_user(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    let user_id = match crate::handlers::links::get_user_id_from_header(&state.db, &headers).await {
-        Some(id) => id,
+    let auth = match crate::handlers::links::authenticate_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
         None => {
             return (
                 StatusCode::UNAUTHORIZED,
@@ -1340,6 +1566,7 @@ pub async fn get_current_user(
                 .into_response()
         }
     };
+    let user_id = auth.user_id();
 
     let user = users::Entity::find_by_id(user_id)
         .filter(users::Column::DeletedAt.is_null())
@@ -1364,6 +1591,8 @@ pub async fn get_current_user(
             .await
             .unwrap_or(0) as i64;
 
+        let organizations = user_organizations(&state.db, user_id).await;
+
         return (
             StatusCode::OK,
             Json(UserProfileResponse {
@@ -1382,6 +1611,10 @@ pub async fn get_current_user(
                 bio_username: user.bio_username,
                 bio_enabled: user.bio_enabled,
                 bio_theme: user.bio_theme,
+                default_folder_id: user.default_folder_id,
+                timezone: user.timezone,
+                organizations,
+                impersonated_by: auth.impersonated_by(),
             }),
         )
             .into_response();
@@ -1413,8 +1646,8 @@ pub async fn update_profile(
     headers: HeaderMap,
     Json(payload): Json<UpdateProfileRequest>,
 ) -> impl IntoResponse {
-    let user_id = match crate::handlers::links::get_user_id_from_header(&state.db, &headers).await {
-        Some(id) => id,
+    let auth = match crate::handlers::links::authenticate_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
         None => {
             return (
                 StatusCode::UNAUTHORIZED,
@@ -1425,6 +1658,7 @@ pub async fn update_profile(
                 .into_response()
         }
     };
+    let user_id = auth.user_id();
 
     let user = users::Entity::find_by_id(user_id)
         .filter(users::Column::DeletedAt.is_null())
@@ -1486,6 +1720,45 @@ pub async fn update_profile(
                 Some(location)
             });
         }
+        if payload.remove_default_folder == Some(true) {
+            active_user.default_folder_id = Set(None);
+        } else if let Some(folder_id) = payload.default_folder_id {
+            // Only a personal folder of this user; org folders can't hold
+            // personal links.
+            let owned = crate::entity::folders::Entity::find_by_id(folder_id)
+                .filter(crate::entity::folders::Column::UserId.eq(user_id))
+                .filter(crate::entity::folders::Column::OrgId.is_null())
+                .one(&state.db)
+                .await
+                .unwrap_or(None);
+            if owned.is_none() {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        error: "Default folder must be one of your own folders".to_string(),
+                    }),
+                )
+                    .into_response();
+            }
+            active_user.default_folder_id = Set(Some(folder_id));
+        }
+        if let Some(timezone) = payload.timezone {
+            if timezone.trim().is_empty() {
+                active_user.timezone = Set(None);
+            } else {
+                let Some(zone) = crate::utils::timezone::Zone::parse(&timezone) else {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(ErrorResponse {
+                            error: "Timezone must be an IANA name such as Europe/Paris or a UTC offset such as +02:00"
+                                .to_string(),
+                        }),
+                    )
+                        .into_response();
+                };
+                active_user.timezone = Set(Some(zone.to_string()));
+            }
+        }
 
         match active_user.update(&state.db).await {
             Ok(updated) => {
@@ -1505,6 +1778,8 @@ pub async fn update_profile(
                     .await
                     .unwrap_or(0) as i64;
 
+                let organizations = user_organizations(&state.db, user_id).await;
+
                 (
                     StatusCode::OK,
                     Json(UserProfileResponse {
@@ -1523,6 +1798,10 @@ pub async fn update_profile(
                         bio_username: updated.bio_username,
                         bio_enabled: updated.bio_enabled,
                         bio_theme: updated.bio_theme,
+                        default_folder_id: updated.default_folder_id,
+                        timezone: updated.timezone,
+                        organizations,
+                        impersonated_by: auth.impersonated_by(),
                     }),
                 )
                     .into_response()
@@ -74,7 +74,9 @@ pub struct BioLink {
     pub code: String,
     pub short_url: String,
     pub label: String,
-    pub click_count: i32,
+    /// `null` when the owner set `hide_click_count`; see `click_count_bucket`.
+    pub click_count: Option<i32>,
+    pub click_count_bucket: Option<String>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -220,6 +222,8 @@ pub async fn update_bio_settings(
 /// Public bio page data. Returns 404 unless the instance flag AND the user's
 /// `bio_enabled` are both on (and the user isn't deleted) — 404 rather than 403
 /// so a disabled page never reveals whether a username exists.
+/// Also served at `/u/{username}`. Only links the owner marked `bio_visible`
+/// are listed.
 #[utoipa::path(
     get,
     path = "/api/bio/{username}",
@@ -276,7 +280,10 @@ pub async fn get_public_bio(
                 short_url: format!("{}/{}", base_url, l.code),
                 code: l.code,
                 label,
-                click_count: l.click_count,
+                click_count: (!l.hide_click_count).then_some(l.click_count),
+                click_count_bucket: l
+                    .hide_click_count
+                    .then(|| crate::handlers::links::click_count_bucket(l.click_count)),
             }
         })
         .collect();
@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
+use crate::utils::validation::ValidationErrorResponse;
 use crate::AppState;
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
@@ -21,6 +24,9 @@ pub struct ContactRequest {
 pub struct ContactResponse {
     pub success: bool,
     pub message: String,
+    /// Messages per field when the form failed validation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<BTreeMap<String, Vec<String>>>,
 }
 
 /// Send a contact form message to admin
@@ -41,11 +47,13 @@ pub async fn send_contact_message(
 ) -> impl IntoResponse {
     // Validate input
     if let Err(e) = payload.validate() {
+        let invalid = ValidationErrorResponse::from(&e);
         return (
             StatusCode::BAD_REQUEST,
             Json(ContactResponse {
                 success: false,
-                message: format!("Validation error: {}", e),
+                message: format!("Validation error: {}", invalid.error),
+                fields: Some(invalid.fields),
             }),
         )
             .into_response();
@@ -62,6 +70,7 @@ pub async fn send_contact_message(
                 Json(ContactResponse {
                     success: true,
                     message: "Message received. We'll get back to you soon.".to_string(),
+                    fields: None,
                 }),
             )
                 .into_response();
@@ -109,6 +118,7 @@ pub async fn send_contact_message(
                 Json(ContactResponse {
                     success: true,
                     message: "Message sent successfully. We'll get back to you soon.".to_string(),
+                    fields: None,
                 }),
             )
                 .into_response()
@@ -121,6 +131,7 @@ pub async fn send_contact_message(
                     success: false,
                     message: "Failed to send message. Please try again later or email us directly."
                         .to_string(),
+                    fields: None,
                 }),
             )
                 .into_response()
@@ -129,7 +140,7 @@ pub async fn send_contact_message(
 }
 
 /// Simple HTML escape for security
-fn html_escape(s: &str) -> String {
+pub(crate) fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
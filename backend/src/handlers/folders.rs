@@ -1,6 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     Json,
 };
 use sea_orm::{
@@ -9,8 +9,9 @@ use sea_orm::{
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::entity::{folders, link_tags, links, org_members, tags};
-use crate::handlers::links::TagInfo;
+use crate::entity::{folders, links};
+use crate::handlers::links::get_link_tags;
+use crate::handlers::organizations::check_org_permission;
 use crate::AppState;
 
 // ============= DTOs =============
@@ -67,14 +68,9 @@ async fn can_view_folder(
     user_id: i32,
 ) -> bool {
     match folder.org_id {
-        Some(org_id) => org_members::Entity::find()
-            .filter(org_members::Column::OrgId.eq(org_id))
-            .filter(org_members::Column::UserId.eq(user_id))
-            .one(db)
+        Some(org_id) => check_org_permission(db, org_id, user_id, "viewer")
             .await
-            .ok()
-            .flatten()
-            .is_some(),
+            .is_ok(),
         None => folder.user_id == Some(user_id),
     }
 }
@@ -85,40 +81,13 @@ async fn can_edit_folder(
     user_id: i32,
 ) -> bool {
     match folder.org_id {
-        Some(org_id) => crate::handlers::organizations::member_can_edit(db, org_id, user_id).await,
+        Some(org_id) => check_org_permission(db, org_id, user_id, "editor")
+            .await
+            .is_ok(),
         None => folder.user_id == Some(user_id),
     }
 }
 
-async fn get_link_tags(db: &sea_orm::DatabaseConnection, link_id: i32) -> Vec<TagInfo> {
-    let link_tags_list = link_tags::Entity::find()
-        .filter(link_tags::Column::LinkId.eq(link_id))
-        .all(db)
-        .await
-        .unwrap_or_default();
-
-    let tag_ids: Vec<i32> = link_tags_list.iter().map(|lt| lt.tag_id).collect();
-
-    if tag_ids.is_empty() {
-        return vec![];
-    }
-
-    let tags_list = tags::Entity::find()
-        .filter(tags::Column::Id.is_in(tag_ids))
-        .all(db)
-        .await
-        .unwrap_or_default();
-
-    tags_list
-        .into_iter()
-        .map(|t| TagInfo {
-            id: t.id,
-            name: t.name,
-            color: t.color,
-        })
-        .collect()
-}
-
 // ============= Handlers =============
 
 /// Create a new folder
@@ -127,7 +96,7 @@ async fn get_link_tags(db: &sea_orm::DatabaseConnection, link_id: i32) -> Vec<Ta
     path = "/folders",
     request_body = CreateFolderRequest,
     responses(
-        (status = 201, description = "Folder created", body = FolderResponse),
+        (status = 201, description = "Folder created", body = FolderResponse, headers(("Location" = String, description = "Path of the new resource"))),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
     ),
@@ -137,7 +106,14 @@ pub async fn create_folder(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(payload): Json<CreateFolderRequest>,
-) -> Result<(StatusCode, Json<FolderResponse>), (StatusCode, Json<serde_json::Value>)> {
+) -> Result<
+    (
+        StatusCode,
+        [(header::HeaderName, String); 1],
+        Json<FolderResponse>,
+    ),
+    (StatusCode, Json<serde_json::Value>),
+> {
     let user_id = get_user_id_from_header(&state.db, &headers)
         .await
         .ok_or_else(|| {
@@ -149,14 +125,7 @@ pub async fn create_folder(
 
     // Org folders can only be created by members with edit rights (not viewers).
     if let Some(org_id) = payload.org_id {
-        if !crate::handlers::organizations::member_can_edit(&state.db, org_id, user_id).await {
-            return Err((
-                StatusCode::FORBIDDEN,
-                Json(
-                    serde_json::json!({"error": "Insufficient permissions to create an organization folder"}),
-                ),
-            ));
-        }
+        check_org_permission(&state.db, org_id, user_id, "editor").await?;
     }
 
     let folder = folders::ActiveModel {
@@ -180,6 +149,7 @@ pub async fn create_folder(
 
     Ok((
         StatusCode::CREATED,
+        [(header::LOCATION, format!("/folders/{}", folder.id))],
         Json(FolderResponse {
             id: folder.id,
             name: folder.name,
@@ -220,24 +190,7 @@ pub async fn get_folders(
     let mut folder_query = folders::Entity::find();
 
     if let Some(org_id) = query.org_id {
-        // Verify user is member of this org before listing its folders
-        use crate::entity::org_members;
-        let is_member = org_members::Entity::find()
-            .filter(org_members::Column::OrgId.eq(org_id))
-            .filter(org_members::Column::UserId.eq(user_id))
-            .one(&state.db)
-            .await
-            .ok()
-            .flatten()
-            .is_some();
-
-        if !is_member {
-            return Err((
-                StatusCode::FORBIDDEN,
-                Json(serde_json::json!({"error": "Not a member of this organization"})),
-            ));
-        }
-
+        check_org_permission(&state.db, org_id, user_id, "viewer").await?;
         folder_query = folder_query.filter(folders::Column::OrgId.eq(org_id));
     } else {
         folder_query = folder_query.filter(folders::Column::UserId.eq(user_id));
@@ -604,6 +557,78 @@ pub async fn move_links_to_folder(
     })))
 }
 
+/// Take links out of whatever folder they are in
+#[utoipa::path(
+    post,
+    path = "/folders/none/links",
+    request_body = MoveLinkToFolderRequest,
+    responses(
+        (status = 200, description = "Links removed from their folders", body = serde_json::Value),
+        (status = 401, description = "Unauthorized"),
+    ),
+    tag = "Folders"
+)]
+pub async fn clear_links_folder(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<MoveLinkToFolderRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = get_user_id_from_header(&state.db, &headers)
+        .await
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+        })?;
+
+    // Same rule as moving into a folder: personal links by their owner, org
+    // links by anyone who can currently edit in that org. Links the caller
+    // can't touch are skipped, not reported.
+    let mut editable_orgs: std::collections::HashMap<i32, bool> = Default::default();
+    let mut cleared_count = 0;
+    for link_id in payload.link_ids {
+        let Some(link) = links::Entity::find_by_id(link_id)
+            .filter(links::Column::DeletedAt.is_null())
+            .one(&state.db)
+            .await
+            .ok()
+            .flatten()
+        else {
+            continue;
+        };
+        if link.folder_id.is_none() {
+            continue;
+        }
+
+        let link_access = match link.org_id {
+            Some(org_id) => match editable_orgs.get(&org_id) {
+                Some(&can_edit) => can_edit,
+                None => {
+                    let can_edit = check_org_permission(&state.db, org_id, user_id, "editor")
+                        .await
+                        .is_ok();
+                    editable_orgs.insert(org_id, can_edit);
+                    can_edit
+                }
+            },
+            None => link.user_id == Some(user_id),
+        };
+
+        if link_access {
+            let mut link: links::ActiveModel = link.into();
+            link.folder_id = Set(None);
+            if link.update(&state.db).await.is_ok() {
+                cleared_count += 1;
+            }
+        }
+    }
+
+    Ok(Json(serde_json::json!({
+        "cleared": cleared_count
+    })))
+}
+
 /// Get links in folder
 #[utoipa::path(
     get,
@@ -671,36 +696,10 @@ pub async fn get_folder_links(
             )
         })?;
 
-    let base_url =
-        std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:5173".to_string());
-    let api_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string());
-    let mut responses = Vec::new();
+    let mut responses = Vec::with_capacity(links_list.len());
     for l in links_list {
         let link_tags = get_link_tags(&state.db, l.id).await;
-        responses.push(crate::handlers::links::LinkResponse {
-            id: l.id,
-            code: l.code.clone(),
-            short_url: format!("{}/{}", base_url, l.code),
-            api_url: format!("{}/{}", api_url, l.code),
-            original_url: l.original_url.clone(),
-            title: l.title.clone(),
-            click_count: l.click_count,
-            created_at: l.created_at.to_string(),
-            expires_at: l.expires_at.map(|e| e.to_string()),
-            has_password: l.password_hash.is_some(),
-            notes: l.notes.clone(),
-            folder_id: l.folder_id,
-            org_id: l.org_id,
-            starts_at: l.starts_at.map(|s| s.to_string()),
-            max_clicks: l.max_clicks,
-            burn_after_reading: l.burn_after_reading,
-            burned_at: l.burned_at.map(|d| d.to_string()),
-            safe_link_interstitial: l.safe_link_interstitial,
-            bio_visible: l.bio_visible,
-            is_active: l.is_active(),
-            is_pinned: l.is_pinned,
-            tags: link_tags,
-        });
+        responses.push(crate::handlers::links::link_response(&l, link_tags));
     }
 
     Ok(Json(responses))
@@ -0,0 +1,269 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::entity::integrations;
+use crate::handlers::links::{get_jwt_auth_from_header, ErrorResponse, SuccessResponse};
+use crate::handlers::organizations::check_org_permission;
+use crate::utils::integrations::{Platform, INTEGRATION_EVENTS};
+use crate::AppState;
+
+const MAX_INTEGRATIONS: u64 = 10;
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateIntegrationRequest {
+    /// "slack" or "discord"
+    pub platform: String,
+    /// The platform's incoming-webhook URL.
+    pub webhook_url: String,
+    /// Events to post, e.g. `["link.created", "link.milestone"]`.
+    pub events: Vec<String>,
+    /// Post for an organization's links instead of the caller's personal ones.
+    /// Requires org admin.
+    pub org_id: Option<i32>,
+}
+
+#[derive(Deserialize, IntoParams, ToSchema)]
+pub struct IntegrationsQuery {
+    /// List an organization's integrations instead of personal ones.
+    pub org_id: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct IntegrationResponse {
+    pub id: i32,
+    pub platform: String,
+    pub webhook_url: String,
+    pub events: Vec<String>,
+    pub org_id: Option<i32>,
+    pub is_active: bool,
+    pub created_at: String,
+}
+
+impl From<integrations::Model> for IntegrationResponse {
+    fn from(i: integrations::Model) -> Self {
+        Self {
+            id: i.id,
+            platform: i.platform.clone(),
+            webhook_url: i.webhook_url.clone(),
+            events: i.event_list().into_iter().map(String::from).collect(),
+            org_id: i.org_id,
+            is_active: i.is_active,
+            created_at: i.created_at.to_string(),
+        }
+    }
+}
+
+fn error(status: StatusCode, message: &str) -> axum::response::Response {
+    (
+        status,
+        Json(ErrorResponse {
+            error: message.to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// Add a Slack or Discord integration
+#[utoipa::path(
+    post,
+    path = "/integrations",
+    request_body = CreateIntegrationRequest,
+    responses(
+        (status = 201, description = "Integration created", body = IntegrationResponse),
+        (status = 400, description = "Unknown platform/event, bad webhook URL, or limit reached"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not an admin of the organization"),
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+pub async fn create_integration(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateIntegrationRequest>,
+) -> impl IntoResponse {
+    let auth = match get_jwt_auth_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
+        None => return error(StatusCode::UNAUTHORIZED, "Unauthorized"),
+    };
+
+    let platform = match Platform::parse(&payload.platform) {
+        Some(p) => p,
+        None => {
+            return error(
+                StatusCode::BAD_REQUEST,
+                "Platform must be 'slack' or 'discord'",
+            )
+        }
+    };
+    let webhook_url = payload.webhook_url.trim().to_string();
+    if let Err(e) = platform.validate_webhook_url(&webhook_url) {
+        return error(StatusCode::BAD_REQUEST, &e);
+    }
+
+    let mut events: Vec<String> = Vec::new();
+    for event in payload.events {
+        let event = event.trim().to_string();
+        if !INTEGRATION_EVENTS.contains(&event.as_str()) {
+            return error(
+                StatusCode::BAD_REQUEST,
+                &format!(
+                    "Unknown event '{}'. Supported: {}",
+                    event,
+                    INTEGRATION_EVENTS.join(", ")
+                ),
+            );
+        }
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    if events.is_empty() {
+        return error(StatusCode::BAD_REQUEST, "Choose at least one event");
+    }
+
+    if let Some(org_id) = payload.org_id {
+        if let Err(e) = check_org_permission(&state.db, org_id, auth.user_id, "admin").await {
+            return e.into_response();
+        }
+    }
+
+    let count = integrations::Entity::find()
+        .filter(integrations::Column::UserId.eq(auth.user_id))
+        .count(&state.db)
+        .await
+        .unwrap_or(0);
+    if count >= MAX_INTEGRATIONS {
+        return error(
+            StatusCode::BAD_REQUEST,
+            &format!("You can have at most {} integrations", MAX_INTEGRATIONS),
+        );
+    }
+
+    let am = integrations::ActiveModel {
+        user_id: Set(auth.user_id),
+        org_id: Set(payload.org_id),
+        platform: Set(platform.as_str().to_string()),
+        webhook_url: Set(webhook_url),
+        events: Set(events.join(",")),
+        is_active: Set(true),
+        ..Default::default()
+    };
+    match am.insert(&state.db).await {
+        Ok(rec) => (StatusCode::CREATED, Json(IntegrationResponse::from(rec))).into_response(),
+        Err(_) => error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to create integration",
+        ),
+    }
+}
+
+/// List personal integrations, or an organization's with `?org_id=`
+#[utoipa::path(
+    get,
+    path = "/integrations",
+    params(IntegrationsQuery),
+    responses(
+        (status = 200, description = "Integrations", body = [IntegrationResponse]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not an admin of the organization"),
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+pub async fn list_integrations(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<IntegrationsQuery>,
+) -> impl IntoResponse {
+    let auth = match get_jwt_auth_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
+        None => return error(StatusCode::UNAUTHORIZED, "Unauthorized"),
+    };
+
+    let mut select = integrations::Entity::find();
+    if let Some(org_id) = query.org_id {
+        if let Err(e) = check_org_permission(&state.db, org_id, auth.user_id, "admin").await {
+            return e.into_response();
+        }
+        select = select.filter(integrations::Column::OrgId.eq(org_id));
+    } else {
+        select = select
+            .filter(integrations::Column::UserId.eq(auth.user_id))
+            .filter(integrations::Column::OrgId.is_null());
+    }
+
+    let rows = select
+        .order_by_desc(integrations::Column::CreatedAt)
+        .all(&state.db)
+        .await
+        .unwrap_or_default();
+    let out: Vec<IntegrationResponse> = rows.into_iter().map(IntegrationResponse::from).collect();
+    (StatusCode::OK, Json(out)).into_response()
+}
+
+/// Delete an integration
+#[utoipa::path(
+    delete,
+    path = "/integrations/{id}",
+    params(("id" = i32, Path, description = "Integration id")),
+    responses(
+        (status = 200, description = "Integration deleted", body = SuccessResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not an admin of the organization"),
+        (status = 404, description = "Integration not found"),
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_integration(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let auth = match get_jwt_auth_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
+        None => return error(StatusCode::UNAUTHORIZED, "Unauthorized"),
+    };
+
+    let integration = match integrations::Entity::find_by_id(id).one(&state.db).await {
+        Ok(Some(i)) => i,
+        Ok(None) => return error(StatusCode::NOT_FOUND, "Integration not found"),
+        Err(_) => return error(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+    };
+
+    // Personal integrations belong to their creator; org integrations to the
+    // org's admins.
+    match integration.org_id {
+        Some(org_id) => {
+            if let Err(e) = check_org_permission(&state.db, org_id, auth.user_id, "admin").await {
+                return e.into_response();
+            }
+        }
+        None if integration.user_id != auth.user_id => {
+            return error(StatusCode::NOT_FOUND, "Integration not found")
+        }
+        None => {}
+    }
+
+    match integrations::Entity::delete_by_id(id).exec(&state.db).await {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(SuccessResponse {
+                message: "Integration deleted".to_string(),
+            }),
+        )
+            .into_response(),
+        Err(_) => error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to delete integration",
+        ),
+    }
+}
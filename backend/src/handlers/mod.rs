@@ -5,8 +5,10 @@ pub mod auth;
 pub mod bio;
 pub mod contact;
 pub mod folders;
+pub mod integrations;
 pub mod links;
 pub mod organizations;
 pub mod passkeys;
 pub mod tags;
+pub mod webhooks;
 pub mod websocket;
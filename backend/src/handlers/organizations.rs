@@ -1,18 +1,20 @@
 use axum::{
-    extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, Set, TransactionTrait,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::entity::{
-    audit_log, click_events, folders, link_tags, links, org_members, organizations, tags, users,
+    audit_log, folders, link_tags, links, org_members, organizations, tags, users,
 };
+use crate::utils::email::OrgEmailBranding;
 use crate::AppState;
 
 // ============= DTOs =============
@@ -27,6 +29,13 @@ pub struct CreateOrgRequest {
 pub struct UpdateOrgRequest {
     pub name: Option<String>,
     pub slug: Option<String>,
+    /// Name used in emails sent for this org. Empty string clears it.
+    pub brand_name: Option<String>,
+    /// http(s) logo URL used in emails sent for this org. Empty string clears it.
+    pub brand_logo_url: Option<String>,
+    /// Prefix for generated link codes (2-12 lowercase letters or digits).
+    /// Empty string clears it.
+    pub code_prefix: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -37,6 +46,8 @@ pub struct InviteMemberRequest {
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateMemberRoleRequest {
+    /// "owner", "admin", "editor" or "viewer". Only owners may grant or take
+    /// away "owner", and the last owner cannot be demoted.
     pub role: String,
 }
 
@@ -53,6 +64,12 @@ pub struct OrgResponse {
     pub slug: String,
     pub owner_id: i32,
     pub created_at: String,
+    /// Name used in emails sent for this org (falls back to `name`).
+    pub brand_name: Option<String>,
+    /// Logo shown in emails sent for this org.
+    pub brand_logo_url: Option<String>,
+    /// Prefix of codes generated for this org's links.
+    pub code_prefix: Option<String>,
     pub member_count: i64,
     pub link_count: i64,
 }
@@ -79,6 +96,140 @@ pub struct AuditLogResponse {
     pub created_at: String,
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct OrgLinksExportQuery {
+    /// `csv` (default) or `json`.
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrgLinkExport {
+    pub id: i32,
+    pub code: String,
+    pub original_url: String,
+    pub short_url: String,
+    pub click_count: i32,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub has_password: bool,
+    pub notes: Option<String>,
+    pub folder_id: Option<i32>,
+    pub folder_name: Option<String>,
+    pub tags: Vec<String>,
+    pub max_clicks: Option<i32>,
+    pub starts_at: Option<String>,
+    pub created_by: Option<i32>,
+}
+
+/// Format version written by `GET /orgs/{org_id}/export`. Imports of any
+/// other version are rejected rather than half-understood.
+pub const ORG_BUNDLE_VERSION: u32 = 1;
+
+/// Everything needed to recreate an organization on another instance. Folder
+/// and tag `id`s are only meaningful inside the bundle: links refer to them,
+/// and the import assigns fresh ones.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrgExportBundle {
+    pub version: u32,
+    pub organization: OrgBundleMeta,
+    /// Members by email; the importing instance matches them to its own users.
+    pub members: Vec<OrgBundleMember>,
+    pub folders: Vec<OrgBundleFolder>,
+    pub tags: Vec<OrgBundleTag>,
+    pub links: Vec<OrgBundleLink>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrgBundleMeta {
+    pub name: String,
+    pub slug: String,
+    pub brand_name: Option<String>,
+    pub brand_logo_url: Option<String>,
+    pub code_prefix: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrgBundleMember {
+    pub email: String,
+    pub role: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrgBundleFolder {
+    pub id: i32,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrgBundleTag {
+    pub id: i32,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrgBundleLink {
+    pub code: String,
+    pub original_url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Bundle id of the link's folder.
+    #[serde(default)]
+    pub folder_id: Option<i32>,
+    /// Bundle ids of the link's tags.
+    #[serde(default)]
+    pub tag_ids: Vec<i32>,
+    /// Clicks on the source instance, for reference. Imported links start at 0.
+    #[serde(default)]
+    pub click_count: i32,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    #[serde(default)]
+    pub expires_at: Option<chrono::NaiveDateTime>,
+    #[serde(default)]
+    pub starts_at: Option<chrono::NaiveDateTime>,
+    #[serde(default)]
+    pub max_clicks: Option<i32>,
+    /// bcrypt hash, so protected links keep their password across instances.
+    /// Hashes are peppered with PASSWORD_PEPPER, so one exported from an
+    /// instance with a different pepper will never verify.
+    #[serde(default)]
+    pub password_hash: Option<String>,
+    #[serde(default)]
+    pub is_pinned: bool,
+    #[serde(default)]
+    pub burn_after_reading: bool,
+    #[serde(default)]
+    pub safe_link_interstitial: bool,
+    #[serde(default)]
+    pub use_interstitial: bool,
+    #[serde(default)]
+    pub redirect_rate_limit: Option<i32>,
+    #[serde(default)]
+    pub hide_click_count: bool,
+    #[serde(default)]
+    pub ttl_after_first_click_secs: Option<i32>,
+    #[serde(default)]
+    pub expired_page_html: Option<String>,
+    /// Reserved code with no destination yet (`original_url` is empty).
+    #[serde(default)]
+    pub is_reserved: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrgImportResponse {
+    pub organization: OrgResponse,
+    pub members_added: usize,
+    /// Member emails with no account on this instance; invite them once they
+    /// have signed up.
+    pub unresolved_members: Vec<String>,
+    pub folders_created: usize,
+    pub tags_created: usize,
+    pub links_created: usize,
+}
+
 // ============= Helper Functions =============
 
 async fn get_user_id_from_header(
@@ -89,7 +240,7 @@ async fn get_user_id_from_header(
     crate::handlers::links::get_user_id_from_header(db, headers).await
 }
 
-async fn check_org_permission(
+pub(crate) async fn check_org_permission(
     db: &sea_orm::DatabaseConnection,
     org_id: i32,
     user_id: i32,
@@ -200,29 +351,18 @@ pub(crate) fn ownership_conflict_body(blocking: &[organizations::Model]) -> serd
     })
 }
 
-/// Delete an organization and all of its data: links (with click events and
-/// tag assignments), folders, tags, audit log, memberships, then the org row.
+/// Delete an organization and all of its data: links (their click events,
+/// tag assignments and history cascade), folders, tags, audit log,
+/// memberships, then the org row.
 pub(crate) async fn purge_organization<C: ConnectionTrait>(
     db: &C,
     org_id: i32,
 ) -> Result<(), sea_orm::DbErr> {
-    let org_links = links::Entity::find()
+    links::Entity::delete_many()
         .filter(links::Column::OrgId.eq(org_id))
-        .all(db)
+        .exec(db)
         .await?;
 
-    for link in org_links {
-        click_events::Entity::delete_many()
-            .filter(click_events::Column::LinkId.eq(link.id))
-            .exec(db)
-            .await?;
-        link_tags::Entity::delete_many()
-            .filter(link_tags::Column::LinkId.eq(link.id))
-            .exec(db)
-            .await?;
-        links::Entity::delete_by_id(link.id).exec(db).await?;
-    }
-
     folders::Entity::delete_many()
         .filter(folders::Column::OrgId.eq(org_id))
         .exec(db)
@@ -283,7 +423,7 @@ async fn log_audit(
     path = "/orgs",
     request_body = CreateOrgRequest,
     responses(
-        (status = 201, description = "Organization created", body = OrgResponse),
+        (status = 201, description = "Organization created", body = OrgResponse, headers(("Location" = String, description = "Path of the new resource"))),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
         (status = 409, description = "Slug already exists"),
@@ -294,7 +434,14 @@ pub async fn create_organization(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(payload): Json<CreateOrgRequest>,
-) -> Result<(StatusCode, Json<OrgResponse>), (StatusCode, Json<serde_json::Value>)> {
+) -> Result<
+    (
+        StatusCode,
+        [(header::HeaderName, String); 1],
+        Json<OrgResponse>,
+    ),
+    (StatusCode, Json<serde_json::Value>),
+> {
     let user_id = get_user_id_from_header(&state.db, &headers)
         .await
         .ok_or_else(|| {
@@ -368,12 +515,16 @@ pub async fn create_organization(
 
     Ok((
         StatusCode::CREATED,
+        [(header::LOCATION, format!("/orgs/{}", org.id))],
         Json(OrgResponse {
             id: org.id,
             name: org.name,
             slug: org.slug,
             owner_id: org.owner_id,
             created_at: org.created_at.to_string(),
+            brand_name: org.brand_name.clone(),
+            brand_logo_url: org.brand_logo_url.clone(),
+            code_prefix: org.code_prefix.clone(),
             member_count: 1,
             link_count: 0,
         }),
@@ -451,6 +602,9 @@ pub async fn get_user_organizations(
             slug: org.slug.clone(),
             owner_id: org.owner_id,
             created_at: org.created_at.to_string(),
+            brand_name: org.brand_name.clone(),
+            brand_logo_url: org.brand_logo_url.clone(),
+            code_prefix: org.code_prefix.clone(),
             member_count,
             link_count,
         });
@@ -524,6 +678,9 @@ pub async fn get_organization(
         slug: org.slug.clone(),
         owner_id: org.owner_id,
         created_at: org.created_at.to_string(),
+        brand_name: org.brand_name.clone(),
+        brand_logo_url: org.brand_logo_url.clone(),
+        code_prefix: org.code_prefix.clone(),
         member_count,
         link_count,
     }))
@@ -578,7 +735,12 @@ pub async fn update_organization(
             )
         })?;
 
+    let qr_version = org.qr_version;
     let mut org: organizations::ActiveModel = org.into();
+    // Branding changes make cached QR images of the org's links stale.
+    if payload.brand_name.is_some() || payload.brand_logo_url.is_some() {
+        org.qr_version = Set(qr_version + 1);
+    }
 
     if let Some(name) = payload.name {
         org.name = Set(name);
@@ -586,6 +748,43 @@ pub async fn update_organization(
     if let Some(slug) = payload.slug {
         org.slug = Set(slug);
     }
+    if let Some(brand_name) = payload.brand_name {
+        let brand_name = brand_name.trim().to_string();
+        if brand_name.chars().count() > 100 {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "Brand name must be at most 100 characters"})),
+            ));
+        }
+        org.brand_name = Set(Some(brand_name).filter(|n| !n.is_empty()));
+    }
+    if let Some(logo) = payload.brand_logo_url {
+        let logo = logo.trim().to_string();
+        if !logo.is_empty() {
+            if let Err(e) = crate::utils::url_policy::validate_http_https_url(&logo) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({ "error": format!("Brand logo: {e}") })),
+                ));
+            }
+        }
+        org.brand_logo_url = Set(Some(logo).filter(|l| !l.is_empty()));
+    }
+    if let Some(prefix) = payload.code_prefix {
+        let prefix = if prefix.trim().is_empty() {
+            None
+        } else {
+            Some(
+                crate::handlers::links::validate_code_prefix(&prefix).map_err(|e| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(serde_json::json!({ "error": e })),
+                    )
+                })?,
+            )
+        };
+        org.code_prefix = Set(prefix);
+    }
 
     let org = org.update(&state.db).await.map_err(|_| {
         (
@@ -624,11 +823,96 @@ pub async fn update_organization(
         slug: org.slug.clone(),
         owner_id: org.owner_id,
         created_at: org.created_at.to_string(),
+        brand_name: org.brand_name.clone(),
+        brand_logo_url: org.brand_logo_url.clone(),
+        code_prefix: org.code_prefix.clone(),
         member_count,
         link_count,
     }))
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QrVersionResponse {
+    /// New cache version of the org's QR images.
+    pub qr_version: i32,
+}
+
+/// Invalidate cached QR images of the org's links
+///
+/// QR images are rendered on demand; what clients cache is keyed on the org's
+/// `qr_version` (it is part of the image ETag), so bumping it makes every
+/// client refetch. Branding updates bump it automatically.
+#[utoipa::path(
+    post,
+    path = "/orgs/{org_id}/qr/regenerate",
+    params(
+        ("org_id" = i32, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 200, description = "QR cache version bumped", body = QrVersionResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn regenerate_org_qr_codes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(org_id): Path<i32>,
+) -> Result<Json<QrVersionResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = get_user_id_from_header(&state.db, &headers)
+        .await
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+        })?;
+
+    check_org_permission(&state.db, org_id, user_id, "admin").await?;
+
+    let org = organizations::Entity::find_by_id(org_id)
+        .one(&state.db)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Database error"})),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Organization not found"})),
+            )
+        })?;
+
+    let qr_version = org.qr_version + 1;
+    let mut org: organizations::ActiveModel = org.into();
+    org.qr_version = Set(qr_version);
+    org.update(&state.db).await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Failed to update organization"})),
+        )
+    })?;
+
+    log_audit(
+        &state.db,
+        org_id,
+        user_id,
+        "regenerate_qr",
+        "organization",
+        Some(org_id),
+        Some(serde_json::json!({ "qr_version": qr_version })),
+        None,
+    )
+    .await;
+
+    Ok(Json(QrVersionResponse { qr_version }))
+}
+
 /// Delete organization
 #[utoipa::path(
     delete,
@@ -854,6 +1138,33 @@ pub async fn invite_member(
     )
     .await;
 
+    if let Some(email_service) = state.email_service.as_ref().filter(|e| e.is_configured()) {
+        let org = organizations::Entity::find_by_id(org_id)
+            .one(&state.db)
+            .await
+            .ok()
+            .flatten();
+        let inviter = users::Entity::find_by_id(user_id)
+            .one(&state.db)
+            .await
+            .ok()
+            .flatten();
+        if let (Some(org), Some(inviter)) = (org, inviter) {
+            let invited_by = inviter.display_name.unwrap_or(inviter.email);
+            if let Err(e) = email_service
+                .send_org_invitation_email(
+                    &invite_user.email,
+                    &OrgEmailBranding::for_org(&org),
+                    &invited_by,
+                    &member.role,
+                )
+                .await
+            {
+                tracing::error!("Failed to send org invitation email: {}", e);
+            }
+        }
+    }
+
     Ok((
         StatusCode::CREATED,
         Json(OrgMemberResponse {
@@ -866,6 +1177,24 @@ pub async fn invite_member(
     ))
 }
 
+/// Another owner of `org_id` besides member `member_id`, if there is one. The
+/// owner rows are locked so two concurrent demotions cannot each count the
+/// other as the remaining owner.
+async fn other_owner<C: ConnectionTrait>(
+    db: &C,
+    org_id: i32,
+    member_id: i32,
+) -> Result<Option<org_members::Model>, sea_orm::DbErr> {
+    let owners = org_members::Entity::find()
+        .filter(org_members::Column::OrgId.eq(org_id))
+        .filter(org_members::Column::Role.eq("owner"))
+        .order_by_asc(org_members::Column::Id)
+        .lock_exclusive()
+        .all(db)
+        .await?;
+    Ok(owners.into_iter().find(|owner| owner.id != member_id))
+}
+
 /// Update member role
 #[utoipa::path(
     put,
@@ -877,7 +1206,7 @@ pub async fn invite_member(
     request_body = UpdateMemberRoleRequest,
     responses(
         (status = 200, description = "Member role updated", body = OrgMemberResponse),
-        (status = 400, description = "Invalid request"),
+        (status = 400, description = "Invalid role, or demoting the last owner"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
         (status = 404, description = "Member not found"),
@@ -902,7 +1231,7 @@ pub async fn update_member_role(
     check_org_permission(&state.db, org_id, user_id, "admin").await?;
 
     // Validate role
-    if !["admin", "editor", "viewer"].contains(&payload.role.as_str()) {
+    if !["owner", "admin", "editor", "viewer"].contains(&payload.role.as_str()) {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(serde_json::json!({"error": "Invalid role"})),
@@ -926,23 +1255,71 @@ pub async fn update_member_role(
             )
         })?;
 
-    // Can't change owner's role
-    if member.role == "owner" {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Cannot change owner's role"})),
-        ));
+    // Ownership is only granted or taken away by an owner.
+    if member.is_owner() || payload.role == "owner" {
+        check_org_permission(&state.db, org_id, user_id, "owner").await?;
     }
 
-    let mut member: org_members::ActiveModel = member.into();
-    member.role = Set(payload.role.clone());
-
-    let member = member.update(&state.db).await.map_err(|_| {
+    let db_error = |_| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(serde_json::json!({"error": "Failed to update member"})),
         )
-    })?;
+    };
+    let txn = state.db.begin().await.map_err(db_error)?;
+
+    // Count the other owners before demoting one, so the org always keeps one.
+    let successor = if member.is_owner() && payload.role != "owner" {
+        match other_owner(&txn, org_id, member.id).await {
+            Ok(Some(successor)) => Some(successor),
+            Ok(None) => {
+                let _ = txn.rollback().await;
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "error": "Cannot demote the last owner. Make another member an owner or transfer ownership first."
+                    })),
+                ));
+            }
+            Err(e) => {
+                let _ = txn.rollback().await;
+                return Err(db_error(e));
+            }
+        }
+    } else {
+        None
+    };
+
+    let demoted_user_id = member.user_id;
+    let mut member: org_members::ActiveModel = member.into();
+    member.role = Set(payload.role.clone());
+    let member = match member.update(&txn).await {
+        Ok(member) => member,
+        Err(e) => {
+            let _ = txn.rollback().await;
+            return Err(db_error(e));
+        }
+    };
+
+    // organizations.owner_id names one of the owners; hand it on when that
+    // one steps down.
+    if let Some(successor) = successor {
+        let moved = organizations::Entity::update_many()
+            .col_expr(
+                organizations::Column::OwnerId,
+                sea_orm::sea_query::Expr::value(successor.user_id),
+            )
+            .filter(organizations::Column::Id.eq(org_id))
+            .filter(organizations::Column::OwnerId.eq(demoted_user_id))
+            .exec(&txn)
+            .await;
+        if let Err(e) = moved {
+            let _ = txn.rollback().await;
+            return Err(db_error(e));
+        }
+    }
+
+    txn.commit().await.map_err(db_error)?;
 
     let user = users::Entity::find_by_id(member.user_id)
         .one(&state.db)
@@ -1178,19 +1555,30 @@ pub async fn transfer_ownership(
         promoted.role = Set("owner".to_string());
         promoted.update(&txn).await?;
 
-        // Previous owner stays in the org as an admin.
+        // Previous owner stays in the org as an admin, provided the promotion
+        // above left someone else owning it.
         if let Some(old_member) = old_owner_member {
+            if other_owner(&txn, org_id, old_member.id).await?.is_none() {
+                return Ok(None);
+            }
             let mut demoted: org_members::ActiveModel = old_member.into();
             demoted.role = Set("admin".to_string());
             demoted.update(&txn).await?;
         }
 
-        Ok::<organizations::Model, sea_orm::DbErr>(org)
+        Ok::<Option<organizations::Model>, sea_orm::DbErr>(Some(org))
     }
     .await;
 
     let org = match transfer {
-        Ok(org) => org,
+        Ok(Some(org)) => org,
+        Ok(None) => {
+            let _ = txn.rollback().await;
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "Cannot demote the last owner"})),
+            ));
+        }
         Err(_) => {
             let _ = txn.rollback().await;
             return Err((
@@ -1240,6 +1628,9 @@ pub async fn transfer_ownership(
         slug: org.slug.clone(),
         owner_id: org.owner_id,
         created_at: org.created_at.to_string(),
+        brand_name: org.brand_name.clone(),
+        brand_logo_url: org.brand_logo_url.clone(),
+        code_prefix: org.code_prefix.clone(),
         member_count,
         link_count,
     }))
@@ -1315,3 +1706,692 @@ pub async fn get_audit_log(
 
     Ok(Json(responses))
 }
+
+/// Export every link in an organization, with folder and tag names
+#[utoipa::path(
+    get,
+    path = "/orgs/{org_id}/links/export",
+    params(
+        ("org_id" = i32, Path, description = "Organization ID"),
+        OrgLinksExportQuery
+    ),
+    responses(
+        (status = 200, description = "CSV file, or JSON array with format=json", body = Vec<OrgLinkExport>),
+        (status = 400, description = "Unknown format"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn export_org_links(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(org_id): Path<i32>,
+    Query(query): Query<OrgLinksExportQuery>,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = get_user_id_from_header(&state.db, &headers)
+        .await
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+        })?;
+
+    check_org_permission(&state.db, org_id, user_id, "editor").await?;
+
+    let as_json = match query.format.as_deref().unwrap_or("csv") {
+        "csv" => false,
+        "json" => true,
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "format must be csv or json"})),
+            ))
+        }
+    };
+
+    let db_error = |_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Database error"})),
+        )
+    };
+
+    let org_links = links::Entity::find()
+        .filter(links::Column::OrgId.eq(org_id))
+        .filter(links::Column::DeletedAt.is_null())
+        .order_by_desc(links::Column::CreatedAt)
+        .all(&state.db)
+        .await
+        .map_err(db_error)?;
+    let link_ids: Vec<i32> = org_links.iter().map(|l| l.id).collect();
+
+    let folder_names: std::collections::HashMap<i32, String> = folders::Entity::find()
+        .filter(folders::Column::OrgId.eq(org_id))
+        .all(&state.db)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|f| (f.id, f.name))
+        .collect();
+
+    let mut tag_names: std::collections::HashMap<i32, Vec<String>> = Default::default();
+    if !link_ids.is_empty() {
+        let pairs = link_tags::Entity::find()
+            .filter(link_tags::Column::LinkId.is_in(link_ids))
+            .find_also_related(tags::Entity)
+            .all(&state.db)
+            .await
+            .map_err(db_error)?;
+        for (lt, tag) in pairs {
+            if let Some(tag) = tag {
+                tag_names.entry(lt.link_id).or_default().push(tag.name);
+            }
+        }
+        for names in tag_names.values_mut() {
+            names.sort();
+        }
+    }
+
+    let base_url = crate::handlers::links::get_base_url();
+    let fmt = |d: chrono::NaiveDateTime| d.format("%Y-%m-%d %H:%M:%S").to_string();
+
+    if as_json {
+        let export: Vec<OrgLinkExport> = org_links
+            .into_iter()
+            .map(|l| OrgLinkExport {
+                short_url: format!("{}/{}", base_url, l.code),
+                folder_name: l.folder_id.and_then(|f| folder_names.get(&f).cloned()),
+                tags: tag_names.remove(&l.id).unwrap_or_default(),
+                id: l.id,
+                code: l.code,
+                original_url: l.original_url,
+                click_count: l.click_count,
+                created_at: fmt(l.created_at),
+                expires_at: l.expires_at.map(fmt),
+                has_password: l.password_hash.is_some(),
+                notes: l.notes,
+                folder_id: l.folder_id,
+                max_clicks: l.max_clicks,
+                starts_at: l.starts_at.map(fmt),
+                created_by: l.user_id,
+            })
+            .collect();
+        return Ok(Json(export).into_response());
+    }
+
+    use crate::handlers::links::{csv_field, link_csv_row, LINK_CSV_HEADER};
+    let mut csv_content = format!("{},Folder Name,Tags\n", LINK_CSV_HEADER);
+    for link in &org_links {
+        let folder = link
+            .folder_id
+            .and_then(|f| folder_names.get(&f))
+            .map(String::as_str)
+            .unwrap_or_default();
+        let tags = tag_names
+            .get(&link.id)
+            .map(|names| names.join(";"))
+            .unwrap_or_default();
+        csv_content.push_str(&format!(
+            "{},{},{}\n",
+            link_csv_row(link, &base_url),
+            csv_field(folder),
+            csv_field(&tags)
+        ));
+    }
+
+    Ok((
+        StatusCode::OK,
+        [
+            (axum::http::header::CONTENT_TYPE, "text/csv"),
+            (
+                axum::http::header::CONTENT_DISPOSITION,
+                "attachment; filename=\"org-links.csv\"",
+            ),
+        ],
+        csv_content,
+    )
+        .into_response())
+}
+
+/// Export an organization as a bundle for `POST /orgs/import`
+#[utoipa::path(
+    get,
+    path = "/orgs/{org_id}/export",
+    params(
+        ("org_id" = i32, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 200, description = "Organization bundle", body = OrgExportBundle),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only owners can export"),
+        (status = 404, description = "Organization not found"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn export_organization(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(org_id): Path<i32>,
+) -> Result<Json<OrgExportBundle>, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = get_user_id_from_header(&state.db, &headers)
+        .await
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+        })?;
+
+    check_org_permission(&state.db, org_id, user_id, "owner").await?;
+
+    let db_error = |_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Database error"})),
+        )
+    };
+
+    let org = organizations::Entity::find_by_id(org_id)
+        .one(&state.db)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Organization not found"})),
+            )
+        })?;
+
+    let members = org_members::Entity::find()
+        .filter(org_members::Column::OrgId.eq(org_id))
+        .find_also_related(users::Entity)
+        .order_by_asc(org_members::Column::JoinedAt)
+        .all(&state.db)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .filter_map(|(member, user)| {
+            user.map(|u| OrgBundleMember {
+                email: u.email,
+                role: member.role,
+            })
+        })
+        .collect();
+
+    let folders = folders::Entity::find()
+        .filter(folders::Column::OrgId.eq(org_id))
+        .order_by_asc(folders::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|f| OrgBundleFolder {
+            id: f.id,
+            name: f.name,
+            color: f.color,
+        })
+        .collect();
+
+    let tags = tags::Entity::find()
+        .filter(tags::Column::OrgId.eq(org_id))
+        .order_by_asc(tags::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|t| OrgBundleTag {
+            id: t.id,
+            name: t.name,
+            color: t.color,
+        })
+        .collect();
+
+    let org_links = links::Entity::find()
+        .filter(links::Column::OrgId.eq(org_id))
+        .filter(links::Column::DeletedAt.is_null())
+        .order_by_asc(links::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(db_error)?;
+
+    let mut link_tag_ids: std::collections::HashMap<i32, Vec<i32>> = Default::default();
+    if !org_links.is_empty() {
+        let pairs = link_tags::Entity::find()
+            .filter(link_tags::Column::LinkId.is_in(org_links.iter().map(|l| l.id)))
+            .order_by_asc(link_tags::Column::TagId)
+            .all(&state.db)
+            .await
+            .map_err(db_error)?;
+        for lt in pairs {
+            link_tag_ids.entry(lt.link_id).or_default().push(lt.tag_id);
+        }
+    }
+
+    let links = org_links
+        .into_iter()
+        .map(|l| OrgBundleLink {
+            tag_ids: link_tag_ids.remove(&l.id).unwrap_or_default(),
+            code: l.code,
+            original_url: l.original_url,
+            title: l.title,
+            notes: l.notes,
+            folder_id: l.folder_id,
+            click_count: l.click_count,
+            created_at: Some(l.created_at),
+            expires_at: l.expires_at,
+            starts_at: l.starts_at,
+            max_clicks: l.max_clicks,
+            password_hash: l.password_hash,
+            is_pinned: l.is_pinned,
+            burn_after_reading: l.burn_after_reading,
+            safe_link_interstitial: l.safe_link_interstitial,
+            use_interstitial: l.use_interstitial,
+            redirect_rate_limit: l.redirect_rate_limit,
+            hide_click_count: l.hide_click_count,
+            ttl_after_first_click_secs: l.ttl_after_first_click_secs,
+            expired_page_html: l.expired_page_html,
+            is_reserved: l.is_reserved,
+        })
+        .collect::<Vec<_>>();
+
+    log_audit(
+        &state.db,
+        org_id,
+        user_id,
+        "export",
+        "organization",
+        Some(org_id),
+        Some(serde_json::json!({"links": links.len()})),
+        None,
+    )
+    .await;
+
+    Ok(Json(OrgExportBundle {
+        version: ORG_BUNDLE_VERSION,
+        organization: OrgBundleMeta {
+            name: org.name,
+            slug: org.slug,
+            brand_name: org.brand_name,
+            brand_logo_url: org.brand_logo_url,
+            code_prefix: org.code_prefix,
+        },
+        members,
+        folders,
+        tags,
+        links,
+    }))
+}
+
+/// Recreate an organization from an export bundle. The caller becomes its
+/// owner; other members are matched to existing accounts by email.
+#[utoipa::path(
+    post,
+    path = "/orgs/import",
+    request_body = OrgExportBundle,
+    responses(
+        (status = 201, description = "Organization imported", body = OrgImportResponse, headers(("Location" = String, description = "Path of the new resource"))),
+        (status = 400, description = "Invalid bundle"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Email not verified, link limit reached or destination blocked"),
+        (status = 409, description = "Slug or link codes already in use"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn import_organization(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(bundle): Json<OrgExportBundle>,
+) -> Result<
+    (
+        StatusCode,
+        [(header::HeaderName, String); 1],
+        Json<OrgImportResponse>,
+    ),
+    (StatusCode, Json<serde_json::Value>),
+> {
+    let user_id = get_user_id_from_header(&state.db, &headers)
+        .await
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+        })?;
+
+    let bad_request = |error: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": error })),
+        )
+    };
+    let db_error = |_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Database error"})),
+        )
+    };
+
+    if bundle.version != ORG_BUNDLE_VERSION {
+        return Err(bad_request(format!(
+            "Unsupported bundle version {} (expected {})",
+            bundle.version, ORG_BUNDLE_VERSION
+        )));
+    }
+    if let Some(member) = bundle
+        .members
+        .iter()
+        .find(|m| !["owner", "admin", "editor", "viewer"].contains(&m.role.as_str()))
+    {
+        return Err(bad_request(format!(
+            "Invalid role {:?} for member {}",
+            member.role, member.email
+        )));
+    }
+
+    let forbidden = |error: String| {
+        (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({ "error": error })),
+        )
+    };
+
+    // The same account gates create_link applies: imported links are the
+    // importer's own.
+    let importer = users::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+        })?;
+    if !importer.email_verified {
+        return Err(forbidden(
+            "Please verify your email address before creating links".to_string(),
+        ));
+    }
+    if let Some(cap) = crate::handlers::links::get_max_links_per_user() {
+        let existing = links::Entity::find()
+            .filter(links::Column::UserId.eq(user_id))
+            .filter(links::Column::DeletedAt.is_null())
+            .count(&state.db)
+            .await
+            .map_err(db_error)?;
+        if existing + bundle.links.len() as u64 > cap {
+            return Err(forbidden(format!(
+                "Importing {} links would exceed the maximum of {} links for this account",
+                bundle.links.len(),
+                cap
+            )));
+        }
+    }
+
+    // Validate every link up front so a bad one can't leave a half-built org.
+    // Each goes through the checks create_link applies; the first failure
+    // rejects the whole bundle.
+    let folder_ids: std::collections::HashSet<i32> = bundle.folders.iter().map(|f| f.id).collect();
+    let tag_ids: std::collections::HashSet<i32> = bundle.tags.iter().map(|t| t.id).collect();
+    let mut codes = std::collections::HashSet::new();
+    let mut checked = Vec::with_capacity(bundle.links.len());
+    for link in &bundle.links {
+        let invalid = |e: String| bad_request(format!("Link {}: {}", link.code, e));
+        if !codes.insert(link.code.as_str()) {
+            return Err(bad_request(format!("Duplicate link code {}", link.code)));
+        }
+        crate::handlers::links::validate_alias(&link.code).map_err(invalid)?;
+        if link.folder_id.is_some_and(|f| !folder_ids.contains(&f))
+            || link.tag_ids.iter().any(|t| !tag_ids.contains(t))
+        {
+            return Err(bad_request(format!(
+                "Link {} refers to a folder or tag missing from the bundle",
+                link.code
+            )));
+        }
+        if link.max_clicks.is_some_and(|n| n <= 0)
+            || link.redirect_rate_limit.is_some_and(|n| n <= 0)
+            || link.ttl_after_first_click_secs.is_some_and(|n| n <= 0)
+        {
+            return Err(invalid(
+                "max_clicks, redirect_rate_limit and ttl_after_first_click_secs must be greater than 0"
+                    .to_string(),
+            ));
+        }
+        // Only hashes this server could have produced: anything else would be
+        // an opaque value the unlock check can never match.
+        if link
+            .password_hash
+            .as_deref()
+            .is_some_and(|h| h.parse::<bcrypt::HashParts>().is_err())
+        {
+            return Err(invalid("password_hash is not a bcrypt hash".to_string()));
+        }
+        let notes = crate::handlers::links::clean_notes(link.notes.clone()).map_err(invalid)?;
+        let url = if link.is_reserved {
+            String::new()
+        } else {
+            let url = crate::handlers::links::validate_url(&link.original_url).map_err(invalid)?;
+            let url = crate::handlers::links::apply_self_link_policy(&state.db, url)
+                .await
+                .map_err(invalid)?;
+            crate::handlers::links::check_blocked(&state.db, &url)
+                .await
+                .map_err(|e| forbidden(format!("Link {}: {}", link.code, e)))?;
+            url
+        };
+        checked.push((url, notes));
+    }
+
+    if organizations::Entity::find()
+        .filter(organizations::Column::Slug.eq(&bundle.organization.slug))
+        .count(&state.db)
+        .await
+        .map_err(db_error)?
+        > 0
+    {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": "Slug already exists"})),
+        ));
+    }
+    if !codes.is_empty() {
+        // Deleted links keep their code too, so check every row.
+        let taken: Vec<String> = links::Entity::find()
+            .filter(links::Column::Code.is_in(codes.iter().copied()))
+            .all(&state.db)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|l| l.code)
+            .collect();
+        if !taken.is_empty() {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({
+                    "error": "Some link codes are already in use on this instance",
+                    "conflicting_codes": taken,
+                })),
+            ));
+        }
+    }
+
+    let emails: Vec<String> = bundle.members.iter().map(|m| m.email.clone()).collect();
+    let known_users: std::collections::HashMap<String, i32> = users::Entity::find()
+        .filter(users::Column::Email.is_in(emails))
+        .all(&state.db)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|u| (u.email, u.id))
+        .collect();
+
+    let txn = state.db.begin().await.map_err(db_error)?;
+
+    let meta = bundle.organization;
+    let org = organizations::ActiveModel {
+        name: Set(meta.name),
+        slug: Set(meta.slug),
+        owner_id: Set(user_id),
+        brand_name: Set(meta.brand_name),
+        brand_logo_url: Set(meta.brand_logo_url),
+        code_prefix: Set(meta.code_prefix),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(db_error)?;
+
+    org_members::ActiveModel {
+        org_id: Set(org.id),
+        user_id: Set(user_id),
+        role: Set("owner".to_string()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(db_error)?;
+
+    let mut members_added = 0;
+    let mut unresolved_members = Vec::new();
+    for member in bundle.members {
+        match known_users.get(&member.email) {
+            Some(&id) if id == user_id => {}
+            Some(&id) => {
+                org_members::ActiveModel {
+                    org_id: Set(org.id),
+                    user_id: Set(id),
+                    role: Set(member.role),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await
+                .map_err(db_error)?;
+                members_added += 1;
+            }
+            None => unresolved_members.push(member.email),
+        }
+    }
+
+    let mut folder_map = std::collections::HashMap::new();
+    for folder in &bundle.folders {
+        let created = folders::ActiveModel {
+            name: Set(folder.name.clone()),
+            color: Set(folder.color.clone()),
+            org_id: Set(Some(org.id)),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(db_error)?;
+        folder_map.insert(folder.id, created.id);
+    }
+
+    let mut tag_map = std::collections::HashMap::new();
+    for tag in &bundle.tags {
+        let created = tags::ActiveModel {
+            name: Set(tag.name.clone()),
+            color: Set(tag.color.clone()),
+            org_id: Set(Some(org.id)),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(db_error)?;
+        tag_map.insert(tag.id, created.id);
+    }
+
+    let links_created = bundle.links.len();
+    let now = chrono::Utc::now().naive_utc();
+    for (link, (url, notes)) in bundle.links.into_iter().zip(checked) {
+        let mut model = links::ActiveModel {
+            code: Set(link.code),
+            original_url: Set(url),
+            user_id: Set(Some(user_id)),
+            org_id: Set(Some(org.id)),
+            folder_id: Set(link.folder_id.and_then(|f| folder_map.get(&f).copied())),
+            click_count: Set(0),
+            expires_at: Set(link.expires_at),
+            starts_at: Set(link.starts_at),
+            max_clicks: Set(link.max_clicks),
+            password_hash: Set(link.password_hash),
+            title: Set(link.title),
+            notes: Set(notes),
+            is_pinned: Set(link.is_pinned),
+            burn_after_reading: Set(link.burn_after_reading),
+            safe_link_interstitial: Set(link.safe_link_interstitial),
+            use_interstitial: Set(link.use_interstitial),
+            redirect_rate_limit: Set(link.redirect_rate_limit),
+            hide_click_count: Set(link.hide_click_count),
+            ttl_after_first_click_secs: Set(link.ttl_after_first_click_secs),
+            // Served as-is later, so a hand-edited bundle is sanitized too.
+            expired_page_html: Set(link
+                .expired_page_html
+                .as_deref()
+                .map(crate::utils::expired_page::sanitize)),
+            is_reserved: Set(link.is_reserved),
+            ..Default::default()
+        };
+        if let Some(created_at) = link.created_at {
+            model.created_at = Set(created_at.min(now));
+        }
+        let created = model.insert(&txn).await.map_err(db_error)?;
+
+        for tag_id in link.tag_ids.iter().filter_map(|t| tag_map.get(t)) {
+            link_tags::ActiveModel {
+                link_id: Set(created.id),
+                tag_id: Set(*tag_id),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(db_error)?;
+        }
+    }
+
+    txn.commit().await.map_err(db_error)?;
+
+    log_audit(
+        &state.db,
+        org.id,
+        user_id,
+        "import",
+        "organization",
+        Some(org.id),
+        Some(serde_json::json!({
+            "members": members_added,
+            "unresolved_members": unresolved_members.len(),
+            "folders": folder_map.len(),
+            "tags": tag_map.len(),
+            "links": links_created,
+        })),
+        None,
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, format!("/orgs/{}", org.id))],
+        Json(OrgImportResponse {
+            organization: OrgResponse {
+                id: org.id,
+                name: org.name,
+                slug: org.slug,
+                owner_id: org.owner_id,
+                created_at: org.created_at.to_string(),
+                brand_name: org.brand_name,
+                brand_logo_url: org.brand_logo_url,
+                code_prefix: org.code_prefix,
+                member_count: members_added as i64 + 1,
+                link_count: links_created as i64,
+            },
+            members_added,
+            unresolved_members,
+            folders_created: folder_map.len(),
+            tags_created: tag_map.len(),
+            links_created,
+        }),
+    ))
+}
@@ -13,6 +13,7 @@ use webauthn_rs::prelude::*;
 use webauthn_rs::Webauthn;
 
 use crate::entity::{passkeys, users};
+use crate::handlers::auth::OrgMembershipSummary;
 use crate::utils::email_domain_policy::{ensure_email_domain_allowed, normalize_email};
 use crate::utils::jwt::create_jwt;
 use crate::AppState;
@@ -69,6 +70,36 @@ struct PendingPasskeyAuthentication {
 
 static AUTH_STATE: Lazy<ExpiringMap<PendingPasskeyAuthentication>> = Lazy::new(ExpiringMap::new);
 
+/// Usernameless ceremonies, keyed by the `challenge_id` handed to the client:
+/// the account is only known once the authenticator answers.
+static DISCOVERABLE_AUTH_STATE: Lazy<ExpiringMap<DiscoverableAuthentication>> =
+    Lazy::new(ExpiringMap::new);
+
+/// WebAuthn user handle for an account. Authenticators echo it back on
+/// discoverable logins, so it must stay stable for existing credentials.
+fn passkey_user_handle(user_id: i32) -> Uuid {
+    Uuid::from_bytes(user_id.to_le_bytes().repeat(4)[0..16].try_into().unwrap())
+}
+
+/// `passkeys.cred_id` value for a raw credential id, in the same format
+/// registration stores it.
+pub fn credential_id_key(cred_id: &[u8]) -> String {
+    format!("{:?}", CredentialID::from(cred_id.to_vec()))
+}
+
+/// The stored passkey (and so the account) a credential id belongs to.
+pub async fn find_passkey_by_credential_id(
+    db: &DatabaseConnection,
+    cred_id: &[u8],
+) -> Option<passkeys::Model> {
+    passkeys::Entity::find()
+        .filter(passkeys::Column::CredId.eq(credential_id_key(cred_id)))
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+}
+
 // Helper to get Webauthn instance
 fn get_webauthn() -> Webauthn {
     let rp_id = std::env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| {
@@ -129,7 +160,7 @@ fn passkeys_enabled() -> bool {
         .unwrap_or(true)
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct RegisterStartRequest {
     /// Accepted for wire compatibility but IGNORED server-side: the target
     /// account is taken from the caller's authenticated identity, never from
@@ -143,28 +174,39 @@ pub struct RegisterStartResponse {
     pub options: CreationChallengeResponse,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct RegisterFinishRequest {
     /// Accepted for wire compatibility but IGNORED server-side: the credential
     /// is bound to the caller's authenticated identity (see `register_finish`).
     #[allow(dead_code)]
     pub username: String,
+    #[schema(value_type = Object)]
     pub credential: RegisterPublicKeyCredential,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct LoginStartRequest {
-    pub username: String,
+    /// Omit for a usernameless (discoverable credential) login.
+    #[serde(default)]
+    pub username: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct LoginStartResponse {
     pub options: RequestChallengeResponse,
+    /// Set for usernameless logins; send it back to `login/finish`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge_id: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct LoginFinishRequest {
-    pub username: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// From `login/start` when it was called without a username.
+    #[serde(default)]
+    pub challenge_id: Option<String>,
+    #[schema(value_type = Object)]
     pub credential: PublicKeyCredential,
 }
 
@@ -173,6 +215,9 @@ pub struct PasskeyAuthResponse {
     pub token: String,
     pub email_verified: bool,
     pub is_admin: bool,
+    pub organizations: Vec<OrgMembershipSummary>,
+    /// True when this sign-in cancelled the account's scheduled deletion.
+    pub deletion_cancelled: bool,
 }
 
 /// Begin passkey enrollment for the authenticated caller. Returns a WebAuthn
@@ -212,6 +257,13 @@ pub async fn register_start(
         Some(auth) => auth,
         None => return (StatusCode::UNAUTHORIZED, "Unauthorized").into_response(),
     };
+    if auth.impersonated_by.is_some() {
+        return (
+            StatusCode::FORBIDDEN,
+            "Passkeys cannot be registered while impersonating",
+        )
+            .into_response();
+    }
 
     let user = match users::Entity::find_by_id(auth.user_id)
         .filter(users::Column::DeletedAt.is_null())
@@ -230,9 +282,7 @@ pub async fn register_start(
             .into_response();
     }
 
-    // Deterministic UUID from ID for demo purposes
-    let user_unique_id =
-        Uuid::from_bytes(user.id.to_le_bytes().repeat(4)[0..16].try_into().unwrap());
+    let user_unique_id = passkey_user_handle(user.id);
 
     // In a real app, you might want to exclude already registered credentials here
     let exclude_credentials: Option<Vec<CredentialID>> = None;
@@ -296,6 +346,13 @@ pub async fn register_finish(
         Some(auth) => auth,
         None => return (StatusCode::UNAUTHORIZED, "Unauthorized").into_response(),
     };
+    if auth.impersonated_by.is_some() {
+        return (
+            StatusCode::FORBIDDEN,
+            "Passkeys cannot be registered while impersonating",
+        )
+            .into_response();
+    }
 
     let reg_state = match REG_STATE.remove(&auth.user_id.to_string()) {
         Some(s) => s,
@@ -394,7 +451,9 @@ pub async fn register_finish(
     (StatusCode::OK, "Passkey registered").into_response()
 }
 
-/// Begin passkey login. Returns a WebAuthn `RequestChallengeResponse`.
+/// Begin passkey login. Returns a WebAuthn `RequestChallengeResponse`. Without
+/// a `username` the challenge accepts any discoverable passkey for this site and
+/// a `challenge_id` is returned for the finish step.
 #[utoipa::path(
     post,
     path = "/auth/passkey/login/start",
@@ -417,7 +476,14 @@ pub async fn login_start(
         )
             .into_response();
     }
-    let username = normalize_email(&payload.username);
+    let Some(username) = payload
+        .username
+        .as_deref()
+        .map(normalize_email)
+        .filter(|u| !u.is_empty())
+    else {
+        return start_discoverable_login();
+    };
     if ensure_email_domain_allowed(&state.db, &username)
         .await
         .is_err()
@@ -484,10 +550,43 @@ pub async fn login_start(
         },
     );
 
-    (StatusCode::OK, Json(LoginStartResponse { options: rcr })).into_response()
+    (
+        StatusCode::OK,
+        Json(LoginStartResponse {
+            options: rcr,
+            challenge_id: None,
+        }),
+    )
+        .into_response()
+}
+
+fn start_discoverable_login() -> axum::response::Response {
+    let (rcr, auth_state) = match get_webauthn().start_discoverable_authentication() {
+        Ok(res) => res,
+        Err(_) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to start authentication",
+            )
+                .into_response()
+        }
+    };
+    let challenge_id = Uuid::new_v4().to_string();
+    DISCOVERABLE_AUTH_STATE.insert(challenge_id.clone(), auth_state);
+
+    (
+        StatusCode::OK,
+        Json(LoginStartResponse {
+            options: rcr,
+            challenge_id: Some(challenge_id),
+        }),
+    )
+        .into_response()
 }
 
-/// Complete passkey login and issue a JWT on success.
+/// Complete passkey login and issue a JWT on success. Usernameless logins
+/// send the `challenge_id` instead of a username; the account is the owner of
+/// the credential the authenticator used.
 #[utoipa::path(
     post,
     path = "/auth/passkey/login/finish",
@@ -511,7 +610,14 @@ pub async fn login_finish(
         )
             .into_response();
     }
-    let username = normalize_email(&payload.username);
+    let Some(username) = payload
+        .username
+        .as_deref()
+        .map(normalize_email)
+        .filter(|u| !u.is_empty())
+    else {
+        return finish_discoverable_login(&state, &payload).await;
+    };
     let pending = match AUTH_STATE.remove(&username) {
         Some(s) => s,
         None => return (StatusCode::BAD_REQUEST, "Authentication state not found").into_response(),
@@ -524,6 +630,67 @@ pub async fn login_finish(
             Err(_) => return (StatusCode::UNAUTHORIZED, "Authentication failed").into_response(),
         };
 
+    complete_passkey_login(
+        &state,
+        pending.user_id,
+        Some(pending.token_version),
+        &auth_result,
+    )
+    .await
+}
+
+async fn finish_discoverable_login(
+    state: &AppState,
+    payload: &LoginFinishRequest,
+) -> axum::response::Response {
+    let pending = match payload
+        .challenge_id
+        .as_deref()
+        .and_then(|id| DISCOVERABLE_AUTH_STATE.remove(id))
+    {
+        Some(s) => s,
+        None => return (StatusCode::BAD_REQUEST, "Authentication state not found").into_response(),
+    };
+
+    let webauthn = get_webauthn();
+    let Ok((user_handle, cred_id)) =
+        webauthn.identify_discoverable_authentication(&payload.credential)
+    else {
+        return (StatusCode::UNAUTHORIZED, "Authentication failed").into_response();
+    };
+    let Some(passkey_db) = find_passkey_by_credential_id(&state.db, cred_id).await else {
+        return (StatusCode::UNAUTHORIZED, "Authentication failed").into_response();
+    };
+    // The authenticator also names the account; it has to agree with the
+    // credential's owner.
+    if user_handle != passkey_user_handle(passkey_db.user_id) {
+        return (StatusCode::UNAUTHORIZED, "Authentication failed").into_response();
+    }
+    let Ok(stored_passkey) = serde_json::from_str::<Passkey>(&passkey_db.cred_public_key) else {
+        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to authenticate").into_response();
+    };
+
+    let auth_result = match webauthn.finish_discoverable_authentication(
+        &payload.credential,
+        pending,
+        &[DiscoverableKey::from(&stored_passkey)],
+    ) {
+        Ok(res) => res,
+        Err(_) => return (StatusCode::UNAUTHORIZED, "Authentication failed").into_response(),
+    };
+
+    complete_passkey_login(state, passkey_db.user_id, None, &auth_result).await
+}
+
+/// Shared tail of both login flows: persist the credential counter and issue
+/// the JWT. `expected_token_version` is the version seen when a username-based
+/// ceremony started; usernameless ones only learn the account at finish.
+async fn complete_passkey_login(
+    state: &AppState,
+    user_id: i32,
+    expected_token_version: Option<i32>,
+    auth_result: &AuthenticationResult,
+) -> axum::response::Response {
     let cred_id_str = format!("{:?}", auth_result.cred_id());
 
     // Serialize login completion against factor revocation and every other
@@ -538,14 +705,18 @@ pub async fn login_finish(
         }
     };
 
-    let user = match users::Entity::find_by_id(pending.user_id)
+    let user = match users::Entity::find_by_id(user_id)
         .filter(users::Column::DeletedAt.is_null())
         .filter(users::Column::DisabledAt.is_null())
         .lock_exclusive()
         .one(&txn)
         .await
     {
-        Ok(Some(user)) if user.token_version == pending.token_version => user,
+        Ok(Some(user))
+            if expected_token_version.is_none_or(|version| user.token_version == version) =>
+        {
+            user
+        }
         _ => {
             let _ = txn.rollback().await;
             return (StatusCode::UNAUTHORIZED, "Authentication state was revoked").into_response();
@@ -554,7 +725,7 @@ pub async fn login_finish(
 
     let passkey_db = match passkeys::Entity::find()
         .filter(passkeys::Column::CredId.eq(&cred_id_str))
-        .filter(passkeys::Column::UserId.eq(pending.user_id))
+        .filter(passkeys::Column::UserId.eq(user_id))
         .lock_exclusive()
         .one(&txn)
         .await
@@ -579,7 +750,7 @@ pub async fn login_finish(
             return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to authenticate").into_response();
         }
     };
-    stored_passkey.update_credential(&auth_result);
+    stored_passkey.update_credential(auth_result);
     let updated_blob = match serde_json::to_string(&stored_passkey) {
         Ok(blob) => blob,
         Err(_) => {
@@ -597,6 +768,17 @@ pub async fn login_finish(
         return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to authenticate").into_response();
     };
 
+    // Signing in during the grace period keeps the account.
+    let deletion_cancelled = user.deletion_scheduled_at.is_some();
+    if deletion_cancelled
+        && crate::utils::account_deletion::cancel_scheduled_deletion(&txn, user.id)
+            .await
+            .is_err()
+    {
+        let _ = txn.rollback().await;
+        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to authenticate").into_response();
+    }
+
     let token = match create_jwt(user.id, &user.email, user.token_version) {
         Ok(t) => t,
         Err(_) => {
@@ -609,12 +791,15 @@ pub async fn login_finish(
         return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to authenticate").into_response();
     }
 
+    let organizations = crate::handlers::auth::user_organizations(&state.db, user.id).await;
     (
         StatusCode::OK,
         Json(PasskeyAuthResponse {
             token,
             email_verified: user.email_verified,
             is_admin: user.is_admin,
+            organizations,
+            deletion_cancelled,
         }),
     )
         .into_response()
@@ -685,7 +870,7 @@ pub async fn list_passkeys(
         .into_response()
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct DeletePasskeyRequest {
     pub passkey_id: i32,
 }
@@ -825,7 +1010,7 @@ pub async fn delete_passkey(
         .into_response()
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct RenamePasskeyRequest {
     pub passkey_id: i32,
     pub name: String,
@@ -1,15 +1,18 @@
 use axum::{
     extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     Json,
 };
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    sea_query::{extension::postgres::PgExpr, Expr, Func, OnConflict},
+    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::entity::{link_tags, links, org_members, tags};
+use crate::entity::{link_tags, links, tags};
+use crate::handlers::organizations::check_org_permission;
 use crate::AppState;
 
 // ============= DTOs =============
@@ -17,6 +20,8 @@ use crate::AppState;
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTagRequest {
     pub name: String,
+    /// Hex color; when omitted the next TAG_COLOR_PALETTE color is used, and
+    /// an empty string means no color
     pub color: Option<String>,
     pub org_id: Option<i32>,
 }
@@ -32,6 +37,14 @@ pub struct TagQuery {
     pub org_id: Option<i32>,
 }
 
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+pub struct TagSuggestQuery {
+    /// Text to match anywhere in the tag name (case-insensitive)
+    pub q: Option<String>,
+    /// Most suggestions to return (default 10, max 50)
+    pub limit: Option<usize>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TagResponse {
     pub id: i32,
@@ -53,8 +66,87 @@ pub struct RemoveTagsFromLinkRequest {
     pub tag_ids: Vec<i32>,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkDeleteTagsRequest {
+    pub ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkDeleteTagsResponse {
+    pub deleted: u64,
+    /// Link-tag associations removed along with the tags
+    pub associations_removed: u64,
+    /// Requested IDs that were not deleted (missing or not editable)
+    pub skipped: Vec<i32>,
+}
+
 // ============= Helper Functions =============
 
+/// Validate a tag color (`#rgb` or `#rrggbb`) and return it as lowercase
+/// `#rrggbb`. A blank value means "no color".
+fn normalize_tag_color(color: &str) -> Result<Option<String>, String> {
+    let color = color.trim();
+    if color.is_empty() {
+        return Ok(None);
+    }
+    let invalid = || "Color must be a hex color like #3b82f6 or #38f".to_string();
+    let hex = color.strip_prefix('#').ok_or_else(invalid)?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let hex = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return Err(invalid()),
+    };
+    Ok(Some(format!("#{}", hex.to_ascii_lowercase())))
+}
+
+/// Colors handed out to tags created without one, in order.
+const DEFAULT_TAG_PALETTE: &[&str] = &[
+    "#3b82f6", "#ef4444", "#10b981", "#f59e0b", "#8b5cf6", "#ec4899", "#14b8a6", "#f97316",
+];
+
+/// Palette for tags created without a color (TAG_COLOR_PALETTE, comma-separated
+/// hex colors; default: a built-in set of eight). Invalid entries are skipped.
+fn tag_color_palette() -> Vec<String> {
+    std::env::var("TAG_COLOR_PALETTE")
+        .ok()
+        .map(|v| parse_tag_palette(&v))
+        .filter(|palette| !palette.is_empty())
+        .unwrap_or_else(|| DEFAULT_TAG_PALETTE.iter().map(|c| c.to_string()).collect())
+}
+
+fn parse_tag_palette(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .filter_map(|color| normalize_tag_color(color).ok().flatten())
+        .collect()
+}
+
+/// The palette color for the next tag in a scope that already has
+/// `existing` tags, wrapping around once every color has been used.
+fn palette_color(palette: &[String], existing: u64) -> Option<String> {
+    if palette.is_empty() {
+        return None;
+    }
+    Some(palette[(existing % palette.len() as u64) as usize].clone())
+}
+
+fn tag_color_from_request(
+    color: Option<&str>,
+) -> Result<Option<String>, (StatusCode, Json<serde_json::Value>)> {
+    match color {
+        Some(color) => normalize_tag_color(color).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": e })),
+            )
+        }),
+        None => Ok(None),
+    }
+}
+
 async fn get_user_id_from_header(
     db: &sea_orm::DatabaseConnection,
     headers: &HeaderMap,
@@ -67,21 +159,18 @@ async fn get_user_id_from_header(
 /// A removed creator must not retain access to an organization tag.
 async fn can_view_tag(db: &sea_orm::DatabaseConnection, tag: &tags::Model, user_id: i32) -> bool {
     match tag.org_id {
-        Some(org_id) => org_members::Entity::find()
-            .filter(org_members::Column::OrgId.eq(org_id))
-            .filter(org_members::Column::UserId.eq(user_id))
-            .one(db)
+        Some(org_id) => check_org_permission(db, org_id, user_id, "viewer")
             .await
-            .ok()
-            .flatten()
-            .is_some(),
+            .is_ok(),
         None => tag.user_id == Some(user_id),
     }
 }
 
 async fn can_edit_tag(db: &sea_orm::DatabaseConnection, tag: &tags::Model, user_id: i32) -> bool {
     match tag.org_id {
-        Some(org_id) => crate::handlers::organizations::member_can_edit(db, org_id, user_id).await,
+        Some(org_id) => check_org_permission(db, org_id, user_id, "editor")
+            .await
+            .is_ok(),
         None => tag.user_id == Some(user_id),
     }
 }
@@ -92,7 +181,9 @@ async fn can_edit_link(
     user_id: i32,
 ) -> bool {
     match link.org_id {
-        Some(org_id) => crate::handlers::organizations::member_can_edit(db, org_id, user_id).await,
+        Some(org_id) => check_org_permission(db, org_id, user_id, "editor")
+            .await
+            .is_ok(),
         None => link.user_id == Some(user_id),
     }
 }
@@ -107,6 +198,41 @@ fn tag_matches_link_scope(tag: &tags::Model, link: &links::Model, user_id: i32)
     }
 }
 
+/// Whether another tag in the same scope (the user's personal tags, or the
+/// organization's) already uses `name`, compared case-insensitively.
+async fn tag_name_taken(
+    db: &sea_orm::DatabaseConnection,
+    user_id: Option<i32>,
+    org_id: Option<i32>,
+    name: &str,
+    exclude_id: Option<i32>,
+) -> Result<bool, (StatusCode, Json<serde_json::Value>)> {
+    let mut query = tags::Entity::find()
+        .filter(Expr::expr(Func::lower(Expr::col(tags::Column::Name))).eq(name.to_lowercase()));
+    query = match org_id {
+        Some(org_id) => query.filter(tags::Column::OrgId.eq(org_id)),
+        None => query
+            .filter(tags::Column::UserId.eq(user_id))
+            .filter(tags::Column::OrgId.is_null()),
+    };
+    if let Some(id) = exclude_id {
+        query = query.filter(tags::Column::Id.ne(id));
+    }
+    query.count(db).await.map(|n| n > 0).map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Database error"})),
+        )
+    })
+}
+
+fn tag_name_conflict() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::CONFLICT,
+        Json(serde_json::json!({"error": "A tag with this name already exists"})),
+    )
+}
+
 /// Count links carrying a tag, excluding soft-deleted links so the reported
 /// `link_count` matches what the tag's link listing actually shows. Counting
 /// raw `link_tags` rows over-counts, since deleting a link is a soft delete that
@@ -139,9 +265,10 @@ async fn count_active_tagged_links(db: &sea_orm::DatabaseConnection, tag_id: i32
     path = "/tags",
     request_body = CreateTagRequest,
     responses(
-        (status = 201, description = "Tag created", body = TagResponse),
+        (status = 201, description = "Tag created", body = TagResponse, headers(("Location" = String, description = "Path of the new resource"))),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Tag name already in use"),
     ),
     tag = "Tags"
 )]
@@ -149,7 +276,14 @@ pub async fn create_tag(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(payload): Json<CreateTagRequest>,
-) -> Result<(StatusCode, Json<TagResponse>), (StatusCode, Json<serde_json::Value>)> {
+) -> Result<
+    (
+        StatusCode,
+        [(header::HeaderName, String); 1],
+        Json<TagResponse>,
+    ),
+    (StatusCode, Json<serde_json::Value>),
+> {
     let user_id = get_user_id_from_header(&state.db, &headers)
         .await
         .ok_or_else(|| {
@@ -161,19 +295,38 @@ pub async fn create_tag(
 
     // Org tags can only be created by members with edit rights (not viewers).
     if let Some(org_id) = payload.org_id {
-        if !crate::handlers::organizations::member_can_edit(&state.db, org_id, user_id).await {
-            return Err((
-                StatusCode::FORBIDDEN,
-                Json(
-                    serde_json::json!({"error": "Insufficient permissions to create an organization tag"}),
-                ),
-            ));
+        check_org_permission(&state.db, org_id, user_id, "editor").await?;
+    }
+
+    let color = match payload.color.as_deref() {
+        Some(color) => tag_color_from_request(Some(color))?,
+        None => {
+            let scope = match payload.org_id {
+                Some(org_id) => tags::Entity::find().filter(tags::Column::OrgId.eq(org_id)),
+                None => tags::Entity::find()
+                    .filter(tags::Column::UserId.eq(user_id))
+                    .filter(tags::Column::OrgId.is_null()),
+            };
+            let existing = scope.count(&state.db).await.unwrap_or(0);
+            palette_color(&tag_color_palette(), existing)
         }
+    };
+
+    if tag_name_taken(
+        &state.db,
+        Some(user_id),
+        payload.org_id,
+        &payload.name,
+        None,
+    )
+    .await?
+    {
+        return Err(tag_name_conflict());
     }
 
     let tag = tags::ActiveModel {
         name: Set(payload.name.clone()),
-        color: Set(payload.color.clone()),
+        color: Set(color),
         user_id: Set(if payload.org_id.is_some() {
             None
         } else {
@@ -192,6 +345,7 @@ pub async fn create_tag(
 
     Ok((
         StatusCode::CREATED,
+        [(header::LOCATION, format!("/tags/{}", tag.id))],
         Json(TagResponse {
             id: tag.id,
             name: tag.name,
@@ -232,24 +386,7 @@ pub async fn get_tags(
     let mut tag_query = tags::Entity::find();
 
     if let Some(org_id) = query.org_id {
-        // Verify user is member of this org before listing its tags
-        use crate::entity::org_members;
-        let is_member = org_members::Entity::find()
-            .filter(org_members::Column::OrgId.eq(org_id))
-            .filter(org_members::Column::UserId.eq(user_id))
-            .one(&state.db)
-            .await
-            .ok()
-            .flatten()
-            .is_some();
-
-        if !is_member {
-            return Err((
-                StatusCode::FORBIDDEN,
-                Json(serde_json::json!({"error": "Not a member of this organization"})),
-            ));
-        }
-
+        check_org_permission(&state.db, org_id, user_id, "viewer").await?;
         tag_query = tag_query.filter(tags::Column::OrgId.eq(org_id));
     } else {
         tag_query = tag_query.filter(tags::Column::UserId.eq(user_id));
@@ -359,9 +496,11 @@ pub async fn get_tag(
     request_body = UpdateTagRequest,
     responses(
         (status = 200, description = "Tag updated", body = TagResponse),
+        (status = 400, description = "Invalid color"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
         (status = 404, description = "Not found"),
+        (status = 409, description = "Tag name already in use"),
     ),
     tag = "Tags"
 )]
@@ -403,13 +542,19 @@ pub async fn update_tag(
         ));
     }
 
+    if let Some(name) = &payload.name {
+        if tag_name_taken(&state.db, tag.user_id, tag.org_id, name, Some(tag.id)).await? {
+            return Err(tag_name_conflict());
+        }
+    }
+
     let mut tag: tags::ActiveModel = tag.into();
 
     if let Some(name) = payload.name {
         tag.name = Set(name);
     }
     if let Some(color) = payload.color {
-        tag.color = Set(Some(color));
+        tag.color = Set(tag_color_from_request(Some(&color))?);
     }
 
     let tag = tag.update(&state.db).await.map_err(|_| {
@@ -497,6 +642,157 @@ pub async fn delete_tag(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Delete several tags at once
+#[utoipa::path(
+    post,
+    path = "/tags/bulk/delete",
+    request_body = BulkDeleteTagsRequest,
+    responses(
+        (status = 200, description = "Tags deleted", body = BulkDeleteTagsResponse),
+        (status = 400, description = "No IDs, more than MAX_BULK_SIZE, or invalid/duplicate IDs"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    tag = "Tags"
+)]
+pub async fn bulk_delete_tags(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<BulkDeleteTagsRequest>,
+) -> Result<Json<BulkDeleteTagsResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = get_user_id_from_header(&state.db, &headers)
+        .await
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+        })?;
+
+    crate::handlers::links::validate_bulk_ids(
+        &payload.ids,
+        crate::handlers::links::max_bulk_size(),
+    )
+    .map_err(|error| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": error })),
+        )
+    })?;
+
+    let db_error = |_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Failed to delete tags"})),
+        )
+    };
+
+    let found = tags::Entity::find()
+        .filter(tags::Column::Id.is_in(payload.ids.clone()))
+        .all(&state.db)
+        .await
+        .map_err(db_error)?;
+    let mut deletable = Vec::new();
+    for tag in &found {
+        if can_edit_tag(&state.db, tag, user_id).await {
+            deletable.push(tag.id);
+        }
+    }
+    let skipped = payload
+        .ids
+        .iter()
+        .copied()
+        .filter(|id| !deletable.contains(id))
+        .collect();
+
+    let txn = state.db.begin().await.map_err(db_error)?;
+    let associations_removed = link_tags::Entity::delete_many()
+        .filter(link_tags::Column::TagId.is_in(deletable.clone()))
+        .exec(&txn)
+        .await
+        .map_err(db_error)?
+        .rows_affected;
+    let deleted = tags::Entity::delete_many()
+        .filter(tags::Column::Id.is_in(deletable))
+        .exec(&txn)
+        .await
+        .map_err(db_error)?
+        .rows_affected;
+    txn.commit().await.map_err(db_error)?;
+
+    Ok(Json(BulkDeleteTagsResponse {
+        deleted,
+        associations_removed,
+        skipped,
+    }))
+}
+
+/// Suggest organization tags for typeahead
+#[utoipa::path(
+    get,
+    path = "/orgs/{org_id}/tags/suggest",
+    params(
+        ("org_id" = i32, Path, description = "Organization ID"),
+        TagSuggestQuery
+    ),
+    responses(
+        (status = 200, description = "Matching org tags, most used first", body = Vec<TagResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a member of this organization"),
+    ),
+    tag = "Tags"
+)]
+pub async fn suggest_org_tags(
+    State(state): State<AppState>,
+    Path(org_id): Path<i32>,
+    headers: HeaderMap,
+    Query(query): Query<TagSuggestQuery>,
+) -> Result<Json<Vec<TagResponse>>, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = get_user_id_from_header(&state.db, &headers)
+        .await
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Unauthorized"})),
+            )
+        })?;
+
+    check_org_permission(&state.db, org_id, user_id, "viewer").await?;
+
+    let mut tag_query = tags::Entity::find().filter(tags::Column::OrgId.eq(org_id));
+    if let Some(q) = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        tag_query = tag_query
+            .filter(Expr::col(tags::Column::Name).ilike(crate::handlers::admin::ilike_pattern(q)));
+    }
+    let matches = tag_query.all(&state.db).await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Database error"})),
+        )
+    })?;
+
+    let mut responses = Vec::with_capacity(matches.len());
+    for tag in matches {
+        let link_count = count_active_tagged_links(&state.db, tag.id).await;
+        responses.push(TagResponse {
+            id: tag.id,
+            name: tag.name,
+            color: tag.color,
+            user_id: tag.user_id,
+            org_id: tag.org_id,
+            created_at: tag.created_at.to_string(),
+            link_count,
+        });
+    }
+    responses.sort_by(|a, b| {
+        b.link_count
+            .cmp(&a.link_count)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    responses.truncate(query.limit.unwrap_or(10).clamp(1, 50));
+
+    Ok(Json(responses))
+}
+
 /// Add tags to a link
 #[utoipa::path(
     post,
@@ -507,6 +803,7 @@ pub async fn delete_tag(
     request_body = AddTagsToLinkRequest,
     responses(
         (status = 200, description = "Tags added", body = serde_json::Value),
+        (status = 400, description = "Link would exceed MAX_TAGS_PER_LINK"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
         (status = 404, description = "Link not found"),
@@ -553,38 +850,66 @@ pub async fn add_tags_to_link(
         ));
     }
 
+    let db_error = |_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Database error"})),
+        )
+    };
+    // The link row lock serializes concurrent adds so the limit check below
+    // sees every association that will be committed.
+    let txn = state.db.begin().await.map_err(db_error)?;
+    links::Entity::find_by_id(link_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(db_error)?;
+
     let mut added_count = 0;
     for tag_id in payload.tag_ids {
         let tag = tags::Entity::find_by_id(tag_id)
-            .one(&state.db)
+            .one(&txn)
             .await
             .ok()
             .flatten();
 
         if let Some(tag) = tag {
             if tag_matches_link_scope(&tag, &link, user_id) {
-                // Check if already linked
-                let existing = link_tags::Entity::find()
-                    .filter(link_tags::Column::LinkId.eq(link_id))
-                    .filter(link_tags::Column::TagId.eq(tag_id))
-                    .one(&state.db)
+                // The (link_id, tag_id) unique index arbitrates concurrent adds:
+                // an existing association inserts nothing and isn't counted.
+                let link_tag = link_tags::ActiveModel {
+                    link_id: Set(link_id),
+                    tag_id: Set(tag_id),
+                    ..Default::default()
+                };
+                let inserted = link_tags::Entity::insert(link_tag)
+                    .on_conflict(
+                        OnConflict::columns([link_tags::Column::LinkId, link_tags::Column::TagId])
+                            .do_nothing()
+                            .to_owned(),
+                    )
+                    .exec_without_returning(&txn)
                     .await
-                    .ok()
-                    .flatten();
-
-                if existing.is_none() {
-                    let link_tag = link_tags::ActiveModel {
-                        link_id: Set(link_id),
-                        tag_id: Set(tag_id),
-                        ..Default::default()
-                    };
-                    let _ = link_tag.insert(&state.db).await;
-                    added_count += 1;
-                }
+                    .unwrap_or(0);
+                added_count += inserted;
             }
         }
     }
 
+    let total = crate::handlers::links::count_link_tags(&txn, link_id)
+        .await
+        .map_err(db_error)?;
+    if total as usize > crate::handlers::links::max_tags_per_link() {
+        let _ = txn.rollback().await;
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": crate::handlers::links::too_many_tags_message()
+            })),
+        ));
+    }
+    txn.commit().await.map_err(db_error)?;
+
     Ok(Json(serde_json::json!({
         "added": added_count
     })))
@@ -761,36 +1086,59 @@ pub async fn get_links_by_tag(
         )
     })?;
 
-    let base_url =
-        std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:5173".to_string());
-    let api_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string());
-    let responses: Vec<crate::handlers::links::LinkResponse> = links_list
-        .into_iter()
-        .map(|l| crate::handlers::links::LinkResponse {
-            id: l.id,
-            code: l.code.clone(),
-            short_url: format!("{}/{}", base_url, l.code),
-            api_url: format!("{}/{}", api_url, l.code),
-            original_url: l.original_url.clone(),
-            title: l.title.clone(),
-            click_count: l.click_count,
-            created_at: l.created_at.to_string(),
-            expires_at: l.expires_at.map(|e| e.to_string()),
-            has_password: l.password_hash.is_some(),
-            notes: l.notes.clone(),
-            folder_id: l.folder_id,
-            org_id: l.org_id,
-            starts_at: l.starts_at.map(|s| s.to_string()),
-            max_clicks: l.max_clicks,
-            burn_after_reading: l.burn_after_reading,
-            burned_at: l.burned_at.map(|d| d.to_string()),
-            safe_link_interstitial: l.safe_link_interstitial,
-            bio_visible: l.bio_visible,
-            is_active: l.is_active(),
-            is_pinned: l.is_pinned,
-            tags: vec![],
-        })
-        .collect();
+    let mut responses = Vec::with_capacity(links_list.len());
+    for l in links_list {
+        let link_tags = crate::handlers::links::get_link_tags(&state.db, l.id).await;
+        responses.push(crate::handlers::links::link_response(&l, link_tags));
+    }
 
     Ok(Json(responses))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_tag_color, palette_color, parse_tag_palette};
+
+    #[test]
+    fn valid_colors_normalize_to_lowercase_six_digits() {
+        assert_eq!(
+            normalize_tag_color("#3B82F6").unwrap().as_deref(),
+            Some("#3b82f6")
+        );
+        assert_eq!(
+            normalize_tag_color("#38f").unwrap().as_deref(),
+            Some("#3388ff")
+        );
+        assert_eq!(
+            normalize_tag_color(" #AbC ").unwrap().as_deref(),
+            Some("#aabbcc")
+        );
+        assert_eq!(normalize_tag_color("").unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_colors_are_rejected() {
+        for color in [
+            "3b82f6",
+            "#3b82f",
+            "#3b82f6ff",
+            "#ggg",
+            "red",
+            "#",
+            "#12 456",
+            "#ａｂｃ",
+        ] {
+            assert!(normalize_tag_color(color).is_err(), "{color}");
+        }
+    }
+
+    #[test]
+    fn palette_skips_invalid_entries_and_cycles() {
+        let palette = parse_tag_palette("#F00, nope, #00ff00,,#0000FF");
+        assert_eq!(palette, ["#ff0000", "#00ff00", "#0000ff"]);
+        assert_eq!(palette_color(&palette, 0).as_deref(), Some("#ff0000"));
+        assert_eq!(palette_color(&palette, 2).as_deref(), Some("#0000ff"));
+        assert_eq!(palette_color(&palette, 3).as_deref(), Some("#ff0000"));
+        assert_eq!(palette_color(&[], 0), None);
+    }
+}
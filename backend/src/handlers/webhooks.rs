@@ -0,0 +1,380 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::entity::{users, webhook_deliveries, webhooks};
+use crate::handlers::links::{get_jwt_auth_from_header, ErrorResponse, SuccessResponse};
+use crate::utils::webhook::{self, EVENT_USER_REGISTERED, SUPPORTED_EVENTS};
+use crate::AppState;
+
+const MAX_WEBHOOKS: u64 = 10;
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    /// http(s) endpoint that receives signed POSTs.
+    pub url: String,
+    /// Event names to subscribe to, e.g. `["link.milestone"]`.
+    pub events: Vec<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CreateWebhookResponse {
+    pub id: i32,
+    pub url: String,
+    pub events: Vec<String>,
+    /// Signing secret — shown ONCE at creation and never again.
+    pub secret: String,
+    pub created_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookInfo {
+    pub id: i32,
+    pub url: String,
+    pub events: Vec<String>,
+    pub is_active: bool,
+    pub created_at: String,
+}
+
+impl From<webhooks::Model> for WebhookInfo {
+    fn from(hook: webhooks::Model) -> Self {
+        Self {
+            id: hook.id,
+            url: hook.url.clone(),
+            events: hook.event_list().into_iter().map(String::from).collect(),
+            is_active: hook.is_active,
+            created_at: hook.created_at.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookDeliveryInfo {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event: String,
+    /// The JSON envelope that was sent.
+    pub payload: String,
+    /// HTTP status from the endpoint; null if no response was received.
+    pub status_code: Option<i32>,
+    /// Start of the endpoint's response body.
+    pub response_snippet: Option<String>,
+    pub error: Option<String>,
+    pub success: bool,
+    pub created_at: String,
+}
+
+impl From<webhook_deliveries::Model> for WebhookDeliveryInfo {
+    fn from(d: webhook_deliveries::Model) -> Self {
+        Self {
+            id: d.id,
+            webhook_id: d.webhook_id,
+            event: d.event,
+            payload: d.payload,
+            status_code: d.status_code,
+            response_snippet: d.response_snippet,
+            error: d.error,
+            success: d.success,
+            created_at: d.created_at.to_string(),
+        }
+    }
+}
+
+fn error(status: StatusCode, message: &str) -> axum::response::Response {
+    (
+        status,
+        Json(ErrorResponse {
+            error: message.to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// Register a webhook. The signing secret is returned once.
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created; the signing secret is returned once", body = CreateWebhookResponse),
+        (status = 400, description = "Invalid URL, unknown event, or webhook limit reached"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Event restricted to admins"),
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> impl IntoResponse {
+    let auth = match get_jwt_auth_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
+        None => return error(StatusCode::UNAUTHORIZED, "Unauthorized"),
+    };
+
+    let url = payload.url.trim().to_string();
+    match url::Url::parse(&url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.has_host() => {}
+        _ => {
+            return error(
+                StatusCode::BAD_REQUEST,
+                "Webhook URL must be an http(s) URL",
+            )
+        }
+    }
+
+    let mut events: Vec<String> = Vec::new();
+    for event in payload.events {
+        let event = event.trim().to_string();
+        if !SUPPORTED_EVENTS.contains(&event.as_str()) {
+            return error(
+                StatusCode::BAD_REQUEST,
+                &format!(
+                    "Unknown event '{}'. Supported: {}",
+                    event,
+                    SUPPORTED_EVENTS.join(", ")
+                ),
+            );
+        }
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    if events.is_empty() {
+        return error(StatusCode::BAD_REQUEST, "Subscribe to at least one event");
+    }
+
+    // Signups are instance-wide data: only admins may receive them.
+    if events.iter().any(|e| e == EVENT_USER_REGISTERED) {
+        let is_admin = users::Entity::find_by_id(auth.user_id)
+            .one(&state.db)
+            .await
+            .ok()
+            .flatten()
+            .map(|u| u.is_admin)
+            .unwrap_or(false);
+        if !is_admin {
+            return error(
+                StatusCode::FORBIDDEN,
+                "Only admins can subscribe to user.registered",
+            );
+        }
+    }
+
+    let count = webhooks::Entity::find()
+        .filter(webhooks::Column::UserId.eq(auth.user_id))
+        .count(&state.db)
+        .await
+        .unwrap_or(0);
+    if count >= MAX_WEBHOOKS {
+        return error(
+            StatusCode::BAD_REQUEST,
+            &format!("You can have at most {} webhooks", MAX_WEBHOOKS),
+        );
+    }
+
+    let random: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+    let secret = format!("whsec_{}", random);
+
+    let am = webhooks::ActiveModel {
+        user_id: Set(auth.user_id),
+        url: Set(url.clone()),
+        secret: Set(secret.clone()),
+        events: Set(events.join(",")),
+        is_active: Set(true),
+        ..Default::default()
+    };
+    match am.insert(&state.db).await {
+        Ok(rec) => (
+            StatusCode::CREATED,
+            Json(CreateWebhookResponse {
+                id: rec.id,
+                url,
+                events,
+                secret,
+                created_at: rec.created_at.to_string(),
+            }),
+        )
+            .into_response(),
+        Err(_) => error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to create webhook",
+        ),
+    }
+}
+
+/// List the caller's webhooks (secrets are never returned).
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    responses(
+        (status = 200, description = "The caller's webhooks", body = [WebhookInfo]),
+        (status = 401, description = "Unauthorized"),
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+pub async fn list_webhooks(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    let auth = match get_jwt_auth_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
+        None => return error(StatusCode::UNAUTHORIZED, "Unauthorized"),
+    };
+    let hooks = webhooks::Entity::find()
+        .filter(webhooks::Column::UserId.eq(auth.user_id))
+        .order_by_desc(webhooks::Column::CreatedAt)
+        .all(&state.db)
+        .await
+        .unwrap_or_default();
+    let out: Vec<WebhookInfo> = hooks.into_iter().map(WebhookInfo::from).collect();
+    (StatusCode::OK, Json(out)).into_response()
+}
+
+/// Delete one of the caller's webhooks.
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    params(("id" = i32, Path, description = "Webhook id")),
+    responses(
+        (status = 200, description = "Webhook deleted", body = SuccessResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Webhook not found"),
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let auth = match get_jwt_auth_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
+        None => return error(StatusCode::UNAUTHORIZED, "Unauthorized"),
+    };
+    let res = webhooks::Entity::delete_many()
+        .filter(webhooks::Column::Id.eq(id))
+        .filter(webhooks::Column::UserId.eq(auth.user_id))
+        .exec(&state.db)
+        .await;
+    match res {
+        Ok(r) if r.rows_affected > 0 => (
+            StatusCode::OK,
+            Json(SuccessResponse {
+                message: "Webhook deleted".to_string(),
+            }),
+        )
+            .into_response(),
+        Ok(_) => error(StatusCode::NOT_FOUND, "Webhook not found"),
+        Err(_) => error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to delete webhook",
+        ),
+    }
+}
+
+/// Recent delivery attempts for one of the caller's webhooks, newest first.
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    params(("id" = i32, Path, description = "Webhook id")),
+    responses(
+        (status = 200, description = "Delivery log", body = [WebhookDeliveryInfo]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Webhook not found"),
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let auth = match get_jwt_auth_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
+        None => return error(StatusCode::UNAUTHORIZED, "Unauthorized"),
+    };
+    let owned = webhooks::Entity::find_by_id(id)
+        .filter(webhooks::Column::UserId.eq(auth.user_id))
+        .one(&state.db)
+        .await
+        .ok()
+        .flatten();
+    if owned.is_none() {
+        return error(StatusCode::NOT_FOUND, "Webhook not found");
+    }
+    let deliveries = webhook_deliveries::Entity::find()
+        .filter(webhook_deliveries::Column::WebhookId.eq(id))
+        .order_by_desc(webhook_deliveries::Column::Id)
+        .all(&state.db)
+        .await
+        .unwrap_or_default();
+    let out: Vec<WebhookDeliveryInfo> = deliveries
+        .into_iter()
+        .map(WebhookDeliveryInfo::from)
+        .collect();
+    (StatusCode::OK, Json(out)).into_response()
+}
+
+/// Re-send a failed delivery with its original payload. The new attempt is
+/// logged and returned.
+#[utoipa::path(
+    post,
+    path = "/webhooks/deliveries/{id}/retry",
+    params(("id" = i32, Path, description = "Delivery id")),
+    responses(
+        (status = 200, description = "Outcome of the new attempt", body = WebhookDeliveryInfo),
+        (status = 400, description = "Delivery already succeeded"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Delivery not found"),
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+pub async fn retry_webhook_delivery(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let auth = match get_jwt_auth_from_header(&state.db, &headers).await {
+        Some(auth) => auth,
+        None => return error(StatusCode::UNAUTHORIZED, "Unauthorized"),
+    };
+    let found = webhook_deliveries::Entity::find_by_id(id)
+        .find_also_related(webhooks::Entity)
+        .one(&state.db)
+        .await
+        .ok()
+        .flatten();
+    let (delivery, hook) = match found {
+        Some((delivery, Some(hook))) if hook.user_id == auth.user_id => (delivery, hook),
+        _ => return error(StatusCode::NOT_FOUND, "Delivery not found"),
+    };
+    if delivery.success {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "Only failed deliveries can be retried",
+        );
+    }
+    match webhook::redeliver(&state.db, &hook, &delivery).await {
+        Ok(attempt) => (StatusCode::OK, Json(WebhookDeliveryInfo::from(attempt))).into_response(),
+        Err(_) => error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to record delivery",
+        ),
+    }
+}
@@ -8,12 +8,48 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::broadcast;
 
 const DEFAULT_AUTH_REVALIDATE_INTERVAL: Duration = Duration::from_secs(30);
 
+/// Clicks per second on one link above which realtime events are coalesced
+/// (REALTIME_COALESCE_THRESHOLD, default: 20). 0 sends every click.
+fn realtime_coalesce_threshold() -> u32 {
+    std::env::var("REALTIME_COALESCE_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(20)
+}
+
+/// Minimum gap between coalesced updates for one link
+/// (REALTIME_COALESCE_INTERVAL_MS, default: 1000).
+fn realtime_coalesce_interval() -> Duration {
+    Duration::from_millis(
+        std::env::var("REALTIME_COALESCE_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1000),
+    )
+}
+
+/// How often `/ws` pings its clients (WS_PING_SECS, default: 30). Proxies drop
+/// connections that stay silent, and a client that answers nothing for two
+/// intervals is disconnected. 0 disables pings.
+fn ws_ping_interval() -> Option<Duration> {
+    let secs = std::env::var("WS_PING_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30);
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
 /// WebSocket state for real-time updates
 #[derive(Clone)]
 pub struct WsState {
@@ -22,6 +58,9 @@ pub struct WsState {
     /// closes, so there is no per-connection state to leak.
     pub click_tx: broadcast::Sender<ClickEvent>,
     auth_revalidate_interval: Duration,
+    /// See [`ws_ping_interval`]; `None` sends no pings.
+    ping_interval: Option<Duration>,
+    coalescer: Option<Arc<ClickCoalescer>>,
 }
 
 impl WsState {
@@ -33,16 +72,62 @@ impl WsState {
     /// tests. Production uses [`Self::new`] and the 30-second default.
     pub fn with_auth_revalidate_interval(auth_revalidate_interval: Duration) -> Self {
         let (click_tx, _) = broadcast::channel(1000);
+        let threshold = realtime_coalesce_threshold();
         Self {
             click_tx,
             auth_revalidate_interval,
+            ping_interval: ws_ping_interval(),
+            coalescer: (threshold > 0)
+                .then(|| Arc::new(ClickCoalescer::new(threshold, realtime_coalesce_interval()))),
         }
     }
 
+    /// Override the ping interval, for tests that need a client to time out
+    /// quickly.
+    pub fn with_ping_interval(mut self, ping_interval: Duration) -> Self {
+        self.ping_interval = Some(ping_interval);
+        self
+    }
+
     /// Broadcast a click event. Connections subscribe to `click_tx` and filter
     /// by user_id on their side; there is no per-connection state to clean up.
+    ///
+    /// A link clicked faster than the coalescing threshold gets at most one
+    /// event per interval, carrying the number of clicks it stands for.
     pub fn broadcast_click(&self, event: ClickEvent) {
-        let _ = self.click_tx.send(event);
+        let Some(coalescer) = &self.coalescer else {
+            let _ = self.click_tx.send(event);
+            return;
+        };
+        let link_id = event.link_id;
+        match coalescer.offer(event, Instant::now()) {
+            Coalesced::Send(event) => {
+                let _ = self.click_tx.send(event);
+            }
+            Coalesced::Held {
+                flush_in: Some(delay),
+            } => {
+                // Trailing update, so the last clicks of a burst still arrive.
+                let tx = self.click_tx.clone();
+                let coalescer = coalescer.clone();
+                match tokio::runtime::Handle::try_current() {
+                    Ok(handle) => {
+                        handle.spawn(async move {
+                            tokio::time::sleep(delay).await;
+                            if let Some(event) = coalescer.flush(link_id, Instant::now()) {
+                                let _ = tx.send(event);
+                            }
+                        });
+                    }
+                    Err(_) => {
+                        if let Some(event) = coalescer.flush(link_id, Instant::now()) {
+                            let _ = tx.send(event);
+                        }
+                    }
+                }
+            }
+            Coalesced::Held { flush_in: None } => {}
+        }
     }
 }
 
@@ -52,6 +137,33 @@ impl Default for WsState {
     }
 }
 
+/// Whether a WebSocket client is still there. Any frame from it counts, pongs
+/// included; one that stays silent for longer than `timeout` is gone.
+#[derive(Debug, Clone, Copy)]
+pub struct Heartbeat {
+    timeout: Duration,
+    last_seen: Instant,
+}
+
+impl Heartbeat {
+    pub fn new(timeout: Duration, now: Instant) -> Self {
+        Self {
+            timeout,
+            last_seen: now,
+        }
+    }
+
+    /// Record a frame from the client.
+    pub fn seen(&mut self, now: Instant) {
+        self.last_seen = now;
+    }
+
+    /// True once the client has been silent for longer than the timeout.
+    pub fn expired(&self, now: Instant) -> bool {
+        now.duration_since(self.last_seen) > self.timeout
+    }
+}
+
 /// Click event for real-time updates
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClickEvent {
@@ -64,6 +176,112 @@ pub struct ClickEvent {
     pub device: Option<String>,
     pub browser: Option<String>,
     pub timestamp: String,
+    /// Clicks this event stands for: 1, or more when a burst on a hot link was
+    /// coalesced (the other fields describe the latest click).
+    #[serde(default = "one_click")]
+    pub clicks: u32,
+}
+
+fn one_click() -> u32 {
+    1
+}
+
+/// Outcome of offering a click to [`ClickCoalescer`].
+#[derive(Debug)]
+pub enum Coalesced {
+    /// Broadcast this event now.
+    Send(ClickEvent),
+    /// Folded into a pending update. `flush_in` is set when the caller should
+    /// [`ClickCoalescer::flush`] the link after that delay.
+    Held { flush_in: Option<Duration> },
+}
+
+/// Per-link throttle for realtime click events. Below `threshold` clicks per
+/// second every click goes out as-is; above it clicks are merged and sent at
+/// most once per `interval`.
+pub struct ClickCoalescer {
+    threshold: u32,
+    interval: Duration,
+    links: Mutex<HashMap<i32, LinkWindow>>,
+}
+
+struct LinkWindow {
+    window_start: Instant,
+    clicks_in_window: u32,
+    last_sent: Option<Instant>,
+    pending: Option<ClickEvent>,
+    flush_scheduled: bool,
+}
+
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Above this many tracked links, idle ones are dropped on the next click.
+const MAX_TRACKED_LINKS: usize = 4096;
+
+impl ClickCoalescer {
+    pub fn new(threshold: u32, interval: Duration) -> Self {
+        Self {
+            threshold,
+            interval,
+            links: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn offer(&self, mut event: ClickEvent, now: Instant) -> Coalesced {
+        let mut links = self.links.lock();
+        if links.len() >= MAX_TRACKED_LINKS {
+            links.retain(|_, w| {
+                w.pending.is_some() || now.duration_since(w.window_start) < RATE_WINDOW
+            });
+        }
+        let window = links.entry(event.link_id).or_insert(LinkWindow {
+            window_start: now,
+            clicks_in_window: 0,
+            last_sent: None,
+            pending: None,
+            flush_scheduled: false,
+        });
+
+        if now.duration_since(window.window_start) >= RATE_WINDOW {
+            window.window_start = now;
+            window.clicks_in_window = 0;
+        }
+        window.clicks_in_window += 1;
+
+        if let Some(pending) = window.pending.take() {
+            event.clicks += pending.clicks;
+        }
+
+        let interval_elapsed = window
+            .last_sent
+            .is_none_or(|sent| now.duration_since(sent) >= self.interval);
+        if window.clicks_in_window <= self.threshold || interval_elapsed {
+            window.last_sent = Some(now);
+            return Coalesced::Send(event);
+        }
+
+        window.pending = Some(event);
+        if window.flush_scheduled {
+            return Coalesced::Held { flush_in: None };
+        }
+        window.flush_scheduled = true;
+        let since_sent = window
+            .last_sent
+            .map_or(Duration::ZERO, |sent| now.duration_since(sent));
+        Coalesced::Held {
+            flush_in: Some(self.interval.saturating_sub(since_sent)),
+        }
+    }
+
+    /// Take the pending update for `link_id`, if clicks were held back.
+    pub fn flush(&self, link_id: i32, now: Instant) -> Option<ClickEvent> {
+        let mut links = self.links.lock();
+        let window = links.get_mut(&link_id)?;
+        window.flush_scheduled = false;
+        let event = window.pending.take()?;
+        window.last_sent = Some(now);
+        Some(event)
+    }
 }
 
 /// WebSocket message types
@@ -189,9 +407,34 @@ async fn handle_socket(
     let mut global_rx = ws_state.click_tx.subscribe();
     let mut revalidate = tokio::time::interval(ws_state.auth_revalidate_interval);
     revalidate.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    // Ping on a fixed interval; a client silent for two of them missed a pong.
+    let mut ping = ws_state.ping_interval.map(|period| {
+        let mut ping = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        ping.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        (ping, Heartbeat::new(period * 2, Instant::now()))
+    });
 
     loop {
         tokio::select! {
+            _ = async {
+                match ping.as_mut() {
+                    Some((ping, _)) => {
+                        ping.tick().await;
+                    }
+                    None => std::future::pending().await,
+                }
+            } => {
+                let Some((_, heartbeat)) = ping.as_ref() else {
+                    continue;
+                };
+                if heartbeat.expired(Instant::now()) {
+                    let _ = sender.send(Message::Close(None)).await;
+                    break;
+                }
+                if sender.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+            }
             _ = revalidate.tick() => {
                 let current_user = resolve_ws_user(
                     &db,
@@ -219,7 +462,14 @@ async fn handle_socket(
                 Err(broadcast::error::RecvError::Closed) => break,
             },
             incoming = receiver.next() => match incoming {
-                Some(Ok(Message::Text(text))) => {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(message)) => {
+                    if let Some((_, heartbeat)) = ping.as_mut() {
+                        heartbeat.seen(Instant::now());
+                    }
+                    let Message::Text(text) = message else {
+                        continue;
+                    };
                     if let Ok(ws_msg) = serde_json::from_str::<WsMessage>(&text) {
                         match ws_msg {
                             WsMessage::Ping => {
@@ -232,8 +482,6 @@ async fn handle_socket(
                         }
                     }
                 }
-                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
-                Some(Ok(_)) => {}
             },
         }
     }
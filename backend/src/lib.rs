@@ -82,20 +82,49 @@ pub async fn https_redirect(req: Request<Body>, next: axum::middleware::Next) ->
     if is_https {
         next.run(req).await
     } else {
-        // Get host from headers
-        let host = req
-            .headers()
-            .get("host")
-            .and_then(|h| h.to_str().ok())
-            .unwrap_or("localhost");
+        let host = https_redirect_host(req.headers().get("host").and_then(|h| h.to_str().ok()));
 
-        let uri = req.uri();
-        let redirect_url = format!("https://{}{}", host, uri);
+        // Only the path and query: an absolute-form request URI carries its
+        // own scheme and authority, which must not leak into the redirect.
+        let path = req
+            .uri()
+            .path_and_query()
+            .map(|pq| pq.as_str())
+            .unwrap_or("/");
+        let redirect_url = format!("https://{}{}", host, path);
 
         Redirect::permanent(&redirect_url).into_response()
     }
 }
 
+/// Host to send an HTTP request to over HTTPS. The client's `Host` header is
+/// only echoed back when it appears in `ALLOWED_HOSTS` (comma-separated,
+/// case-insensitive); anything else gets `BASE_URL`'s host so a forged header
+/// can't turn the redirect into an open redirect.
+pub fn https_redirect_host(requested: Option<&str>) -> String {
+    let allowed = std::env::var("ALLOWED_HOSTS").unwrap_or_default();
+    if let Some(host) = requested.map(|h| h.trim().to_ascii_lowercase()) {
+        if allowed
+            .split(',')
+            .map(|h| h.trim().to_ascii_lowercase())
+            .any(|h| !h.is_empty() && h == host)
+        {
+            return host;
+        }
+    }
+
+    std::env::var("BASE_URL")
+        .ok()
+        .and_then(|base| url::Url::parse(&base).ok())
+        .and_then(|base| {
+            base.host_str().map(|host| match base.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host.to_string(),
+            })
+        })
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
 /// Ensure at least one admin exists in the system
 /// If no admins exist, promote the first user to admin
 pub async fn ensure_admin_exists(db: &DatabaseConnection) {
@@ -247,12 +276,18 @@ pub async fn health_check(
             .as_ref()
             .is_some_and(|e| e.is_configured());
         let backup_configured = state.backup.is_configured();
+        // A deep click backlog means flushes are failing; the instance still
+        // serves redirects, so this degrades the status without failing it.
+        let click_buffer_pending = state.click_buffer.len();
+        let backlogged = click_buffer_pending > utils::click_buffer::backlog_threshold();
         let status = serde_json::json!({
-            "status": "healthy",
+            "status": if backlogged { "degraded" } else { "healthy" },
             "database": "connected",
             "redis": if state.redis_cache.is_some() { "connected" } else { "disabled" },
             "email": if email_configured { "configured" } else { "disabled" },
-            "backup": if backup_configured { "configured" } else { "disabled" }
+            "backup": if backup_configured { "configured" } else { "disabled" },
+            "click_buffer_pending": click_buffer_pending,
+            "click_buffer_dropped": state.click_buffer.dropped_clicks()
         });
         (StatusCode::OK, axum::Json(status)).into_response()
     } else {
@@ -264,6 +299,34 @@ pub async fn health_check(
     }
 }
 
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct VersionResponse {
+    /// Crate version from Cargo.toml.
+    pub version: String,
+    /// Commit the binary was built from (`GIT_SHA` at build time).
+    pub git_sha: Option<String>,
+    /// When the binary was built (`BUILD_TIMESTAMP` at build time).
+    pub build_timestamp: Option<String>,
+}
+
+/// Which build is running. Baked in at compile time, so it is free to serve.
+#[utoipa::path(
+    get,
+    path = "/version",
+    responses(
+        (status = 200, description = "Server build information", body = VersionResponse),
+    ),
+    tag = "System"
+)]
+pub async fn get_version() -> axum::Json<VersionResponse> {
+    let baked = |value: Option<&str>| value.filter(|v| !v.is_empty()).map(str::to_string);
+    axum::Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_sha: baked(option_env!("GIT_SHA")),
+        build_timestamp: baked(option_env!("BUILD_TIMESTAMP")),
+    })
+}
+
 /// Build the complete application router — the single source of truth for
 /// routes and middleware, shared by the binary and the integration tests.
 ///
@@ -309,6 +372,28 @@ pub fn build_router(app_state: AppState) -> Router {
             "/auth/api-keys/:id",
             delete(handlers::api_keys::delete_api_key),
         )
+        .route(
+            "/webhooks",
+            get(handlers::webhooks::list_webhooks).post(handlers::webhooks::create_webhook),
+        )
+        .route("/webhooks/:id", delete(handlers::webhooks::delete_webhook))
+        .route(
+            "/webhooks/:id/deliveries",
+            get(handlers::webhooks::list_webhook_deliveries),
+        )
+        .route(
+            "/webhooks/deliveries/:id/retry",
+            post(handlers::webhooks::retry_webhook_delivery),
+        )
+        .route(
+            "/integrations",
+            get(handlers::integrations::list_integrations)
+                .post(handlers::integrations::create_integration),
+        )
+        .route(
+            "/integrations/:id",
+            delete(handlers::integrations::delete_integration),
+        )
         .route(
             "/auth/passkey/register/start",
             post(handlers::passkeys::register_start),
@@ -353,11 +438,18 @@ pub fn build_router(app_state: AppState) -> Router {
             "/links/check-code",
             get(handlers::links::check_code_availability),
         )
+        .route("/links/reserve", post(handlers::links::reserve_link))
+        .route(
+            "/links/claim-batch",
+            post(handlers::links::claim_links_batch),
+        )
         .route(
             "/links/health-check",
             post(handlers::links::check_url_health),
         )
+        .route("/links/broken", get(handlers::links::get_broken_links))
         .route("/links/build-utm", post(handlers::links::build_utm_url))
+        .route("/links/preview-url", post(handlers::links::preview_url))
         .route("/links/sparklines", get(handlers::links::get_sparklines))
         .route(
             "/links/preview-metadata",
@@ -371,10 +463,22 @@ pub fn build_router(app_state: AppState) -> Router {
         .route("/links/:id/clone", post(handlers::links::clone_link))
         .route("/links/:id/pin", post(handlers::links::toggle_pin))
         .route("/links/:id/stats", get(handlers::analytics::get_link_stats))
+        .route(
+            "/links/:id/clicks",
+            get(handlers::analytics::get_click_events),
+        )
         .route(
             "/links/:id/clicks/realtime",
             get(handlers::analytics::get_realtime_clicks),
         )
+        .route(
+            "/links/:id/heatmap",
+            get(handlers::analytics::get_click_heatmap),
+        )
+        .route(
+            "/links/:id/compare",
+            get(handlers::analytics::compare_link_periods),
+        )
         .route(
             "/links/:id/tags",
             post(handlers::tags::add_tags_to_link).delete(handlers::tags::remove_tags_from_link),
@@ -383,6 +487,7 @@ pub fn build_router(app_state: AppState) -> Router {
             "/links/:id/rules",
             get(handlers::links::get_routing_rules).put(handlers::links::replace_routing_rules),
         )
+        .route("/links/:id/history", get(handlers::links::get_link_history))
         // Analytics routes
         .route(
             "/analytics/dashboard",
@@ -394,6 +499,10 @@ pub fn build_router(app_state: AppState) -> Router {
             get(handlers::organizations::get_user_organizations)
                 .post(handlers::organizations::create_organization),
         )
+        .route(
+            "/orgs/import",
+            post(handlers::organizations::import_organization),
+        )
         .route(
             "/orgs/:org_id",
             get(handlers::organizations::get_organization)
@@ -414,10 +523,26 @@ pub fn build_router(app_state: AppState) -> Router {
             "/orgs/:org_id/transfer-ownership",
             post(handlers::organizations::transfer_ownership),
         )
+        .route(
+            "/orgs/:org_id/qr/regenerate",
+            post(handlers::organizations::regenerate_org_qr_codes),
+        )
         .route(
             "/orgs/:org_id/audit",
             get(handlers::organizations::get_audit_log),
         )
+        .route(
+            "/orgs/:org_id/tags/suggest",
+            get(handlers::tags::suggest_org_tags),
+        )
+        .route(
+            "/orgs/:org_id/links/export",
+            get(handlers::organizations::export_org_links),
+        )
+        .route(
+            "/orgs/:org_id/export",
+            get(handlers::organizations::export_organization),
+        )
         // Folder routes
         .route(
             "/folders",
@@ -429,6 +554,11 @@ pub fn build_router(app_state: AppState) -> Router {
                 .put(handlers::folders::update_folder)
                 .delete(handlers::folders::delete_folder),
         )
+        // Static segment; takes priority over /folders/:folder_id/links.
+        .route(
+            "/folders/none/links",
+            post(handlers::folders::clear_links_folder),
+        )
         .route(
             "/folders/:folder_id/links",
             get(handlers::folders::get_folder_links).post(handlers::folders::move_links_to_folder),
@@ -444,6 +574,7 @@ pub fn build_router(app_state: AppState) -> Router {
                 .put(handlers::tags::update_tag)
                 .delete(handlers::tags::delete_tag),
         )
+        .route("/tags/bulk/delete", post(handlers::tags::bulk_delete_tags))
         .route("/tags/:tag_id/links", get(handlers::tags::get_links_by_tag))
         // Contact form
         .route("/contact", post(handlers::contact::send_contact_message))
@@ -467,6 +598,10 @@ pub fn build_router(app_state: AppState) -> Router {
             "/admin/users/:user_id/enable",
             post(handlers::admin::enable_user),
         )
+        .route(
+            "/admin/users/:user_id/impersonate",
+            post(handlers::admin::impersonate_user),
+        )
         .route(
             "/admin/users/:user_id/make-admin",
             post(handlers::admin::make_admin),
@@ -480,6 +615,12 @@ pub fn build_router(app_state: AppState) -> Router {
             post(handlers::admin::admin_verify_email),
         )
         .route("/admin/links", get(handlers::admin::get_all_links))
+        .route("/admin/search", get(handlers::admin::admin_search))
+        .route(
+            "/admin/links/expired",
+            get(handlers::admin::get_expired_links),
+        )
+        .route("/admin/links/top", get(handlers::admin::get_top_links))
         .route(
             "/admin/links/bulk/delete",
             post(handlers::admin::admin_bulk_delete_links),
@@ -496,6 +637,14 @@ pub fn build_router(app_state: AppState) -> Router {
             "/admin/links/:link_id/restore",
             post(handlers::admin::admin_restore_link),
         )
+        .route(
+            "/admin/links/:link_id/disable",
+            post(handlers::admin::admin_disable_link),
+        )
+        .route(
+            "/admin/links/:link_id/enable",
+            post(handlers::admin::admin_enable_link),
+        )
         .route(
             "/admin/links/:link_id/block-domain",
             post(handlers::admin::admin_block_domain_from_link),
@@ -539,11 +688,15 @@ pub fn build_router(app_state: AppState) -> Router {
         .route("/sse", get(handlers::websocket::sse_handler))
         // Health check
         .route("/health", get(health_check))
+        .route("/version", get(get_version))
         // Server-side avatar proxy so a public-bio visitor's browser never
         // connects to the (user-supplied) external avatar host directly.
         // Registered before /api/bio/:username so the static path is unambiguous.
         .route("/api/bio/avatar", get(handlers::links::proxy_bio_avatar))
         .route("/api/bio/:username", get(handlers::bio::get_public_bio))
+        // Short public profile URL; keyed by the unique bio username since
+        // display names aren't unique.
+        .route("/u/:username", get(handlers::bio::get_public_bio))
         // Redirect route (must be last to not conflict with other routes)
         .route("/:code/verify", post(handlers::links::verify_link_password))
         .route("/:code/preview", get(handlers::links::preview_link))
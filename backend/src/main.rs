@@ -76,7 +76,11 @@ async fn main() {
         let service = EmailService::new();
         if service.is_configured() {
             tracing::info!("Email service enabled");
-            Some(Arc::new(service))
+            // Mail goes through the persistent queue so SMTP hiccups are
+            // retried instead of dropped.
+            let service = Arc::new(service.with_queue(db.clone()));
+            utils::email_queue::spawn_worker(db.clone(), service.clone());
+            Some(service)
         } else {
             tracing::info!("Email service disabled (SMTP not configured)");
             None
@@ -92,6 +96,26 @@ async fn main() {
     // retention window (ANALYTICS_PII_RETENTION_DAYS, default ~13 months).
     utils::privacy::spawn_retention_task(db.clone());
 
+    // Minute sweep for scheduled links whose start time has passed: clears
+    // stale cache entries and sends opt-in activation emails.
+    utils::link_activation::spawn_activation_task(
+        db.clone(),
+        redis_cache.clone(),
+        email_service.clone(),
+    );
+
+    // Daily soft-delete of links expired longer than EXPIRED_LINK_GRACE_DAYS
+    // (off unless set).
+    utils::link_expiry::spawn_compaction_task(db.clone(), redis_cache.clone());
+
+    // Hourly sweep deleting accounts whose self-deletion grace period has
+    // passed (off unless ACCOUNT_DELETION_GRACE_DAYS is set).
+    utils::account_deletion::spawn_finalize_task(db.clone(), redis_cache.clone());
+
+    // Periodic HEAD checks of link destinations for `GET /links/broken`
+    // (off unless LINK_HEALTH_CHECK_INTERVAL_MINUTES is set).
+    utils::link_health::spawn_health_check_task(db.clone());
+
     // Initialize backup service
     let backup = Arc::new(BackupService::new().await);
     if backup.is_configured() {
@@ -2,7 +2,8 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::handlers::{
-    admin, analytics, api_keys, auth, bio, contact, folders, links, organizations, passkeys, tags,
+    admin, analytics, api_keys, auth, bio, contact, folders, integrations, links, organizations,
+    passkeys, tags, webhooks,
 };
 
 #[derive(OpenApi)]
@@ -35,6 +36,8 @@ use crate::handlers::{
         (name = "Admin", description = "Instance administration: users, links, organizations, blocking, backups"),
         (name = "Contact", description = "Contact form"),
         (name = "Bio", description = "Public link-in-bio pages"),
+        (name = "Webhooks", description = "Signed event notifications and Slack/Discord integrations"),
+        (name = "System", description = "Server build information"),
     ),
     paths(
         // Authentication
@@ -55,6 +58,16 @@ use crate::handlers::{
         api_keys::list_api_keys,
         api_keys::delete_api_key,
 
+        // Webhooks
+        webhooks::create_webhook,
+        webhooks::list_webhooks,
+        webhooks::delete_webhook,
+        webhooks::list_webhook_deliveries,
+        webhooks::retry_webhook_delivery,
+        integrations::create_integration,
+        integrations::list_integrations,
+        integrations::delete_integration,
+
         // Passkeys (WebAuthn)
         passkeys::register_start,
         passkeys::register_finish,
@@ -82,9 +95,14 @@ use crate::handlers::{
         links::export_links_csv,
         links::clone_link,
         links::toggle_pin,
+        links::get_link_history,
         links::check_code_availability,
+        links::reserve_link,
+        links::claim_links_batch,
         links::check_url_health,
+        links::get_broken_links,
         links::build_utm_url,
+        links::preview_url,
         links::get_sparklines,
         links::get_link_preview_metadata,
         links::preview_link,
@@ -93,12 +111,16 @@ use crate::handlers::{
         analytics::get_link_stats,
         analytics::get_dashboard_stats,
         analytics::get_realtime_clicks,
+        analytics::get_click_events,
+        analytics::get_click_heatmap,
+        analytics::compare_link_periods,
 
         // Organizations
         organizations::create_organization,
         organizations::get_user_organizations,
         organizations::get_organization,
         organizations::update_organization,
+        organizations::regenerate_org_qr_codes,
         organizations::delete_organization,
         organizations::get_organization_members,
         organizations::invite_member,
@@ -106,6 +128,9 @@ use crate::handlers::{
         organizations::remove_member,
         organizations::transfer_ownership,
         organizations::get_audit_log,
+        organizations::export_org_links,
+        organizations::export_organization,
+        organizations::import_organization,
 
         // Folders
         folders::create_folder,
@@ -114,6 +139,7 @@ use crate::handlers::{
         folders::update_folder,
         folders::delete_folder,
         folders::move_links_to_folder,
+        folders::clear_links_folder,
         folders::get_folder_links,
 
         // Tags
@@ -122,9 +148,11 @@ use crate::handlers::{
         tags::get_tag,
         tags::update_tag,
         tags::delete_tag,
+        tags::bulk_delete_tags,
         tags::add_tags_to_link,
         tags::remove_tags_from_link,
         tags::get_links_by_tag,
+        tags::suggest_org_tags,
 
         // Admin
         admin::get_admin_stats,
@@ -134,12 +162,18 @@ use crate::handlers::{
         admin::hard_delete_user,
         admin::restore_user,
         admin::enable_user,
+        admin::impersonate_user,
         admin::make_admin,
         admin::remove_admin,
         admin::admin_verify_email,
         admin::get_all_links,
+        admin::admin_search,
+        admin::get_expired_links,
+        admin::get_top_links,
         admin::admin_delete_link,
         admin::admin_restore_link,
+        admin::admin_disable_link,
+        admin::admin_enable_link,
         admin::admin_bulk_delete_links,
         admin::admin_bulk_restore_links,
         admin::admin_block_domain_from_link,
@@ -159,6 +193,9 @@ use crate::handlers::{
 
         // Contact
         contact::send_contact_message,
+
+        // System
+        crate::get_version,
     ),
     components(
         schemas(
@@ -166,17 +203,42 @@ use crate::handlers::{
             auth::RegisterRequest,
             auth::LoginRequest,
             auth::AuthResponse,
+            auth::OrgMembershipSummary,
             auth::MessageResponse,
+            auth::VerifyEmailRequest,
+            auth::ResendVerificationRequest,
+            auth::ForgotPasswordRequest,
+            auth::ResetPasswordRequest,
+            auth::ChangePasswordRequest,
+            auth::DeleteAccountRequest,
+            auth::UpdateProfileRequest,
+            auth::UserProfileResponse,
+            auth::AppSettingsResponse,
 
             // API key schemas
             api_keys::CreateApiKeyRequest,
             api_keys::CreateApiKeyResponse,
             api_keys::ApiKeyInfo,
 
-            // Passkey schemas (WebAuthn ceremony bodies are opaque and not expanded)
+            // Webhook schemas
+            webhooks::CreateWebhookRequest,
+            webhooks::CreateWebhookResponse,
+            webhooks::WebhookInfo,
+            webhooks::WebhookDeliveryInfo,
+            integrations::CreateIntegrationRequest,
+            integrations::IntegrationsQuery,
+            integrations::IntegrationResponse,
+
+            // Passkey schemas (WebAuthn credentials are opaque objects and not expanded)
             passkeys::PasskeyAuthResponse,
             passkeys::PasskeyInfo,
             passkeys::PasskeyListResponse,
+            passkeys::RegisterStartRequest,
+            passkeys::RegisterFinishRequest,
+            passkeys::LoginStartRequest,
+            passkeys::LoginFinishRequest,
+            passkeys::DeletePasskeyRequest,
+            passkeys::RenamePasskeyRequest,
 
             // Link-in-bio schemas
             bio::BioSettingsRequest,
@@ -186,20 +248,45 @@ use crate::handlers::{
 
             // Link schemas
             links::CreateLinkRequest,
+            links::ReserveLinkRequest,
+            links::ClaimLinksRequest,
+            links::ClaimLinksResponse,
             links::UpdateLinkRequest,
             links::BulkCreateLinkRequest,
+            links::BulkLinkItem,
+            links::BulkLinkSpec,
             links::BulkDeleteRequest,
             links::BulkUpdateRequest,
             links::LinksQuery,
             links::LinkResponse,
             links::CreateLinkResponse,
             links::BulkCreateLinkResponse,
+            links::BulkCreateResult,
             links::BulkDeleteResponse,
             links::BulkUpdateResponse,
             links::ErrorResponse,
+            links::AliasConflictResponse,
             links::SuccessResponse,
             links::VerifyPasswordRequest,
             links::TagInfo,
+            links::LinkUrlHistoryEntry,
+            links::PreviewUrlRequest,
+            links::PreviewUrlResponse,
+            links::LinkPreviewResponse,
+            links::PreviewMetadataRequest,
+            links::LinkPreviewData,
+            links::HealthCheckRequest,
+            links::UrlHealthResponse,
+            links::ReputationInfo,
+            links::BrokenLinksQuery,
+            links::BrokenLinkResponse,
+            links::CheckCodeResponse,
+            links::BuildUtmRequest,
+            links::BuildUtmResponse,
+            links::SparklineResponse,
+            links::SparklineData,
+            links::CloneLinkResponse,
+            links::PinResponse,
 
             // Analytics schemas
             analytics::AnalyticsQuery,
@@ -212,7 +299,14 @@ use crate::handlers::{
             analytics::BrowserStats,
             analytics::OsStats,
             analytics::RefererStats,
+            analytics::SourceStats,
             analytics::RecentClick,
+            analytics::ClickEventsQuery,
+            analytics::ClickEventsPage,
+            analytics::HeatmapQuery,
+            analytics::HeatmapResponse,
+            analytics::CompareQuery,
+            analytics::PeriodComparisonResponse,
             analytics::GeoPoint,
             analytics::TopLink,
 
@@ -223,8 +317,17 @@ use crate::handlers::{
             organizations::UpdateMemberRoleRequest,
             organizations::TransferOwnershipRequest,
             organizations::OrgResponse,
+            organizations::QrVersionResponse,
             organizations::OrgMemberResponse,
             organizations::AuditLogResponse,
+            organizations::OrgLinkExport,
+            organizations::OrgExportBundle,
+            organizations::OrgBundleMeta,
+            organizations::OrgBundleMember,
+            organizations::OrgBundleFolder,
+            organizations::OrgBundleTag,
+            organizations::OrgBundleLink,
+            organizations::OrgImportResponse,
 
             // Folder schemas
             folders::CreateFolderRequest,
@@ -237,17 +340,25 @@ use crate::handlers::{
             tags::CreateTagRequest,
             tags::UpdateTagRequest,
             tags::TagQuery,
+            tags::TagSuggestQuery,
             tags::TagResponse,
             tags::AddTagsToLinkRequest,
             tags::RemoveTagsFromLinkRequest,
+            tags::BulkDeleteTagsRequest,
+            tags::BulkDeleteTagsResponse,
 
             // Admin schemas
             admin::AdminResponse,
+            admin::ImpersonateRequest,
+            admin::ImpersonateResponse,
             admin::AdminStatsResponse,
             admin::AdminUserResponse,
             admin::AdminUsersListResponse,
             admin::AdminLinkResponse,
             admin::AdminLinksListResponse,
+            admin::AdminTopLinkResponse,
+            admin::AdminTopLinksResponse,
+            admin::AdminSearchResponse,
             admin::BulkLinkIdsRequest,
             admin::BulkLinkActionResponse,
             admin::BlockFromLinkResponse,
@@ -263,6 +374,16 @@ use crate::handlers::{
             admin::BlockedEmailDomainResponse,
             admin::BackupResponse,
             admin::BackupListResponse,
+
+            // Contact schemas
+            contact::ContactRequest,
+            contact::ContactResponse,
+
+            // System schemas
+            crate::VersionResponse,
+
+            // Shared schemas
+            crate::utils::validation::ValidationErrorResponse,
         )
     ),
     modifiers(&SecurityAddon)
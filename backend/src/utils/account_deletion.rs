@@ -0,0 +1,177 @@
+//! Account self-deletion. With `ACCOUNT_DELETION_GRACE_DAYS` set, deleting an
+//! account only schedules it; signing in before the date cancels, and an
+//! hourly sweep deletes the accounts whose date has passed.
+
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, TransactionTrait,
+};
+use std::sync::Arc;
+
+use crate::entity::{api_keys, links, passkeys, users};
+use crate::utils::cache::RedisCache;
+
+/// Grace period before a self-deleted account is removed, from
+/// `ACCOUNT_DELETION_GRACE_DAYS`. Unset, `0` or invalid deletes immediately.
+pub fn account_deletion_grace_days() -> Option<i64> {
+    std::env::var("ACCOUNT_DELETION_GRACE_DAYS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|d| *d > 0)
+}
+
+/// Soft-delete `user` and clean up what goes with the account: personal
+/// links, click PII, API keys and passkeys. Returns the codes of the deleted
+/// links so their cache entries can be dropped once the caller commits.
+pub async fn delete_account_data<C: ConnectionTrait>(
+    txn: &C,
+    user: users::Model,
+    deleted_at: chrono::NaiveDateTime,
+) -> Result<Vec<String>, sea_orm::DbErr> {
+    let user_id = user.id;
+    let cached_codes = links::Entity::find()
+        .filter(links::Column::UserId.eq(user_id))
+        .filter(links::Column::OrgId.is_null())
+        .filter(links::Column::DeletedAt.is_null())
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|link| link.code)
+        .collect::<Vec<_>>();
+
+    let next_token_version = user
+        .token_version
+        .checked_add(1)
+        .ok_or_else(|| sea_orm::DbErr::Custom("token_version overflow".to_string()))?;
+
+    let mut active_user: users::ActiveModel = user.into();
+    active_user.deleted_at = Set(Some(deleted_at));
+    active_user.deletion_scheduled_at = Set(None);
+    active_user.token_version = Set(next_token_version);
+    // Free the bio username for reuse and take the public page down.
+    active_user.bio_username = Set(None);
+    active_user.bio_enabled = Set(false);
+    active_user.update(txn).await?;
+
+    // Organization links belong to the team, not their creator.
+    links::Entity::update_many()
+        .col_expr(links::Column::DeletedAt, Expr::value(deleted_at))
+        .filter(links::Column::UserId.eq(user_id))
+        .filter(links::Column::OrgId.is_null())
+        .filter(links::Column::DeletedAt.is_null())
+        .exec(txn)
+        .await?;
+
+    crate::utils::privacy::purge_click_pii_for_user(txn, user_id).await?;
+
+    api_keys::Entity::delete_many()
+        .filter(api_keys::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    passkeys::Entity::delete_many()
+        .filter(passkeys::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+
+    Ok(cached_codes)
+}
+
+/// Cancel the scheduled deletion of `user_id` on sign-in. `Ok(false)` means it is
+/// no longer pending: the sweep got to the account first.
+pub async fn cancel_scheduled_deletion<C: ConnectionTrait>(
+    db: &C,
+    user_id: i32,
+) -> Result<bool, sea_orm::DbErr> {
+    let res = users::Entity::update_many()
+        .col_expr(
+            users::Column::DeletionScheduledAt,
+            Expr::value(Option::<chrono::NaiveDateTime>::None),
+        )
+        .filter(users::Column::Id.eq(user_id))
+        .filter(users::Column::DeletionScheduledAt.is_not_null())
+        .filter(users::Column::DeletedAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+/// Delete every account whose scheduled deletion is due as of `now` and
+/// return the link codes to drop from the cache. An account that meanwhile
+/// became the owner of an org with other members is left scheduled and
+/// retried on the next sweep.
+pub async fn finalize_due_deletions(
+    db: &DatabaseConnection,
+    now: chrono::NaiveDateTime,
+) -> Result<Vec<String>, sea_orm::DbErr> {
+    let due = users::Entity::find()
+        .filter(users::Column::DeletionScheduledAt.lte(now))
+        .filter(users::Column::DeletedAt.is_null())
+        .all(db)
+        .await?;
+
+    let mut cached_codes = Vec::new();
+    for user in due {
+        let txn = db.begin().await?;
+        // Re-check under the row lock: a sign-in may have cancelled it.
+        let user = users::Entity::find_by_id(user.id)
+            .filter(users::Column::DeletionScheduledAt.lte(now))
+            .filter(users::Column::DeletedAt.is_null())
+            .lock_exclusive()
+            .one(&txn)
+            .await?;
+        let Some(user) = user else {
+            txn.rollback().await?;
+            continue;
+        };
+
+        let split = crate::handlers::organizations::split_owned_orgs(&txn, user.id).await?;
+        if !split.blocking.is_empty() {
+            tracing::warn!(
+                "Scheduled deletion of user {} postponed: still owns organizations with other members",
+                user.id
+            );
+            txn.rollback().await?;
+            continue;
+        }
+
+        let codes = delete_account_data(&txn, user, now).await?;
+        txn.commit().await?;
+        cached_codes.extend(codes);
+    }
+    Ok(cached_codes)
+}
+
+/// Spawn the hourly sweep finalizing scheduled deletions when
+/// `ACCOUNT_DELETION_GRACE_DAYS` is set.
+pub fn spawn_finalize_task(db: DatabaseConnection, redis_cache: Option<Arc<RedisCache>>) {
+    let Some(days) = account_deletion_grace_days() else {
+        tracing::info!(
+            "Account deletion grace period disabled (ACCOUNT_DELETION_GRACE_DAYS unset)"
+        );
+        return;
+    };
+
+    tracing::info!(
+        "Account deletion grace period enabled: accounts are deleted {} days after the request",
+        days
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            match finalize_due_deletions(&db, chrono::Utc::now().naive_utc()).await {
+                Ok(codes) => {
+                    if let Some(cache) = redis_cache.as_deref() {
+                        for code in &codes {
+                            if let Err(e) = cache.invalidate_link(code).await {
+                                tracing::warn!("Failed to invalidate cache for {}: {}", code, e);
+                            }
+                        }
+                    }
+                }
+                Err(e) => tracing::error!("Scheduled account deletion sweep failed: {}", e),
+            }
+        }
+    });
+}
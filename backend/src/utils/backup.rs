@@ -6,6 +6,7 @@ use flate2::write::GzEncoder;
 use flate2::Compression;
 use std::io::Write;
 use tokio::process::Command;
+use tokio::sync::{Mutex, MutexGuard};
 use tracing::{error, info};
 
 /// Backup service for PostgreSQL to S3/R2
@@ -13,6 +14,25 @@ pub struct BackupService {
     s3_client: Option<S3Client>,
     bucket: String,
     database_url: String,
+    /// Held for the whole dump + upload so two backups never run at once.
+    running: Mutex<()>,
+}
+
+/// Why [`BackupService::create_backup`] didn't produce a backup.
+#[derive(Debug, PartialEq)]
+pub enum BackupError {
+    /// Another backup is still running; nothing was started.
+    InProgress,
+    Failed(String),
+}
+
+impl std::fmt::Display for BackupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackupError::InProgress => f.write_str("Backup already in progress"),
+            BackupError::Failed(e) => f.write_str(e),
+        }
+    }
 }
 
 impl BackupService {
@@ -54,6 +74,7 @@ impl BackupService {
             s3_client,
             bucket,
             database_url,
+            running: Mutex::new(()),
         }
     }
 
@@ -61,8 +82,19 @@ impl BackupService {
         self.s3_client.is_some()
     }
 
-    /// Create a backup and upload to S3
-    pub async fn create_backup(&self) -> Result<String, String> {
+    /// Claim the backup lock, or `None` while another backup holds it.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, ()>> {
+        self.running.try_lock().ok()
+    }
+
+    /// Create a backup and upload to S3. Fails fast with
+    /// [`BackupError::InProgress`] instead of queueing behind a running one.
+    pub async fn create_backup(&self) -> Result<String, BackupError> {
+        let _running = self.try_lock().ok_or(BackupError::InProgress)?;
+        self.dump_and_upload().await.map_err(BackupError::Failed)
+    }
+
+    async fn dump_and_upload(&self) -> Result<String, String> {
         let client = self
             .s3_client
             .as_ref()
@@ -203,6 +235,30 @@ impl Clone for BackupService {
             s3_client: None, // Will be re-initialized when needed
             bucket: self.bucket.clone(),
             database_url: self.database_url.clone(),
+            running: Mutex::new(()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn second_backup_is_rejected_while_one_runs() {
+        let service = BackupService::new().await;
+
+        let held = service.try_lock().expect("lock is free");
+        assert_eq!(service.create_backup().await, Err(BackupError::InProgress));
+        assert!(service.try_lock().is_none());
+
+        // Once released the next call gets past the lock (and fails on the
+        // missing S3 config instead).
+        drop(held);
+        assert!(matches!(
+            service.create_backup().await,
+            Err(BackupError::Failed(_))
+        ));
+        assert!(service.try_lock().is_some());
+    }
+}
@@ -16,6 +16,8 @@ pub struct CachedLink {
     pub user_id: Option<i32>,
     /// When true, redirect must go through the frontend interstitial first.
     pub safe_link_interstitial: bool,
+    /// Per-link redirects-per-second budget, enforced on cache hits too.
+    pub redirect_rate_limit: Option<i32>,
 }
 
 impl CachedLink {
@@ -30,6 +32,7 @@ impl CachedLink {
             "click_count": self.click_count,
             "user_id": self.user_id,
             "safe_link_interstitial": self.safe_link_interstitial,
+            "redirect_rate_limit": self.redirect_rate_limit,
         })
         .to_string()
     }
@@ -46,6 +49,7 @@ impl CachedLink {
             click_count: json["click_count"].as_i64()? as i32,
             user_id: json["user_id"].as_i64().map(|n| n as i32),
             safe_link_interstitial: json["safe_link_interstitial"].as_bool().unwrap_or(false),
+            redirect_rate_limit: json["redirect_rate_limit"].as_i64().map(|n| n as i32),
         })
     }
 }
@@ -97,6 +101,10 @@ impl RedisCache {
         format!("link_generation:{}", code)
     }
 
+    fn link_rate_key(link_id: i32, second: i64) -> String {
+        format!("link_rate:{}:{}", link_id, second)
+    }
+
     /// Read a cached link and its invalidation generation in one Redis command.
     ///
     /// Writers capture this generation before loading from Postgres and may only
@@ -180,6 +188,30 @@ impl RedisCache {
         Ok(())
     }
 
+    /// Count a redirect of `link_id` against the current one-second window and
+    /// return how many redirects that window has seen, shared by every
+    /// instance. `None` when Redis is not connected.
+    pub async fn hit_link_rate(&self, link_id: i32) -> Result<Option<u64>, redis::RedisError> {
+        let conn_guard = self.connection.read().await;
+        let Some(conn) = conn_guard.as_ref() else {
+            return Ok(None);
+        };
+
+        let mut conn = conn.clone();
+        let key = Self::link_rate_key(link_id, chrono::Utc::now().timestamp());
+        let (count,): (u64,) = redis::pipe()
+            .atomic()
+            .cmd("INCR")
+            .arg(&key)
+            .cmd("EXPIRE")
+            .arg(&key)
+            .arg(2)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+        Ok(Some(count))
+    }
+
     /// Update click count in cache
     pub async fn increment_clicks(&self, code: &str) -> Result<(), redis::RedisError> {
         // Invalidate the cache so the next request fetches fresh data
@@ -217,6 +249,7 @@ mod tests {
             click_count: 0,
             user_id: Some(1),
             safe_link_interstitial: false,
+            redirect_rate_limit: None,
         }
     }
 
@@ -1,20 +1,24 @@
 use parking_lot::RwLock;
+use sea_orm::sea_query::LockType;
 use sea_orm::{
     ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Set, TransactionTrait,
 };
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::interval;
 use tracing::{error, info, warn};
 
 use crate::entity::{click_events, links};
+use crate::utils::webhook;
 
 /// Click event data to be batched
 #[derive(Clone, Debug)]
 pub struct ClickData {
     pub link_id: i32,
     pub ip_address: Option<String>,
+    pub ip_hash: Option<String>,
     pub user_agent: Option<String>,
     pub referer: Option<String>,
     pub country: Option<String>,
@@ -25,6 +29,125 @@ pub struct ClickData {
     pub device: Option<String>,
     pub browser: Option<String>,
     pub os: Option<String>,
+    pub source: Option<String>,
+    pub is_preview: bool,
+    /// Clicks this event stands for; set by [`ClickBuffer::add_click`] when
+    /// sampling a hot link, 1 otherwise.
+    pub sample_rate: i32,
+}
+
+/// Fire `link.milestone` for each threshold the link passed in this flush.
+/// `link` carries the post-update click_count; `count` is what was just added.
+fn notify_milestones(db: &DatabaseConnection, link: &links::Model, count: i32) {
+    for milestone in webhook::crossed_milestones(link.click_count - count, link.click_count) {
+        webhook::dispatch(
+            db.clone(),
+            webhook::Audience::Link {
+                user_id: link.user_id,
+                org_id: link.org_id,
+            },
+            webhook::EVENT_LINK_MILESTONE,
+            serde_json::json!({
+                "link_id": link.id,
+                "code": link.code,
+                "short_url": format!("{}/{}", crate::handlers::links::get_base_url(), link.code),
+                "milestone": milestone,
+                "click_count": link.click_count,
+            }),
+        );
+    }
+}
+
+/// Pending events above which `/health` reports the buffer as backlogged
+/// (CLICK_BUFFER_HEALTH_THRESHOLD, default: 10000).
+pub fn backlog_threshold() -> usize {
+    std::env::var("CLICK_BUFFER_HEALTH_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10_000)
+}
+
+/// Most click events held in memory while flushes fail
+/// (CLICK_BUFFER_MAX_EVENTS, default: 100000). Past this, new analytics events
+/// are dropped instead of growing the process until it runs out of memory.
+pub fn max_pending_events() -> usize {
+    std::env::var("CLICK_BUFFER_MAX_EVENTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n: &usize| n > 0)
+        .unwrap_or(100_000)
+}
+
+/// Longest `User-Agent` kept on a click event, in characters
+/// (MAX_USER_AGENT_LENGTH, default: 512). Real browsers stay well under this;
+/// anything longer is cut before it reaches the buffer.
+pub fn max_user_agent_length() -> usize {
+    std::env::var("MAX_USER_AGENT_LENGTH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n: &usize| n > 0)
+        .unwrap_or(512)
+}
+
+/// Longest referring host kept on a click event, in characters
+/// (MAX_REFERER_LENGTH, default: 255).
+pub fn max_referer_length() -> usize {
+    std::env::var("MAX_REFERER_LENGTH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n: &usize| n > 0)
+        .unwrap_or(255)
+}
+
+/// 1-in-N storage of click events for links clicked more than `threshold`
+/// times a minute. The aggregate click_count still counts every click.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClickSampling {
+    pub threshold: u32,
+    pub rate: u32,
+}
+
+/// Sampling policy from CLICK_SAMPLING_THRESHOLD (clicks per link per
+/// minute; unset = store every event) and CLICK_SAMPLE_RATE (default: 10).
+pub fn click_sampling() -> Option<ClickSampling> {
+    let threshold = std::env::var("CLICK_SAMPLING_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n: &u32| n > 0)?;
+    let rate = std::env::var("CLICK_SAMPLE_RATE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n: &u32| n > 1)
+        .unwrap_or(10);
+    Some(ClickSampling { threshold, rate })
+}
+
+/// Length of the window a link's click rate is measured over.
+const SAMPLING_WINDOW: Duration = Duration::from_secs(60);
+
+/// Clicks a link got in its current sampling window.
+struct ClickRate {
+    window_start: std::time::Instant,
+    clicks: u32,
+}
+
+/// Window in which repeat clicks on a link from the same visitor count once
+/// (CLICK_DEBOUNCE_MS; unset or 0 = count every click). Catches double-clicks
+/// and link prefetchers; tracked per instance.
+pub fn click_debounce() -> Option<Duration> {
+    std::env::var("CLICK_DEBOUNCE_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n: &u64| n > 0)
+        .map(Duration::from_millis)
+}
+
+/// Cut `value` to at most `max` characters, never splitting a character.
+pub fn truncate_chars(value: &str, max: usize) -> &str {
+    match value.char_indices().nth(max) {
+        Some((end, _)) => &value[..end],
+        None => value,
+    }
 }
 
 /// Buffered click counter for aggregating click count updates
@@ -44,6 +167,22 @@ pub struct ClickBuffer {
     flush_interval_secs: u64,
     /// Signals the flush task to flush early once the buffer reaches max_buffer_size.
     flush_notify: Arc<tokio::sync::Notify>,
+    /// Hard cap on buffered events; see [`max_pending_events`].
+    max_pending: usize,
+    /// Events dropped because the buffer was at `max_pending`.
+    dropped: Arc<AtomicU64>,
+    /// Whether we're currently dropping, so the warning is logged once per
+    /// episode rather than once per click.
+    dropping: Arc<AtomicBool>,
+    /// See [`click_sampling`]; `None` stores every event.
+    sampling: Option<ClickSampling>,
+    /// Per-link click rate for sampling, pruned at each flush.
+    rates: Arc<parking_lot::Mutex<HashMap<i32, ClickRate>>>,
+    /// See [`click_debounce`]; `None` counts every click.
+    debounce: Option<Duration>,
+    /// When each (link, ip_hash) last had a click counted, pruned at each
+    /// flush.
+    last_counted: Arc<parking_lot::Mutex<HashMap<(i32, String), std::time::Instant>>>,
 }
 
 impl Default for ClickBuffer {
@@ -70,24 +209,85 @@ impl ClickBuffer {
             max_buffer_size,
             flush_interval_secs,
             flush_notify: Arc::new(tokio::sync::Notify::new()),
+            max_pending: max_pending_events(),
+            dropped: Arc::new(AtomicU64::new(0)),
+            dropping: Arc::new(AtomicBool::new(false)),
+            sampling: click_sampling(),
+            rates: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            debounce: click_debounce(),
+            last_counted: Arc::new(parking_lot::Mutex::new(HashMap::new())),
         }
     }
 
     /// Add a click event to the buffer and count it towards the link's
-    /// aggregate click_count (applied to links.click_count at flush).
-    pub fn add_click(&self, data: ClickData) {
-        let link_id = data.link_id;
-
-        // Increment counter
-        {
-            let mut counters = self.counters.write();
-            counters
-                .entry(link_id)
-                .and_modify(|c| c.count += 1)
-                .or_insert(ClickCounter { count: 1 });
+    /// aggregate click_count (applied to links.click_count at flush). When the
+    /// buffer is full only the analytics event is dropped; the count is a
+    /// single integer per link and is always kept. Past the sampling threshold
+    /// only every Nth event is kept, tagged with its sample rate.
+    pub fn add_click(&self, mut data: ClickData) {
+        self.add_count_only(data.link_id);
+        if let Some(rate) = self.sample(data.link_id) {
+            data.sample_rate = rate;
+            self.push_event(data);
         }
+    }
 
-        self.push_event(data);
+    /// The sample rate to store this click's event with, or `None` to skip
+    /// it. Every click is kept up to the threshold within a window; past it,
+    /// the first of each run of `rate` clicks is.
+    fn sample(&self, link_id: i32) -> Option<i32> {
+        let Some(sampling) = self.sampling else {
+            return Some(1);
+        };
+        let now = std::time::Instant::now();
+        let mut rates = self.rates.lock();
+        let rate = rates.entry(link_id).or_insert(ClickRate {
+            window_start: now,
+            clicks: 0,
+        });
+        if now.duration_since(rate.window_start) >= SAMPLING_WINDOW {
+            rate.window_start = now;
+            rate.clicks = 0;
+        }
+        rate.clicks += 1;
+        if rate.clicks <= sampling.threshold {
+            return Some(1);
+        }
+        ((rate.clicks - sampling.threshold - 1) % sampling.rate == 0)
+            .then_some(sampling.rate as i32)
+    }
+
+    /// Whether this visitor already had a click on the link counted within
+    /// the debounce window, in which case this one should be ignored. The
+    /// window runs from the counted click, so a steady stream of repeats
+    /// still counts once per window.
+    pub fn is_repeat_click(&self, link_id: i32, ip_hash: &str) -> bool {
+        let Some(window) = self.debounce else {
+            return false;
+        };
+        let now = std::time::Instant::now();
+        let mut last_counted = self.last_counted.lock();
+        match last_counted.get_mut(&(link_id, ip_hash.to_string())) {
+            Some(at) if now.duration_since(*at) < window => true,
+            Some(at) => {
+                *at = now;
+                false
+            }
+            None => {
+                last_counted.insert((link_id, ip_hash.to_string()), now);
+                false
+            }
+        }
+    }
+
+    /// Count a click towards the link's aggregate click_count without
+    /// buffering an analytics event, for visitors who opted out of tracking.
+    pub fn add_count_only(&self, link_id: i32) {
+        self.counters
+            .write()
+            .entry(link_id)
+            .and_modify(|c| c.count += 1)
+            .or_insert(ClickCounter { count: 1 });
     }
 
     /// Buffer only the analytics event row, without touching the aggregate
@@ -101,9 +301,18 @@ impl ClickBuffer {
     fn push_event(&self, data: ClickData) {
         let should_flush = {
             let mut events = self.events.write();
+            if events.len() >= self.max_pending {
+                drop(events);
+                self.record_dropped(1);
+                self.flush_notify.notify_one();
+                return;
+            }
             events.push(data);
             events.len() >= self.max_buffer_size
         };
+        if self.dropping.swap(false, Ordering::Relaxed) {
+            info!("Click buffer below capacity again; recording click events");
+        }
 
         // Trigger an early flush when the buffer is full so it can't grow
         // unbounded between timer ticks under load.
@@ -112,11 +321,36 @@ impl ClickBuffer {
         }
     }
 
+    fn record_dropped(&self, n: usize) {
+        self.dropped.fetch_add(n as u64, Ordering::Relaxed);
+        if !self.dropping.swap(true, Ordering::Relaxed) {
+            warn!(
+                "Click buffer full ({} events, flushes failing?); dropping new click events",
+                self.max_pending
+            );
+        }
+    }
+
+    /// Click events dropped since startup because the buffer was full.
+    pub fn dropped_clicks(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
     /// Check if buffer should be flushed
     pub fn should_flush(&self) -> bool {
         self.events.read().len() >= self.max_buffer_size
     }
 
+    /// Click events waiting to be flushed. Failed flushes re-queue their
+    /// events, so a steadily growing value means writes are failing.
+    pub fn len(&self) -> usize {
+        self.events.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.read().is_empty()
+    }
+
     /// Number of clicks buffered (not yet flushed to the DB) for a link.
     /// Used so click limits account for in-flight clicks, not just the DB count.
     pub fn pending_count(&self, link_id: i32) -> i32 {
@@ -141,6 +375,17 @@ impl ClickBuffer {
             std::mem::take(&mut *buffer)
         };
 
+        if self.sampling.is_some() {
+            self.rates
+                .lock()
+                .retain(|_, rate| rate.window_start.elapsed() < SAMPLING_WINDOW);
+        }
+        if let Some(window) = self.debounce {
+            self.last_counted
+                .lock()
+                .retain(|_, at| at.elapsed() < window);
+        }
+
         if events.is_empty() && counters.is_empty() {
             return;
         }
@@ -192,10 +437,12 @@ impl ClickBuffer {
 
             // Lock the active parent while its events and counter are written.
             // Missing/deleted parents are isolated and discarded; they cannot
-            // poison valid links in the same flush.
+            // poison valid links in the same flush. Taken at the strength the
+            // counter UPDATE needs: two concurrent flushes both upgrading from
+            // FOR SHARE deadlock. FOR NO KEY UPDATE doesn't block FK checks.
             let parent = links::Entity::find_by_id(link_id)
                 .filter(links::Column::DeletedAt.is_null())
-                .lock_shared()
+                .lock(LockType::NoKeyUpdate)
                 .one(&txn)
                 .await;
             match parent {
@@ -232,6 +479,7 @@ impl ClickBuffer {
                         .map(|e| click_events::ActiveModel {
                             link_id: Set(e.link_id),
                             ip_address: Set(e.ip_address),
+                            ip_hash: Set(e.ip_hash),
                             user_agent: Set(e.user_agent),
                             referer: Set(e.referer),
                             country: Set(e.country),
@@ -242,28 +490,38 @@ impl ClickBuffer {
                             device: Set(e.device),
                             browser: Set(e.browser),
                             os: Set(e.os),
+                            source: Set(e.source),
+                            is_preview: Set(e.is_preview),
+                            sample_rate: Set(e.sample_rate),
                             ..Default::default()
                         })
                         .collect();
                     click_events::Entity::insert_many(models).exec(&txn).await?;
                 }
 
+                let mut updated = None;
                 if count > 0 {
                     use sea_orm::sea_query::Expr;
-                    links::Entity::update_many()
+                    updated = links::Entity::update_many()
                         .col_expr(
                             links::Column::ClickCount,
                             Expr::col(links::Column::ClickCount).add(count),
                         )
                         .filter(links::Column::Id.eq(link_id))
-                        .exec(&txn)
-                        .await?;
+                        .exec_with_returning(&txn)
+                        .await?
+                        .into_iter()
+                        .next();
                 }
 
-                txn.commit().await
+                txn.commit().await.map(|_| updated)
             }
             .await;
 
+            if let Ok(Some(link)) = &persist_result {
+                notify_milestones(db, link, count);
+            }
+
             if let Err(e) = persist_result {
                 error!(
                     "Click flush: failed to persist link {} (will retry {} events / {} increments): {}",
@@ -286,6 +544,14 @@ impl ClickBuffer {
             let mut buffer = self.events.write();
             retry_events.append(&mut *buffer);
             *buffer = retry_events;
+            // Keep the oldest; the newest arrivals are the ones dropped.
+            let overflow = buffer.len().saturating_sub(self.max_pending);
+            if overflow > 0 {
+                let keep = buffer.len() - overflow;
+                buffer.truncate(keep);
+                drop(buffer);
+                self.record_dropped(overflow);
+            }
         }
         if !retry_counts.is_empty() {
             let mut buffer = self.counters.write();
@@ -329,6 +595,131 @@ impl Clone for ClickBuffer {
             max_buffer_size: self.max_buffer_size,
             flush_interval_secs: self.flush_interval_secs,
             flush_notify: self.flush_notify.clone(),
+            max_pending: self.max_pending,
+            dropped: self.dropped.clone(),
+            dropping: self.dropping.clone(),
+            sampling: self.sampling,
+            rates: self.rates.clone(),
+            debounce: self.debounce,
+            last_counted: self.last_counted.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(link_id: i32) -> ClickData {
+        ClickData {
+            link_id,
+            ip_address: None,
+            ip_hash: None,
+            user_agent: None,
+            referer: None,
+            country: None,
+            city: None,
+            region: None,
+            latitude: None,
+            longitude: None,
+            device: None,
+            browser: None,
+            os: None,
+            source: None,
+            is_preview: false,
+            sample_rate: 1,
         }
     }
+
+    #[test]
+    fn truncate_chars_respects_char_boundaries() {
+        assert_eq!(truncate_chars("abcdef", 3), "abc");
+        assert_eq!(truncate_chars("abc", 10), "abc");
+        assert_eq!(truncate_chars("ünïcödé", 4), "ünïc");
+    }
+
+    #[test]
+    fn len_counts_buffered_events() {
+        let buffer = ClickBuffer::new();
+        assert!(buffer.is_empty());
+
+        buffer.add_click(click(1));
+        buffer.add_click(click(1));
+        buffer.add_event_only(click(2));
+        assert_eq!(buffer.len(), 3);
+        assert!(!buffer.is_empty());
+        // Event-only clicks don't touch the aggregate counter.
+        assert_eq!(buffer.pending_count(1), 2);
+        assert_eq!(buffer.pending_count(2), 0);
+    }
+
+    #[test]
+    fn full_buffer_drops_events_but_keeps_counts() {
+        let buffer = ClickBuffer {
+            max_pending: 2,
+            ..ClickBuffer::new()
+        };
+        for _ in 0..5 {
+            buffer.add_click(click(1));
+        }
+        buffer.add_event_only(click(2));
+
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.dropped_clicks(), 4);
+        assert!(buffer.dropping.load(Ordering::Relaxed));
+        assert_eq!(buffer.pending_count(1), 5);
+    }
+
+    #[test]
+    fn hot_links_keep_one_event_in_n_but_every_count() {
+        let buffer = ClickBuffer {
+            sampling: Some(ClickSampling {
+                threshold: 3,
+                rate: 4,
+            }),
+            ..ClickBuffer::new()
+        };
+        for _ in 0..15 {
+            buffer.add_click(click(1));
+        }
+        buffer.add_click(click(2));
+
+        // 3 below the threshold, then clicks 4, 8 and 12 of the remaining 12.
+        let rates: Vec<i32> = buffer
+            .events
+            .read()
+            .iter()
+            .filter(|e| e.link_id == 1)
+            .map(|e| e.sample_rate)
+            .collect();
+        assert_eq!(rates, [1, 1, 1, 4, 4, 4]);
+        assert_eq!(rates.iter().sum::<i32>(), 15);
+        assert_eq!(buffer.pending_count(1), 15);
+        // Other links have their own window.
+        assert_eq!(buffer.len(), 7);
+    }
+
+    #[test]
+    fn repeat_clicks_within_the_window_are_flagged() {
+        let buffer = ClickBuffer {
+            debounce: Some(Duration::from_millis(50)),
+            ..ClickBuffer::new()
+        };
+        assert!(!buffer.is_repeat_click(1, "a"));
+        assert!(buffer.is_repeat_click(1, "a"));
+        // Other visitors and other links are tracked separately.
+        assert!(!buffer.is_repeat_click(1, "b"));
+        assert!(!buffer.is_repeat_click(2, "a"));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(!buffer.is_repeat_click(1, "a"));
+        assert!(buffer.is_repeat_click(1, "a"));
+
+        let off = ClickBuffer {
+            debounce: None,
+            ..ClickBuffer::new()
+        };
+        assert!(!off.is_repeat_click(1, "a"));
+        assert!(!off.is_repeat_click(1, "a"));
+    }
 }
@@ -0,0 +1,76 @@
+//! Channel tagging for clicks. When the same short link is shared in several
+//! places with `?src=newsletter`, `?src=twitter`, ... the value of that query
+//! parameter is stored on each click event so stats can be split by source.
+
+/// Longest source value kept; matches the `click_events.source` column.
+pub const MAX_SOURCE_LENGTH: usize = 64;
+
+/// Query parameter read as the click source (CLICK_SOURCE_PARAM, default: `src`).
+/// Set it to an empty string to stop capturing sources.
+pub fn click_source_param() -> String {
+    std::env::var("CLICK_SOURCE_PARAM")
+        .map(|v| v.trim().to_string())
+        .unwrap_or_else(|_| "src".to_string())
+}
+
+/// The value of `param` in a raw query string, percent-decoded, trimmed and
+/// capped at [`MAX_SOURCE_LENGTH`] characters. The first occurrence wins;
+/// empty values count as no source.
+pub fn extract_source(query: Option<&str>, param: &str) -> Option<String> {
+    if param.is_empty() {
+        return None;
+    }
+    url::form_urlencoded::parse(query?.as_bytes())
+        .find(|(key, _)| key == param)
+        .map(|(_, value)| {
+            value
+                .trim()
+                .chars()
+                .take(MAX_SOURCE_LENGTH)
+                .collect::<String>()
+        })
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_named_param() {
+        assert_eq!(
+            extract_source(Some("src=twitter"), "src"),
+            Some("twitter".to_string())
+        );
+        assert_eq!(
+            extract_source(Some("confirm=1&src=news%20letter&x=y"), "src"),
+            Some("news letter".to_string())
+        );
+        assert_eq!(
+            extract_source(Some("utm_source=mail"), "utm_source"),
+            Some("mail".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_or_empty_is_none() {
+        assert_eq!(extract_source(None, "src"), None);
+        assert_eq!(extract_source(Some("confirm=1"), "src"), None);
+        assert_eq!(extract_source(Some("src="), "src"), None);
+        assert_eq!(extract_source(Some("src=%20"), "src"), None);
+        assert_eq!(extract_source(Some("src=twitter"), ""), None);
+    }
+
+    #[test]
+    fn first_value_wins_and_is_capped() {
+        assert_eq!(
+            extract_source(Some("src=a&src=b"), "src"),
+            Some("a".to_string())
+        );
+        let long = format!("src={}", "x".repeat(200));
+        assert_eq!(
+            extract_source(Some(&long), "src").unwrap().len(),
+            MAX_SOURCE_LENGTH
+        );
+    }
+}
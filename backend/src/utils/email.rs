@@ -1,12 +1,22 @@
 use lettre::{
-    message::header::ContentType, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
-    AsyncTransport, Message, Tokio1Executor,
+    message::header::ContentType,
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Tls, TlsParameters},
+        PoolConfig,
+    },
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
 };
 use parking_lot::Mutex;
+use sea_orm::DatabaseConnection;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::{error, info, warn};
 
+use crate::handlers::contact::html_escape;
+use crate::utils::email_queue;
+
 /// Global email rate limiter to prevent abuse and control costs
 /// Uses a sliding window approach: tracks emails sent in the current hour
 struct GlobalEmailRateLimiter {
@@ -87,11 +97,18 @@ impl GlobalEmailRateLimiter {
 static EMAIL_RATE_LIMITER: once_cell::sync::Lazy<GlobalEmailRateLimiter> =
     once_cell::sync::Lazy::new(GlobalEmailRateLimiter::new);
 
+/// Clones share the SMTP transport, and with it the connection pool.
+#[derive(Clone)]
 pub struct EmailService {
     mailer: Option<AsyncSmtpTransport<Tokio1Executor>>,
     from_email: String,
     from_name: String,
     frontend_url: String,
+    /// When set, sends go through the persistent queue (see `email_queue`)
+    /// instead of SMTP directly.
+    queue: Option<DatabaseConnection>,
+    /// Wakes the queue worker when a message is enqueued.
+    wake: Arc<tokio::sync::Notify>,
 }
 
 impl Default for EmailService {
@@ -125,82 +142,60 @@ impl EmailService {
                 host, smtp_port, smtp_tls
             );
 
-            let transport_result = match smtp_tls.to_lowercase().as_str() {
+            let tls = match smtp_tls.to_lowercase().as_str() {
                 // Port 465 style: TLS from the start (implicit TLS / SMTPS)
                 "tls" | "ssl" | "implicit" => {
                     info!("Using implicit TLS/SSL (port 465 style)");
-                    match lettre::transport::smtp::client::TlsParameters::new(host.clone()) {
-                        Ok(tls_params) => Ok(
-                            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)
-                                .port(smtp_port)
-                                .tls(lettre::transport::smtp::client::Tls::Wrapper(tls_params))
-                                .credentials(creds)
-                                .build(),
-                        ),
-                        Err(e) => {
+                    TlsParameters::new(host.clone())
+                        .map(Tls::Wrapper)
+                        .map_err(|e| {
                             error!("Failed to create TLS parameters: {}", e);
-                            Err(format!("TLS error: {}", e))
-                        }
-                    }
+                            format!("TLS error: {}", e)
+                        })
                 }
                 // Port 587 style: STARTTLS (start plain, upgrade to TLS)
                 "starttls" | "required" => {
                     info!("Using STARTTLS (port 587 style)");
-                    match lettre::transport::smtp::client::TlsParameters::new(host.clone()) {
-                        Ok(tls_params) => Ok(
-                            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)
-                                .port(smtp_port)
-                                .tls(lettre::transport::smtp::client::Tls::Required(tls_params))
-                                .credentials(creds)
-                                .build(),
-                        ),
-                        Err(e) => {
+                    TlsParameters::new(host.clone())
+                        .map(Tls::Required)
+                        .map_err(|e| {
                             error!("Failed to create STARTTLS parameters: {}", e);
-                            Err(format!("STARTTLS error: {}", e))
-                        }
-                    }
+                            format!("STARTTLS error: {}", e)
+                        })
                 }
                 // No encryption (not recommended, but useful for local testing)
                 "none" | "false" | "off" => {
                     info!("Using no TLS (insecure)");
-                    Ok(
-                        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)
-                            .port(smtp_port)
-                            .tls(lettre::transport::smtp::client::Tls::None)
-                            .credentials(creds)
-                            .build(),
-                    )
+                    Ok(Tls::None)
                 }
                 // Auto-detect based on port
                 _ => {
                     if smtp_port == 465 {
                         info!("Auto-detected implicit TLS for port 465");
-                        match lettre::transport::smtp::client::TlsParameters::new(host.clone()) {
-                            Ok(tls_params) => Ok(
-                                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)
-                                    .port(smtp_port)
-                                    .tls(lettre::transport::smtp::client::Tls::Wrapper(tls_params))
-                                    .credentials(creds)
-                                    .build(),
-                            ),
-                            Err(e) => Err(format!("TLS error: {}", e)),
-                        }
+                        TlsParameters::new(host.clone())
+                            .map(Tls::Wrapper)
+                            .map_err(|e| format!("TLS error: {}", e))
                     } else {
                         info!("Auto-detected STARTTLS for port {}", smtp_port);
-                        match lettre::transport::smtp::client::TlsParameters::new(host.clone()) {
-                            Ok(tls_params) => Ok(
-                                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)
-                                    .port(smtp_port)
-                                    .tls(lettre::transport::smtp::client::Tls::Required(tls_params))
-                                    .credentials(creds)
-                                    .build(),
-                            ),
-                            Err(e) => Err(format!("STARTTLS error: {}", e)),
-                        }
+                        TlsParameters::new(host.clone())
+                            .map(Tls::Required)
+                            .map_err(|e| format!("STARTTLS error: {}", e))
                     }
                 }
             };
 
+            // One pooled transport per service: connections are kept open and
+            // reused across sends instead of a fresh handshake per message.
+            let transport_result = tls.map(|tls| {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)
+                    .port(smtp_port)
+                    .tls(tls)
+                    .credentials(creds)
+                    .timeout(Some(Duration::from_secs(smtp_timeout_secs())))
+                    .pool_config(PoolConfig::new().max_size(smtp_pool_size()))
+                    .build()
+            });
+
             match transport_result {
                 Ok(transport) => {
                     info!("SMTP email service initialized successfully");
@@ -221,9 +216,22 @@ impl EmailService {
             from_email,
             from_name,
             frontend_url,
+            queue: None,
+            wake: Arc::new(tokio::sync::Notify::new()),
         }
     }
 
+    /// Queue outgoing mail in the database; `email_queue::spawn_worker`
+    /// delivers it with retries.
+    pub fn with_queue(mut self, db: DatabaseConnection) -> Self {
+        self.queue = Some(db);
+        self
+    }
+
+    pub(crate) fn queue_wake(&self) -> Arc<tokio::sync::Notify> {
+        self.wake.clone()
+    }
+
     pub fn is_configured(&self) -> bool {
         self.mailer.is_some()
     }
@@ -249,6 +257,30 @@ impl EmailService {
         subject: &str,
         html_body: &str,
         reply_to: Option<&str>,
+    ) -> Result<(), String> {
+        let Some(db) = &self.queue else {
+            return self.deliver(to, subject, html_body, reply_to).await;
+        };
+        if self.mailer.is_none() {
+            return Err("Email service not configured".to_string());
+        }
+        // Bad addresses would only fail every retry; reject them up front.
+        to.parse::<lettre::message::Mailbox>()
+            .map_err(|e| format!("Invalid to address: {}", e))?;
+        email_queue::enqueue(db, to, subject, html_body, reply_to)
+            .await
+            .map_err(|e| format!("Failed to queue email: {}", e))?;
+        self.wake.notify_one();
+        Ok(())
+    }
+
+    /// Send one message over SMTP now.
+    pub(crate) async fn deliver(
+        &self,
+        to: &str,
+        subject: &str,
+        html_body: &str,
+        reply_to: Option<&str>,
     ) -> Result<(), String> {
         let mailer = self.mailer.as_ref().ok_or("Email service not configured")?;
 
@@ -311,7 +343,7 @@ impl EmailService {
         <p><a href="{}" class="button">Verify Email</a></p>
         <p>Or copy and paste this link into your browser:</p>
         <p><a href="{}">{}</a></p>
-        <p>This link expires in 24 hours.</p>
+        <p>This link expires in {}.</p>
         <div class="footer">
             <p>If you didn't create an account on opn.onl, you can safely ignore this email.</p>
         </div>
@@ -319,7 +351,10 @@ impl EmailService {
 </body>
 </html>
 "#,
-            verification_url, verification_url, verification_url
+            verification_url,
+            verification_url,
+            verification_url,
+            describe_minutes(email_verification_ttl_hours() * 60)
         );
 
         self.send_email(to, "Verify your email - opn.onl", &html)
@@ -348,7 +383,7 @@ impl EmailService {
         <p><a href="{}" class="button">Reset Password</a></p>
         <p>Or copy and paste this link into your browser:</p>
         <p><a href="{}">{}</a></p>
-        <p>This link expires in 1 hour.</p>
+        <p>This link expires in {}.</p>
         <div class="footer">
             <p>If you didn't request a password reset, you can safely ignore this email.</p>
         </div>
@@ -356,7 +391,10 @@ impl EmailService {
 </body>
 </html>
 "#,
-            reset_url, reset_url, reset_url
+            reset_url,
+            reset_url,
+            reset_url,
+            describe_minutes(password_reset_ttl_minutes())
         );
 
         self.send_email(to, "Reset your password - opn.onl", &html)
@@ -390,11 +428,177 @@ impl EmailService {
 
         self.send_email(to, "Welcome to opn.onl!", &html).await
     }
+
+    pub async fn send_link_activated_email(
+        &self,
+        to: &str,
+        short_url: &str,
+        original_url: &str,
+    ) -> Result<(), String> {
+        let html = format!(
+            r#"
+<!DOCTYPE html>
+<html>
+<head>
+    <style>
+        body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; }}
+        .container {{ max-width: 600px; margin: 0 auto; padding: 20px; }}
+        .button {{ display: inline-block; padding: 12px 24px; background-color: #2563eb; color: white; text-decoration: none; border-radius: 8px; font-weight: 600; }}
+        .footer {{ margin-top: 40px; font-size: 12px; color: #666; }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>Your link is live</h1>
+        <p>Your scheduled short link <a href="{}">{}</a> is now active and redirects to:</p>
+        <p>{}</p>
+        <p><a href="{}/dashboard" class="button">Go to Dashboard</a></p>
+        <div class="footer">
+            <p>You're receiving this because activation notices are turned on for this link.</p>
+        </div>
+    </div>
+</body>
+</html>
+"#,
+            short_url,
+            short_url,
+            html_escape(original_url),
+            self.frontend_url
+        );
+
+        self.send_email(to, "Your scheduled link is live - opn.onl", &html)
+            .await
+    }
+
+    /// Subject and HTML for the notice sent when someone is added to an
+    /// organization, branded with the org's name and logo.
+    pub fn org_invitation_email(
+        &self,
+        branding: &OrgEmailBranding,
+        invited_by: &str,
+        role: &str,
+    ) -> (String, String) {
+        let org_name = html_escape(&branding.name);
+        let header = match &branding.logo_url {
+            Some(logo) => format!(
+                r#"<img src="{}" alt="{}" class="logo">"#,
+                html_escape(logo),
+                org_name
+            ),
+            None => format!("<p><strong>{}</strong></p>", html_escape(&self.from_name)),
+        };
+        let html = format!(
+            r#"
+<!DOCTYPE html>
+<html>
+<head>
+    <style>
+        body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; }}
+        .container {{ max-width: 600px; margin: 0 auto; padding: 20px; }}
+        .logo {{ max-height: 48px; max-width: 200px; }}
+        .button {{ display: inline-block; padding: 12px 24px; background-color: #2563eb; color: white; text-decoration: none; border-radius: 8px; font-weight: 600; }}
+        .footer {{ margin-top: 40px; font-size: 12px; color: #666; }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        {}
+        <h1>You've joined {}</h1>
+        <p>{} added you to <strong>{}</strong> as {}.</p>
+        <p>Its shared links, folders and tags are now in your dashboard.</p>
+        <p><a href="{}/dashboard" class="button">Go to Dashboard</a></p>
+        <div class="footer">
+            <p>If you weren't expecting this, you can leave the organization from your dashboard.</p>
+        </div>
+    </div>
+</body>
+</html>
+"#,
+            header,
+            org_name,
+            html_escape(invited_by),
+            org_name,
+            html_escape(role),
+            self.frontend_url
+        );
+        (format!("You've been added to {}", branding.name), html)
+    }
+
+    pub async fn send_org_invitation_email(
+        &self,
+        to: &str,
+        branding: &OrgEmailBranding,
+        invited_by: &str,
+        role: &str,
+    ) -> Result<(), String> {
+        let (subject, html) = self.org_invitation_email(branding, invited_by, role);
+        self.send_email(to, &subject, &html).await
+    }
 }
 
-impl Clone for EmailService {
-    fn clone(&self) -> Self {
-        Self::new()
+/// How an organization presents itself in the emails sent on its behalf.
+#[derive(Debug, Clone)]
+pub struct OrgEmailBranding {
+    pub name: String,
+    pub logo_url: Option<String>,
+}
+
+impl OrgEmailBranding {
+    pub fn for_org(org: &crate::entity::organizations::Model) -> Self {
+        Self {
+            name: org
+                .brand_name
+                .clone()
+                .filter(|n| !n.trim().is_empty())
+                .unwrap_or_else(|| org.name.clone()),
+            logo_url: org.brand_logo_url.clone(),
+        }
+    }
+}
+
+/// Most SMTP connections kept open for reuse (SMTP_POOL_SIZE, default: 10)
+fn smtp_pool_size() -> u32 {
+    std::env::var("SMTP_POOL_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(10)
+}
+
+/// Connect and per-command SMTP timeout in seconds (SMTP_TIMEOUT_SECS, default: 60)
+fn smtp_timeout_secs() -> u64 {
+    std::env::var("SMTP_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(60)
+}
+
+/// Lifetime of password reset links (PASSWORD_RESET_TTL_MINUTES, default: 60)
+pub fn password_reset_ttl_minutes() -> i64 {
+    std::env::var("PASSWORD_RESET_TTL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|m| *m > 0)
+        .unwrap_or(60)
+}
+
+/// Lifetime of email verification links (EMAIL_VERIFICATION_TTL_HOURS, default: 24)
+pub fn email_verification_ttl_hours() -> i64 {
+    std::env::var("EMAIL_VERIFICATION_TTL_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|h| *h > 0)
+        .unwrap_or(24)
+}
+
+/// "1 hour", "24 hours", "90 minutes" — for the expiry line in emails.
+fn describe_minutes(minutes: i64) -> String {
+    match (minutes % 60, minutes / 60) {
+        (0, 1) => "1 hour".to_string(),
+        (0, hours) => format!("{hours} hours"),
+        _ if minutes == 1 => "1 minute".to_string(),
+        _ => format!("{minutes} minutes"),
     }
 }
 
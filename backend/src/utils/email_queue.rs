@@ -0,0 +1,227 @@
+//! Persistent outbox for transactional email. `EmailService::send_*` writes a
+//! row here instead of talking to SMTP from the request; a background worker
+//! claims due rows, delivers them, and reschedules failures with exponential
+//! backoff. Pending mail survives a restart because it lives in Postgres.
+//!
+//! A claim bumps `attempts` and pushes `next_attempt_at` out by a lease, so a
+//! row being delivered is not picked up again by another instance; if the
+//! process dies mid-send the row simply becomes due again when the lease ends.
+
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, DatabaseConnection, DbBackend, DbErr, EntityTrait,
+    Statement,
+};
+use std::sync::Arc;
+
+use crate::entity::email_queue;
+use crate::utils::EmailService;
+
+/// First retry delay; doubles on each further failure.
+const BASE_RETRY_SECS: i64 = 30;
+/// Upper bound on the retry delay.
+const MAX_RETRY_SECS: i64 = 60 * 60;
+/// How long a claimed row is hidden from other workers while it is sent.
+const CLAIM_LEASE_SECS: i64 = 5 * 60;
+/// Rows claimed per pass.
+const BATCH_SIZE: i64 = 20;
+/// Fallback poll interval, for retries coming due and mail queued by other
+/// instances.
+const POLL_INTERVAL_SECS: u64 = 10;
+
+/// Delivery attempts before a message is given up on (EMAIL_MAX_ATTEMPTS, default: 5).
+pub fn max_attempts() -> i32 {
+    std::env::var("EMAIL_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n: &i32| *n > 0)
+        .unwrap_or(5)
+}
+
+/// Delay before retrying a message whose `attempts`-th delivery just failed:
+/// 30s, 1m, 2m, 4m, ... capped at an hour.
+pub fn retry_delay(attempts: i32) -> chrono::Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    chrono::Duration::seconds((BASE_RETRY_SECS << exponent).min(MAX_RETRY_SECS))
+}
+
+/// When to try again after a failed attempt, or `None` once `attempts` has
+/// reached `max_attempts` and the message should be marked failed.
+pub fn next_retry_at(
+    attempts: i32,
+    max_attempts: i32,
+    now: chrono::NaiveDateTime,
+) -> Option<chrono::NaiveDateTime> {
+    (attempts < max_attempts).then(|| now + retry_delay(attempts))
+}
+
+/// Queue a message for delivery as soon as a worker picks it up.
+pub async fn enqueue(
+    db: &DatabaseConnection,
+    to: &str,
+    subject: &str,
+    html_body: &str,
+    reply_to: Option<&str>,
+) -> Result<i32, DbErr> {
+    let row = email_queue::ActiveModel {
+        recipient: Set(to.to_string()),
+        subject: Set(subject.to_string()),
+        html_body: Set(html_body.to_string()),
+        reply_to: Set(reply_to.map(str::to_string)),
+        attempts: Set(0),
+        next_attempt_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(row.id)
+}
+
+/// Claim up to `limit` messages due at `now`, counting the attempt and
+/// leasing them so no other worker takes them meanwhile.
+pub async fn claim_due(
+    db: &DatabaseConnection,
+    now: chrono::NaiveDateTime,
+    limit: i64,
+) -> Result<Vec<email_queue::Model>, DbErr> {
+    email_queue::Entity::find()
+        .from_raw_sql(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"UPDATE email_queue
+               SET attempts = attempts + 1,
+                   next_attempt_at = $2
+               WHERE id IN (
+                   SELECT id FROM email_queue
+                   WHERE failed_at IS NULL AND next_attempt_at <= $1
+                   ORDER BY next_attempt_at
+                   LIMIT $3
+                   FOR UPDATE SKIP LOCKED
+               )
+               RETURNING *"#,
+            [
+                now.into(),
+                (now + chrono::Duration::seconds(CLAIM_LEASE_SECS)).into(),
+                limit.into(),
+            ],
+        ))
+        .all(db)
+        .await
+}
+
+/// Delivered: drop the row.
+pub async fn mark_sent(db: &DatabaseConnection, id: i32) -> Result<(), DbErr> {
+    email_queue::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// Failed attempt on a claimed row: schedule the retry, or mark the message
+/// failed once it is out of attempts.
+pub async fn mark_attempt_failed(
+    db: &DatabaseConnection,
+    row: &email_queue::Model,
+    error: &str,
+    now: chrono::NaiveDateTime,
+) -> Result<(), DbErr> {
+    let mut update = email_queue::ActiveModel {
+        id: Set(row.id),
+        last_error: Set(Some(error.to_string())),
+        ..Default::default()
+    };
+    match next_retry_at(row.attempts, max_attempts(), now) {
+        Some(at) => update.next_attempt_at = Set(at),
+        None => update.failed_at = Set(Some(now)),
+    }
+    update.update(db).await?;
+    Ok(())
+}
+
+/// One pass of the worker: deliver everything due. Returns how many messages
+/// were sent.
+pub async fn process_due(
+    db: &DatabaseConnection,
+    email_service: &EmailService,
+) -> Result<usize, DbErr> {
+    let mut sent = 0;
+    loop {
+        let batch = claim_due(db, chrono::Utc::now().naive_utc(), BATCH_SIZE).await?;
+        if batch.is_empty() {
+            return Ok(sent);
+        }
+        for row in &batch {
+            match email_service
+                .deliver(
+                    &row.recipient,
+                    &row.subject,
+                    &row.html_body,
+                    row.reply_to.as_deref(),
+                )
+                .await
+            {
+                Ok(()) => {
+                    mark_sent(db, row.id).await?;
+                    sent += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Email {} delivery attempt {} failed: {}",
+                        row.id,
+                        row.attempts,
+                        e
+                    );
+                    mark_attempt_failed(db, row, &e, chrono::Utc::now().naive_utc()).await?;
+                }
+            }
+        }
+    }
+}
+
+/// Spawn the delivery worker. It runs on a timer and whenever the service
+/// queues a message.
+pub fn spawn_worker(db: DatabaseConnection, email_service: Arc<EmailService>) {
+    let wake = email_service.queue_wake();
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = wake.notified() => {}
+            }
+            if let Err(e) = process_due(&db, &email_service).await {
+                tracing::error!("Email queue pass failed: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_and_caps() {
+        assert_eq!(retry_delay(1), chrono::Duration::seconds(30));
+        assert_eq!(retry_delay(2), chrono::Duration::seconds(60));
+        assert_eq!(retry_delay(3), chrono::Duration::seconds(120));
+        assert_eq!(retry_delay(8), chrono::Duration::seconds(3600));
+        assert_eq!(retry_delay(1000), chrono::Duration::seconds(3600));
+        // Defensive: a row that was never attempted retries at the base delay.
+        assert_eq!(retry_delay(0), chrono::Duration::seconds(30));
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            next_retry_at(1, 3, now),
+            Some(now + chrono::Duration::seconds(30))
+        );
+        assert_eq!(
+            next_retry_at(2, 3, now),
+            Some(now + chrono::Duration::seconds(60))
+        );
+        assert_eq!(next_retry_at(3, 3, now), None);
+    }
+}
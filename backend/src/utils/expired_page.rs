@@ -0,0 +1,286 @@
+//! Custom "link gone" page: HTML a link owner attaches to be shown instead of
+//! the default text once the link has expired or used up its clicks. Unlike
+//! a fallback redirect, the visitor stays on the short domain and sees the
+//! page with a 410.
+//!
+//! The HTML is reduced to an allowlist of presentational tags and attributes
+//! when it is saved, and served under a CSP that runs no scripts and loads
+//! nothing but inline styles and images.
+
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Largest page accepted, in bytes (MAX_EXPIRED_PAGE_BYTES, default: 32768).
+pub fn max_expired_page_bytes() -> usize {
+    std::env::var("MAX_EXPIRED_PAGE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(32 * 1024)
+}
+
+/// Served with the page: no scripts, plugins, forms or frames, only inline
+/// styles and images.
+pub const CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; style-src 'unsafe-inline'; img-src https: data:; sandbox";
+
+const ALLOWED_TAGS: &[&str] = &[
+    "html",
+    "head",
+    "body",
+    "title",
+    "style",
+    "main",
+    "header",
+    "footer",
+    "section",
+    "article",
+    "nav",
+    "div",
+    "span",
+    "p",
+    "br",
+    "hr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "a",
+    "img",
+    "strong",
+    "em",
+    "b",
+    "i",
+    "u",
+    "small",
+    "ul",
+    "ol",
+    "li",
+    "blockquote",
+    "code",
+    "pre",
+    "table",
+    "thead",
+    "tbody",
+    "tr",
+    "th",
+    "td",
+    "figure",
+    "figcaption",
+];
+
+/// Dropped together with everything inside them.
+const DROPPED_WITH_CONTENT: &[&str] = &[
+    "script", "iframe", "object", "embed", "noscript", "template", "svg", "math", "textarea",
+    "select",
+];
+
+const ALLOWED_ATTRIBUTES: &[&str] = &[
+    "href", "src", "alt", "title", "class", "id", "style", "width", "height", "lang", "dir",
+    "colspan", "rowspan",
+];
+
+static ATTRIBUTE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"([^\s"'>/=]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+)))?"#)
+        .expect("attribute pattern")
+});
+
+/// Validate and clean a caller-supplied page: over-size pages are rejected,
+/// the rest are sanitized (empty becomes `None`, which removes the page).
+pub fn clean(html: Option<String>) -> Result<Option<String>, String> {
+    let Some(html) = html else {
+        return Ok(None);
+    };
+    let max = max_expired_page_bytes();
+    if html.len() > max {
+        return Err(format!("Expired page is too large (max {max} bytes)"));
+    }
+    let cleaned = sanitize(&html).trim().to_string();
+    Ok((!cleaned.is_empty()).then_some(cleaned))
+}
+
+/// Keep only allowlisted tags and attributes. Comments, doctypes and
+/// disallowed tags are dropped (script-like elements with their contents),
+/// and `href`/`src` must be http(s), relative or, for images, a data URI.
+pub fn sanitize(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let opens_tag = after
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+        if !opens_tag {
+            out.push_str("&lt;");
+            rest = after;
+            continue;
+        }
+        if let Some(comment) = after.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = after.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &after[..end];
+        rest = &after[end + 1..];
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/');
+        let raw_name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("");
+        let name = raw_name.to_ascii_lowercase();
+        if closing {
+            if ALLOWED_TAGS.contains(&name.as_str()) {
+                out.push_str("</");
+                out.push_str(&name);
+                out.push('>');
+            }
+            continue;
+        }
+        if DROPPED_WITH_CONTENT.contains(&name.as_str()) {
+            rest = skip_past_closing_tag(rest, &name);
+            continue;
+        }
+        if !ALLOWED_TAGS.contains(&name.as_str()) {
+            continue;
+        }
+
+        out.push('<');
+        out.push_str(&name);
+        for caps in ATTRIBUTE.captures_iter(&tag[raw_name.len()..]) {
+            let attribute = caps[1].to_ascii_lowercase();
+            if !ALLOWED_ATTRIBUTES.contains(&attribute.as_str()) {
+                continue;
+            }
+            let value = caps
+                .get(2)
+                .or_else(|| caps.get(3))
+                .or_else(|| caps.get(4))
+                .map_or("", |m| m.as_str());
+            if matches!(attribute.as_str(), "href" | "src") && !safe_url(value, &name) {
+                continue;
+            }
+            out.push(' ');
+            out.push_str(&attribute);
+            out.push_str("=\"");
+            out.push_str(&escape_attribute(value));
+            out.push('"');
+        }
+        out.push('>');
+
+        // Style sheets are raw text up to their closing tag.
+        if name == "style" {
+            let close = find_ignore_case(rest, "</style").unwrap_or(rest.len());
+            out.push_str(&rest[..close]);
+            rest = &rest[close..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The 410 page for a visitor whose link is gone.
+pub fn response(html: &str) -> Response {
+    (
+        StatusCode::GONE,
+        [
+            (header::CONTENT_TYPE, "text/html; charset=utf-8"),
+            (header::REFERRER_POLICY, "no-referrer"),
+            (header::CACHE_CONTROL, "no-store"),
+            (header::CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY),
+        ],
+        html.to_string(),
+    )
+        .into_response()
+}
+
+fn safe_url(value: &str, tag: &str) -> bool {
+    let url = value.trim().to_ascii_lowercase();
+    ["https://", "http://", "mailto:", "/", "#"]
+        .iter()
+        .any(|prefix| url.starts_with(prefix))
+        || (tag == "img" && url.starts_with("data:image/"))
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    // ASCII lowercasing keeps byte offsets intact.
+    haystack.to_ascii_lowercase().find(needle)
+}
+
+fn skip_past_closing_tag<'a>(rest: &'a str, name: &str) -> &'a str {
+    let Some(close) = find_ignore_case(rest, &format!("</{name}")) else {
+        return "";
+    };
+    let tail = &rest[close..];
+    tail.find('>').map_or("", |end| &tail[end + 1..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_presentational_markup() {
+        let html = r#"<!DOCTYPE html><html><head><style>h1 { color: red; }</style></head><body><h1 class="t">Gone</h1><p>See <a href="https://iana.org">this</a> instead.</p><img src="https://iana.org/logo.png" alt="logo"></body></html>"#;
+        assert_eq!(
+            sanitize(html),
+            r#"<html><head><style>h1 { color: red; }</style></head><body><h1 class="t">Gone</h1><p>See <a href="https://iana.org">this</a> instead.</p><img src="https://iana.org/logo.png" alt="logo"></body></html>"#
+        );
+    }
+
+    #[test]
+    fn drops_scripts_handlers_and_unsafe_urls() {
+        let html = r#"<p onclick="steal()" style="color:red">Hi</p><SCRIPT>alert(1)</script><a href=" javascript:alert(1)">x</a><iframe src="https://evil.test"><p>inside</p></iframe><img src="data:text/html,x" onerror=alert(1)><form action="/x"><b>ok</b></form><!-- note -->"#;
+        assert_eq!(
+            sanitize(html),
+            r#"<p style="color:red">Hi</p><a>x</a><img><b>ok</b>"#
+        );
+    }
+
+    #[test]
+    fn attribute_values_cannot_break_out() {
+        let html = r#"<p title='a"><script>alert(1)</script>'>x</p>"#;
+        let out = sanitize(html);
+        assert!(!out.contains("<script"), "{out}");
+    }
+
+    #[test]
+    fn style_cannot_smuggle_tags() {
+        let out = sanitize("<style>p{}</STYLE><script>alert(1)</script>");
+        assert_eq!(out, "<style>p{}</style>");
+    }
+
+    #[test]
+    fn stray_angle_brackets_are_escaped() {
+        assert_eq!(sanitize("1 < 2 > 0"), "1 &lt; 2 > 0");
+    }
+
+    #[test]
+    fn clean_rejects_oversized_and_empties_to_none() {
+        assert_eq!(clean(Some("<script>x</script>  ".into())), Ok(None));
+        assert_eq!(clean(None), Ok(None));
+        assert!(clean(Some("a".repeat(max_expired_page_bytes() + 1))).is_err());
+    }
+}
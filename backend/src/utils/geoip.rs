@@ -1,7 +1,11 @@
+use lru::LruCache;
 use maxminddb::{geoip2, Reader};
 use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use std::net::IpAddr;
+use std::num::NonZeroUsize;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 /// GeoIP location data
 #[derive(Debug, Clone, Default)]
@@ -15,6 +19,46 @@ pub struct GeoLocation {
     pub longitude: Option<f64>,
 }
 
+/// How much of a visitor's location is kept with a click (GEO_PRECISION).
+/// Ordered from coarsest to finest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GeoPrecision {
+    Country,
+    Region,
+    City,
+}
+
+/// `country`, `region` or `city` (default: city, everything the database
+/// knows).
+pub fn geo_precision() -> GeoPrecision {
+    match std::env::var("GEO_PRECISION")
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "country" => GeoPrecision::Country,
+        "region" => GeoPrecision::Region,
+        _ => GeoPrecision::City,
+    }
+}
+
+impl GeoPrecision {
+    /// Drop the parts of `geo` finer than this precision. Coordinates are
+    /// city-level, so they go with the city.
+    pub fn apply(self, mut geo: GeoLocation) -> GeoLocation {
+        if self < GeoPrecision::City {
+            geo.city = None;
+            geo.latitude = None;
+            geo.longitude = None;
+        }
+        if self < GeoPrecision::Region {
+            geo.region = None;
+        }
+        geo
+    }
+}
+
 /// Try to load the MaxMind GeoIP database
 /// The database should be placed at: ./data/GeoLite2-City.mmdb
 static GEOIP_READER: Lazy<Option<Reader<Vec<u8>>>> = Lazy::new(|| {
@@ -53,8 +97,9 @@ pub fn lookup_ip(ip_str: &str) -> GeoLocation {
         None => return GeoLocation::default(),
     };
 
-    let ip: IpAddr = match ip_str.parse() {
-        Ok(ip) => ip,
+    // IPv4-mapped IPv6 (`::ffff:a.b.c.d`) is looked up as the IPv4 it carries.
+    let ip: IpAddr = match ip_str.parse::<IpAddr>() {
+        Ok(ip) => ip.to_canonical(),
         Err(_) => return GeoLocation::default(),
     };
 
@@ -105,11 +150,81 @@ pub fn lookup_ip(ip_str: &str) -> GeoLocation {
     }
 }
 
+/// Number of IPs whose location is kept in memory (GEO_CACHE_SIZE, default:
+/// 1024). 0 turns the cache off.
+fn geo_cache_size() -> usize {
+    std::env::var("GEO_CACHE_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1024)
+}
+
+/// How long a cached location is trusted (GEO_CACHE_TTL_SECS, default: 3600).
+fn geo_cache_ttl() -> Duration {
+    Duration::from_secs(
+        std::env::var("GEO_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600),
+    )
+}
+
+/// Bounded IP → location cache so a burst of clicks from one address costs a
+/// single database lookup. Entries expire after `ttl` and the least recently
+/// used IP is evicted once `capacity` is reached.
+pub struct GeoCache {
+    entries: Mutex<LruCache<String, (GeoLocation, Instant)>>,
+    ttl: Duration,
+}
+
+impl GeoCache {
+    pub fn new(capacity: NonZeroUsize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+        }
+    }
+
+    /// Cached location for `ip`, calling `lookup` only on a miss or once the
+    /// cached entry has expired.
+    pub fn get_or_lookup(&self, ip: &str, lookup: impl FnOnce(&str) -> GeoLocation) -> GeoLocation {
+        if let Some((geo, cached_at)) = self.entries.lock().get(ip) {
+            if cached_at.elapsed() < self.ttl {
+                return geo.clone();
+            }
+        }
+        let geo = lookup(ip);
+        self.entries
+            .lock()
+            .put(ip.to_string(), (geo.clone(), Instant::now()));
+        geo
+    }
+}
+
+static GEO_CACHE: Lazy<Option<GeoCache>> = Lazy::new(|| {
+    NonZeroUsize::new(geo_cache_size()).map(|size| GeoCache::new(size, geo_cache_ttl()))
+});
+
+/// [`lookup_ip`] through the process-wide [`GeoCache`]; used on the redirect
+/// path where the same visitor tends to click several times in a row.
+pub fn lookup_ip_cached(ip_str: &str) -> GeoLocation {
+    match GEO_CACHE.as_ref() {
+        Some(cache) => cache.get_or_lookup(ip_str, lookup_ip),
+        None => lookup_ip(ip_str),
+    }
+}
+
 /// Check if an IP address is private/local
 fn is_private_ip(ip: &IpAddr) -> bool {
     match ip {
         IpAddr::V4(ipv4) => ipv4.is_private() || ipv4.is_loopback() || ipv4.is_link_local(),
-        IpAddr::V6(ipv6) => ipv6.is_loopback(),
+        IpAddr::V6(ipv6) => {
+            let first = ipv6.segments()[0];
+            ipv6.is_loopback()
+                || ipv6.is_unspecified()
+                || (first & 0xfe00) == 0xfc00 // unique local fc00::/7
+                || (first & 0xffc0) == 0xfe80 // link local fe80::/10
+        }
     }
 }
 
@@ -236,5 +351,9 @@ mod tests {
         assert!(is_private_ip(&"192.168.1.1".parse().unwrap()));
         assert!(is_private_ip(&"10.0.0.1".parse().unwrap()));
         assert!(!is_private_ip(&"8.8.8.8".parse().unwrap()));
+        assert!(is_private_ip(&"::1".parse().unwrap()));
+        assert!(is_private_ip(&"fd12:3456::1".parse().unwrap()));
+        assert!(is_private_ip(&"fe80::1".parse().unwrap()));
+        assert!(!is_private_ip(&"2606:4700::1111".parse().unwrap()));
     }
 }
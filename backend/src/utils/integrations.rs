@@ -0,0 +1,157 @@
+//! Chat integrations (Slack / Discord incoming webhooks).
+//!
+//! These ride on the webhook event plumbing in [`crate::utils::webhook`], but
+//! instead of our signed envelope they receive a plain chat message in the
+//! shape each platform expects.
+
+use serde_json::{json, Value};
+
+use crate::utils::webhook::{EVENT_LINK_CREATED, EVENT_LINK_MILESTONE};
+
+/// Events an integration may post messages for.
+pub const INTEGRATION_EVENTS: &[&str] = &[EVENT_LINK_CREATED, EVENT_LINK_MILESTONE];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Platform {
+    Slack,
+    Discord,
+}
+
+impl Platform {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "slack" => Some(Self::Slack),
+            "discord" => Some(Self::Discord),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+        }
+    }
+
+    /// Only the platform's own incoming-webhook endpoints are accepted, so an
+    /// integration can't be used to POST to arbitrary hosts.
+    pub fn validate_webhook_url(self, raw: &str) -> Result<(), String> {
+        let parsed = url::Url::parse(raw.trim()).map_err(|_| "Invalid webhook URL".to_string())?;
+        let host = parsed.host_str().unwrap_or_default();
+        let ok = parsed.scheme() == "https"
+            && match self {
+                Self::Slack => host == "hooks.slack.com" && parsed.path().starts_with("/services/"),
+                Self::Discord => {
+                    matches!(host, "discord.com" | "discordapp.com")
+                        && parsed.path().starts_with("/api/webhooks/")
+                }
+            };
+        if ok {
+            Ok(())
+        } else {
+            Err(format!(
+                "Not a {} incoming webhook URL",
+                match self {
+                    Self::Slack => "Slack",
+                    Self::Discord => "Discord",
+                }
+            ))
+        }
+    }
+}
+
+/// Human-readable one-liner for an event, or None for events integrations
+/// don't render.
+fn describe(event: &str, data: &Value) -> Option<String> {
+    let short_url = data["short_url"].as_str().unwrap_or_default();
+    match event {
+        EVENT_LINK_CREATED => Some(format!(
+            "New short link {} → {}",
+            short_url,
+            data["original_url"].as_str().unwrap_or_default()
+        )),
+        EVENT_LINK_MILESTONE => Some(format!(
+            "🎉 {} just reached {} clicks",
+            short_url,
+            data["milestone"].as_i64().unwrap_or_default()
+        )),
+        _ => None,
+    }
+}
+
+/// The request body to POST to `platform`'s incoming webhook for this event.
+pub fn format_message(platform: Platform, event: &str, data: &Value) -> Option<Value> {
+    let text = describe(event, data)?;
+    Some(match platform {
+        Platform::Slack => json!({ "text": text }),
+        // Discord rejects content over 2000 characters.
+        Platform::Discord => json!({ "content": text.chars().take(2000).collect::<String>() }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn milestone() -> Value {
+        json!({ "short_url": "https://opn.onl/abc123", "milestone": 1000, "click_count": 1004 })
+    }
+
+    #[test]
+    fn slack_uses_text_field() {
+        let body = format_message(Platform::Slack, EVENT_LINK_MILESTONE, &milestone()).unwrap();
+        assert_eq!(
+            body,
+            json!({ "text": "🎉 https://opn.onl/abc123 just reached 1000 clicks" })
+        );
+    }
+
+    #[test]
+    fn discord_uses_content_field() {
+        let data =
+            json!({ "short_url": "https://opn.onl/abc123", "original_url": "https://iana.org/" });
+        let body = format_message(Platform::Discord, EVENT_LINK_CREATED, &data).unwrap();
+        assert_eq!(
+            body,
+            json!({ "content": "New short link https://opn.onl/abc123 → https://iana.org/" })
+        );
+    }
+
+    #[test]
+    fn discord_content_is_capped() {
+        let data = json!({ "short_url": "https://opn.onl/x", "original_url": "a".repeat(5000) });
+        let body = format_message(Platform::Discord, EVENT_LINK_CREATED, &data).unwrap();
+        assert_eq!(body["content"].as_str().unwrap().chars().count(), 2000);
+    }
+
+    #[test]
+    fn unknown_events_are_not_rendered() {
+        assert!(format_message(Platform::Slack, "user.registered", &json!({})).is_none());
+    }
+
+    #[test]
+    fn webhook_urls_must_match_platform() {
+        assert!(Platform::Slack
+            .validate_webhook_url("https://hooks.slack.com/services/T0/B0/xyz")
+            .is_ok());
+        assert!(Platform::Discord
+            .validate_webhook_url("https://discord.com/api/webhooks/1/abc")
+            .is_ok());
+        assert!(Platform::Slack
+            .validate_webhook_url("https://discord.com/api/webhooks/1/abc")
+            .is_err());
+        assert!(Platform::Discord
+            .validate_webhook_url("http://discord.com/api/webhooks/1/abc")
+            .is_err());
+        assert!(Platform::Slack
+            .validate_webhook_url("https://evil.example/services/x")
+            .is_err());
+    }
+
+    #[test]
+    fn platform_parse_is_case_insensitive() {
+        assert_eq!(Platform::parse("Slack"), Some(Platform::Slack));
+        assert_eq!(Platform::parse("DISCORD"), Some(Platform::Discord));
+        assert_eq!(Platform::parse("teams"), None);
+    }
+}
@@ -20,14 +20,83 @@ pub struct Claims {
     /// before this field existed) decoding as version 0.
     #[serde(default)]
     pub token_version: i32,
+    /// Admin user id when this is a support impersonation token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonated_by: Option<i32>,
+}
+
+/// Server-side secret mixed into account and link passwords before bcrypt
+/// (PASSWORD_PEPPER, unset = none), so a database dump alone can't be cracked.
+/// Changing it invalidates every password hashed with the old one.
+fn password_pepper() -> Option<String> {
+    env::var("PASSWORD_PEPPER").ok().filter(|p| !p.is_empty())
+}
+
+/// What bcrypt sees for `password`. With a pepper it is the base64 HMAC of the
+/// password keyed with the pepper rather than the two appended: bcrypt only
+/// reads 72 bytes, which would let a long password push the pepper out.
+fn peppered(password: &str, pepper: Option<&str>) -> String {
+    use base64::Engine as _;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    let Some(pepper) = pepper else {
+        return password.to_string();
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(pepper.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(password.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+/// Outcome of checking a password against a stored hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordCheck {
+    Mismatch,
+    Match,
+    /// Matches a hash made before PASSWORD_PEPPER was set; hash it again so
+    /// it is peppered too.
+    MatchNeedsRehash,
 }
 
 pub fn hash_password(password: &str) -> Result<String, bcrypt::BcryptError> {
-    hash(password, DEFAULT_COST)
+    hash_password_with(password, password_pepper().as_deref())
+}
+
+fn hash_password_with(password: &str, pepper: Option<&str>) -> Result<String, bcrypt::BcryptError> {
+    hash(peppered(password, pepper), DEFAULT_COST)
+}
+
+/// Check `password` against `hash`, accepting hashes from before the pepper.
+pub fn check_password(password: &str, hash: &str) -> Result<PasswordCheck, bcrypt::BcryptError> {
+    check_password_with(password, hash, password_pepper().as_deref())
+}
+
+fn check_password_with(
+    password: &str,
+    hash: &str,
+    pepper: Option<&str>,
+) -> Result<PasswordCheck, bcrypt::BcryptError> {
+    if verify(peppered(password, pepper), hash)? {
+        return Ok(PasswordCheck::Match);
+    }
+    if pepper.is_some() && verify(password, hash)? {
+        return Ok(PasswordCheck::MatchNeedsRehash);
+    }
+    Ok(PasswordCheck::Mismatch)
 }
 
 pub fn verify_password(password: &str, hash: &str) -> Result<bool, bcrypt::BcryptError> {
-    verify(password, hash)
+    Ok(check_password(password, hash)? != PasswordCheck::Mismatch)
+}
+
+/// A lazily-computed bcrypt hash to verify against when there is no real one
+/// (unknown login, unprotected link), so response time doesn't reveal which.
+pub fn dummy_password_hash() -> &'static str {
+    use once_cell::sync::Lazy;
+    static DUMMY: Lazy<String> =
+        Lazy::new(|| hash_password("not-a-real-password").unwrap_or_default());
+    DUMMY.as_str()
 }
 
 /// Read and validate the JWT signing secret from the environment.
@@ -89,6 +158,7 @@ pub fn create_jwt(
         exp: expiration as usize,
         user_id,
         token_version,
+        impersonated_by: None,
     };
 
     encode(
@@ -98,6 +168,45 @@ pub fn create_jwt(
     )
 }
 
+/// Lifetime of impersonation tokens (IMPERSONATION_TOKEN_MINUTES, default: 30)
+pub fn impersonation_token_minutes() -> i64 {
+    env::var("IMPERSONATION_TOKEN_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|m| *m > 0)
+        .unwrap_or(30)
+}
+
+/// Mint a short-lived token that authenticates as `user_id` on behalf of the
+/// admin `impersonated_by`. Returns the token and its expiry.
+pub fn create_impersonation_jwt(
+    user_id: i32,
+    email: &str,
+    token_version: i32,
+    impersonated_by: i32,
+) -> Result<(String, chrono::DateTime<Utc>), jsonwebtoken::errors::Error> {
+    let secret = jwt_secret();
+
+    let expires_at = Utc::now()
+        .checked_add_signed(Duration::minutes(impersonation_token_minutes()))
+        .expect("valid timestamp");
+
+    let claims = Claims {
+        sub: email.to_owned(),
+        exp: expires_at.timestamp() as usize,
+        user_id,
+        token_version,
+        impersonated_by: Some(impersonated_by),
+    };
+
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )?;
+    Ok((token, expires_at))
+}
+
 pub fn decode_jwt(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
     let secret = jwt_secret();
 
@@ -113,6 +222,61 @@ pub fn decode_jwt(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn peppered_hashes_round_trip_and_need_the_pepper() {
+        let hash = hash_password_with("hunter22", Some("pepper")).unwrap();
+        assert_eq!(
+            check_password_with("hunter22", &hash, Some("pepper")).unwrap(),
+            PasswordCheck::Match
+        );
+        assert_eq!(
+            check_password_with("wrong", &hash, Some("pepper")).unwrap(),
+            PasswordCheck::Mismatch
+        );
+        // The stored hash is useless without the pepper.
+        assert!(!verify("hunter22", &hash).unwrap());
+        assert_eq!(
+            check_password_with("hunter22", &hash, Some("other")).unwrap(),
+            PasswordCheck::Mismatch
+        );
+        assert_eq!(
+            check_password_with("hunter22", &hash, None).unwrap(),
+            PasswordCheck::Mismatch
+        );
+    }
+
+    #[test]
+    fn pre_pepper_hashes_still_verify_and_ask_for_a_rehash() {
+        let legacy = hash_password_with("hunter22", None).unwrap();
+        assert_eq!(
+            check_password_with("hunter22", &legacy, None).unwrap(),
+            PasswordCheck::Match
+        );
+        assert_eq!(
+            check_password_with("hunter22", &legacy, Some("pepper")).unwrap(),
+            PasswordCheck::MatchNeedsRehash
+        );
+        assert_eq!(
+            check_password_with("wrong", &legacy, Some("pepper")).unwrap(),
+            PasswordCheck::Mismatch
+        );
+    }
+
+    #[test]
+    fn pepper_applies_to_passwords_past_the_bcrypt_limit() {
+        // Appended, a pepper would sit past bcrypt's 72 bytes and be ignored.
+        let long = "a".repeat(80);
+        let hash = hash_password_with(&long, Some("pepper")).unwrap();
+        assert_eq!(
+            check_password_with(&long, &hash, Some("other")).unwrap(),
+            PasswordCheck::Mismatch
+        );
+        assert_eq!(
+            check_password_with(&format!("{long}b"), &hash, Some("pepper")).unwrap(),
+            PasswordCheck::Mismatch
+        );
+    }
+
     // Single test (no parallel writes to the shared JWT_SECRET env var) covering
     // both the startup guard (B1) and a normal sign/verify round-trip.
     #[test]
@@ -0,0 +1,82 @@
+//! Landing interstitial: a small countdown page that links to the destination,
+//! served instead of an immediate 302 where a deployment must show visitors
+//! where they are going before they get there. Unlike the safe-link
+//! interstitial (an SPA screen the visitor confirms), this page is rendered
+//! by the backend and the click is recorded when it is served.
+
+use crate::handlers::contact::html_escape;
+
+/// Serve every redirect through the landing page (USE_INTERSTITIAL, default: false).
+/// Links can also opt in individually with `use_interstitial`.
+pub fn interstitial_forced() -> bool {
+    std::env::var("USE_INTERSTITIAL")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse::<bool>()
+        .unwrap_or(false)
+}
+
+/// Seconds the landing page waits before forwarding (INTERSTITIAL_COUNTDOWN_SECS,
+/// default: 5, max 60).
+pub fn countdown_secs() -> u64 {
+    std::env::var("INTERSTITIAL_COUNTDOWN_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(5)
+        .min(60)
+}
+
+/// Content-Security-Policy for the page: only its own inline style and
+/// countdown script may run.
+pub const CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; style-src 'unsafe-inline'; script-src 'unsafe-inline'";
+
+/// Render the countdown page. The forward happens through a meta refresh, so
+/// it still works with scripts disabled; the script only ticks the counter.
+pub fn render(destination: &str, seconds: u64) -> String {
+    let url = html_escape(destination);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="referrer" content="no-referrer">
+<meta http-equiv="refresh" content="{seconds};url={url}">
+<title>Redirecting…</title>
+<style>
+body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; color: #333; max-width: 560px; margin: 15vh auto; padding: 0 20px; text-align: center; }}
+a {{ color: #2563eb; word-break: break-all; }}
+</style>
+</head>
+<body>
+<p>You are being redirected in <span id="countdown">{seconds}</span> seconds to:</p>
+<p><a href="{url}" rel="noreferrer">{url}</a></p>
+<script>
+var n = {seconds}, el = document.getElementById("countdown");
+var t = setInterval(function () {{ n = Math.max(n - 1, 0); el.textContent = n; if (n === 0) clearInterval(t); }}, 1000);
+</script>
+</body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_links_and_refreshes_to_destination() {
+        let html = render("https://iana.org/a?b=1&c=2", 3);
+        assert!(html.contains(r#"content="3;url=https://iana.org/a?b=1&amp;c=2""#));
+        assert!(html.contains(r#"href="https://iana.org/a?b=1&amp;c=2""#));
+        assert!(html.contains(r#"<span id="countdown">3</span>"#));
+    }
+
+    #[test]
+    fn destination_cannot_break_out_of_attributes() {
+        let html = render(r#"https://iana.org/"><script>alert(1)</script>"#, 5);
+        assert!(!html.contains("<script>alert"));
+        assert!(html.contains("&quot;&gt;&lt;script&gt;"));
+    }
+}
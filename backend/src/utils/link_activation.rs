@@ -0,0 +1,113 @@
+//! Scheduled activation: links with a future `starts_at` go live on their own
+//! when the time passes. A periodic sweep picks up links whose start time has
+//! just passed, drops any cached "not yet active" entry, and, when the owner
+//! opted in (`notify_on_start`), emails them. `start_notified` records that a
+//! link's current schedule has been handled so it is never processed twice.
+
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use std::sync::Arc;
+
+use crate::entity::{links, users};
+use crate::handlers::links::get_base_url;
+use crate::utils::cache::RedisCache;
+use crate::utils::EmailService;
+
+/// How often the activation sweep runs, in seconds.
+const SWEEP_INTERVAL_SECS: u64 = 60;
+
+fn due_condition(now: chrono::NaiveDateTime) -> Condition {
+    Condition::all()
+        .add(links::Column::StartsAt.is_not_null())
+        .add(links::Column::StartsAt.lte(now))
+        .add(links::Column::StartNotified.eq(false))
+        .add(links::Column::DeletedAt.is_null())
+}
+
+/// Live links whose `starts_at` is at or before `now` and that haven't been
+/// handled by the activation sweep yet.
+pub async fn links_due_for_activation(
+    db: &DatabaseConnection,
+    now: chrono::NaiveDateTime,
+) -> Result<Vec<links::Model>, sea_orm::DbErr> {
+    links::Entity::find()
+        .filter(due_condition(now))
+        .order_by_asc(links::Column::StartsAt)
+        .all(db)
+        .await
+}
+
+/// Mark every due link as handled and return them. The flag is flipped in the
+/// same `UPDATE ... RETURNING`, so with several instances running each link is
+/// claimed by exactly one of them.
+pub async fn claim_due_links(
+    db: &DatabaseConnection,
+    now: chrono::NaiveDateTime,
+) -> Result<Vec<links::Model>, sea_orm::DbErr> {
+    links::Entity::update_many()
+        .col_expr(links::Column::StartNotified, Expr::value(true))
+        .filter(due_condition(now))
+        .exec_with_returning(db)
+        .await
+}
+
+/// One pass of the sweep. Returns how many links were activated.
+pub async fn run_activation_sweep(
+    db: &DatabaseConnection,
+    redis_cache: Option<&RedisCache>,
+    email_service: Option<&EmailService>,
+) -> Result<usize, sea_orm::DbErr> {
+    let activated = claim_due_links(db, chrono::Utc::now().naive_utc()).await?;
+    let base_url = get_base_url();
+
+    for link in &activated {
+        if let Some(cache) = redis_cache {
+            if let Err(e) = cache.invalidate_link(&link.code).await {
+                tracing::warn!("Failed to invalidate cache for {}: {}", link.code, e);
+            }
+        }
+
+        if !link.notify_on_start {
+            continue;
+        }
+        let (Some(email_service), Some(user_id)) = (email_service, link.user_id) else {
+            continue;
+        };
+        let owner = match users::Entity::find_by_id(user_id).one(db).await {
+            Ok(Some(u)) if !u.is_deleted() => u,
+            _ => continue,
+        };
+        let short_url = format!("{}/{}", base_url, link.code);
+        if let Err(e) = email_service
+            .send_link_activated_email(&owner.email, &short_url, &link.original_url)
+            .await
+        {
+            tracing::warn!("Activation notice for link {} failed: {}", link.id, e);
+        }
+    }
+
+    Ok(activated.len())
+}
+
+/// Spawn the activation sweep.
+pub fn spawn_activation_task(
+    db: DatabaseConnection,
+    redis_cache: Option<Arc<RedisCache>>,
+    email_service: Option<Arc<EmailService>>,
+) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(SWEEP_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match run_activation_sweep(&db, redis_cache.as_deref(), email_service.as_deref()).await
+            {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("Scheduled link activation: {} link(s) went live", n),
+                Err(e) => tracing::error!("Scheduled link activation sweep failed: {}", e),
+            }
+        }
+    });
+}
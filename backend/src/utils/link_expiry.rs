@@ -0,0 +1,93 @@
+//! Expired links: selection for the admin listing and an optional compaction
+//! sweep. An expired link keeps answering 410 while its row is live; once it
+//! has been expired for longer than the grace period the sweep soft-deletes
+//! it, after which the code behaves like any other deleted link.
+
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Select,
+};
+use std::sync::Arc;
+
+use crate::entity::links;
+use crate::utils::cache::RedisCache;
+
+/// Compaction grace period in days, from `EXPIRED_LINK_GRACE_DAYS`.
+/// Unset, `0` or invalid disables compaction.
+pub fn expired_link_grace_days() -> Option<i64> {
+    std::env::var("EXPIRED_LINK_GRACE_DAYS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|d| *d > 0)
+}
+
+fn expired_before(cutoff: chrono::NaiveDateTime) -> Condition {
+    Condition::all()
+        .add(links::Column::ExpiresAt.is_not_null())
+        .add(links::Column::ExpiresAt.lt(cutoff))
+        .add(links::Column::DeletedAt.is_null())
+}
+
+/// Live links whose `expires_at` has passed as of `now`, most recently
+/// expired first.
+pub fn expired_links_query(now: chrono::NaiveDateTime) -> Select<links::Entity> {
+    links::Entity::find()
+        .filter(expired_before(now))
+        .order_by_desc(links::Column::ExpiresAt)
+}
+
+/// Soft-delete links that expired more than `grace_days` before `now` and
+/// return them.
+pub async fn compact_expired_links(
+    db: &DatabaseConnection,
+    now: chrono::NaiveDateTime,
+    grace_days: i64,
+) -> Result<Vec<links::Model>, sea_orm::DbErr> {
+    links::Entity::update_many()
+        .col_expr(links::Column::DeletedAt, Expr::value(now))
+        .filter(expired_before(now - chrono::Duration::days(grace_days)))
+        .exec_with_returning(db)
+        .await
+}
+
+/// Spawn the daily compaction sweep when `EXPIRED_LINK_GRACE_DAYS` is set.
+pub fn spawn_compaction_task(db: DatabaseConnection, redis_cache: Option<Arc<RedisCache>>) {
+    let Some(days) = expired_link_grace_days() else {
+        tracing::info!("Expired link compaction disabled (EXPIRED_LINK_GRACE_DAYS unset)");
+        return;
+    };
+
+    tracing::info!(
+        "Expired link compaction enabled: soft-deleting links expired over {} days ago",
+        days
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+        loop {
+            interval.tick().await;
+            match compact_expired_links(&db, chrono::Utc::now().naive_utc(), days).await {
+                Ok(compacted) => {
+                    if let Some(cache) = redis_cache.as_deref() {
+                        for link in &compacted {
+                            if let Err(e) = cache.invalidate_link(&link.code).await {
+                                tracing::warn!(
+                                    "Failed to invalidate cache for {}: {}",
+                                    link.code,
+                                    e
+                                );
+                            }
+                        }
+                    }
+                    if !compacted.is_empty() {
+                        tracing::info!(
+                            "Expired link compaction soft-deleted {} link(s)",
+                            compacted.len()
+                        );
+                    }
+                }
+                Err(e) => tracing::error!("Expired link compaction failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,196 @@
+//! Destination health checks. A periodic sweep HEAD-requests a batch of live
+//! links' destinations through the same SSRF guard as the on-demand
+//! `/links/health-check`, and stamps each link with `last_checked` and
+//! `last_status`. `GET /links/broken` lists links whose last check failed.
+
+use sea_orm::{
+    sea_query::{Expr, NullOrdering},
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use std::future::Future;
+
+use crate::entity::links;
+
+/// `last_status` recorded when a destination gave no HTTP response at all
+/// (DNS failure, refused connection, timeout, SSRF refusal).
+pub const NO_RESPONSE: i32 = 0;
+
+/// Pause between two checks so a sweep never bursts at one host.
+const PAUSE_BETWEEN_CHECKS_MS: u64 = 500;
+
+/// Minutes between health sweeps, from `LINK_HEALTH_CHECK_INTERVAL_MINUTES`.
+/// Unset, `0` or invalid disables the sweep.
+pub fn health_check_interval_minutes() -> Option<u64> {
+    std::env::var("LINK_HEALTH_CHECK_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|m| *m > 0)
+}
+
+/// Links checked per sweep (LINK_HEALTH_CHECK_SAMPLE_SIZE, default: 50)
+pub fn health_check_sample_size() -> u64 {
+    std::env::var("LINK_HEALTH_CHECK_SAMPLE_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(50)
+}
+
+/// Whether a recorded status means the destination is broken: no response,
+/// or a 4xx/5xx answer.
+pub fn is_broken_status(status: i32) -> bool {
+    status == NO_RESPONSE || status >= 400
+}
+
+/// Condition matching links whose last check found them broken.
+pub fn broken_condition() -> Condition {
+    Condition::all()
+        .add(links::Column::LastStatus.is_not_null())
+        .add(
+            Condition::any()
+                .add(links::Column::LastStatus.eq(NO_RESPONSE))
+                .add(links::Column::LastStatus.gte(400)),
+        )
+}
+
+/// Live links with a destination, never-checked ones first and then the
+/// longest unchecked.
+async fn links_due(
+    db: &DatabaseConnection,
+    limit: u64,
+) -> Result<Vec<(i32, String)>, sea_orm::DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    links::Entity::find()
+        .select_only()
+        .column(links::Column::Id)
+        .column(links::Column::OriginalUrl)
+        .filter(links::Column::DeletedAt.is_null())
+        .filter(links::Column::DisabledByAdmin.eq(false))
+        .filter(links::Column::IsReserved.eq(false))
+        .filter(links::Column::OriginalUrl.ne(""))
+        .filter(
+            Condition::any()
+                .add(links::Column::ExpiresAt.is_null())
+                .add(links::Column::ExpiresAt.gt(now)),
+        )
+        .order_by_with_nulls(links::Column::LastChecked, Order::Asc, NullOrdering::First)
+        .order_by_asc(links::Column::Id)
+        .limit(limit)
+        .into_tuple()
+        .all(db)
+        .await
+}
+
+/// Stamp one link with the outcome of a check.
+pub async fn record_check(
+    db: &DatabaseConnection,
+    link_id: i32,
+    status: i32,
+    checked_at: chrono::NaiveDateTime,
+) -> Result<(), sea_orm::DbErr> {
+    links::Entity::update_many()
+        .col_expr(links::Column::LastChecked, Expr::value(checked_at))
+        .col_expr(links::Column::LastStatus, Expr::value(status))
+        .filter(links::Column::Id.eq(link_id))
+        .exec(db)
+        .await
+        .map(|_| ())
+}
+
+/// Check up to `limit` due links with `probe`. Returns how many were found
+/// broken.
+pub async fn run_health_sweep<P, F>(
+    db: &DatabaseConnection,
+    limit: u64,
+    pause: std::time::Duration,
+    probe: P,
+) -> Result<usize, sea_orm::DbErr>
+where
+    P: Fn(String) -> F,
+    F: Future<Output = Result<u16, String>>,
+{
+    let due = links_due(db, limit).await?;
+    check_links(db, due, pause, probe).await
+}
+
+/// Probe each `(link id, destination)` in turn and record the outcome.
+/// `probe` returns the destination's HTTP status, or an error when there was
+/// no response. Returns how many links were found broken.
+pub async fn check_links<P, F>(
+    db: &DatabaseConnection,
+    targets: Vec<(i32, String)>,
+    pause: std::time::Duration,
+    probe: P,
+) -> Result<usize, sea_orm::DbErr>
+where
+    P: Fn(String) -> F,
+    F: Future<Output = Result<u16, String>>,
+{
+    let mut broken = 0;
+    for (i, (link_id, url)) in targets.into_iter().enumerate() {
+        if i > 0 && !pause.is_zero() {
+            tokio::time::sleep(pause).await;
+        }
+        let status = match probe(url).await {
+            Ok(code) => i32::from(code),
+            Err(_) => NO_RESPONSE,
+        };
+        if is_broken_status(status) {
+            broken += 1;
+        }
+        record_check(db, link_id, status, chrono::Utc::now().naive_utc()).await?;
+    }
+    Ok(broken)
+}
+
+/// Spawn the periodic health sweep when `LINK_HEALTH_CHECK_INTERVAL_MINUTES`
+/// is set.
+pub fn spawn_health_check_task(db: DatabaseConnection) {
+    let Some(minutes) = health_check_interval_minutes() else {
+        tracing::info!(
+            "Destination health checks disabled (LINK_HEALTH_CHECK_INTERVAL_MINUTES unset)"
+        );
+        return;
+    };
+    let sample = health_check_sample_size();
+
+    tracing::info!(
+        "Destination health checks enabled: {} link(s) every {} minute(s)",
+        sample,
+        minutes
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(minutes * 60));
+        loop {
+            interval.tick().await;
+            match run_health_sweep(
+                &db,
+                sample,
+                std::time::Duration::from_millis(PAUSE_BETWEEN_CHECKS_MS),
+                crate::handlers::links::probe_destination,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("Destination health check found {} broken link(s)", n),
+                Err(e) => tracing::error!("Destination health check failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_and_missing_responses_are_broken() {
+        assert!(is_broken_status(NO_RESPONSE));
+        assert!(is_broken_status(404));
+        assert!(is_broken_status(503));
+        assert!(!is_broken_status(200));
+        assert!(!is_broken_status(301));
+    }
+}
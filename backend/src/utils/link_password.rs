@@ -0,0 +1,77 @@
+//! Strength rules for link passwords. These protect a shared link rather than
+//! an account, so they are configured separately from account passwords
+//! (always at least 8 characters).
+
+/// Minimum strength a new link password must meet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinkPasswordPolicy {
+    /// Fewest characters accepted.
+    pub min_length: usize,
+    /// Require at least one letter and one digit.
+    pub require_letter_and_digit: bool,
+}
+
+impl LinkPasswordPolicy {
+    /// Check `password` against the policy, with a message for the caller
+    /// when it is too weak.
+    pub fn check(&self, password: &str) -> Result<(), String> {
+        if password.chars().count() < self.min_length {
+            return Err(format!(
+                "Link password must be at least {} characters",
+                self.min_length
+            ));
+        }
+        if self.require_letter_and_digit
+            && !(password.chars().any(char::is_alphabetic)
+                && password.chars().any(|c| c.is_ascii_digit()))
+        {
+            return Err("Link password must contain a letter and a digit".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Link password policy (LINK_PASSWORD_MIN_LENGTH, default: 6;
+/// LINK_PASSWORD_REQUIRE_LETTER_AND_DIGIT, default: false).
+pub fn link_password_policy() -> LinkPasswordPolicy {
+    LinkPasswordPolicy {
+        min_length: std::env::var("LINK_PASSWORD_MIN_LENGTH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(6),
+        require_letter_and_digit: std::env::var("LINK_PASSWORD_REQUIRE_LETTER_AND_DIGIT")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enforces_minimum_length_in_characters() {
+        let policy = LinkPasswordPolicy {
+            min_length: 6,
+            require_letter_and_digit: false,
+        };
+        assert!(policy.check("1").is_err());
+        assert!(policy.check("12345").is_err());
+        assert!(policy.check("123456").is_ok());
+        // Characters, not bytes.
+        assert!(policy.check("ééééé").is_err());
+        assert!(policy.check("éééééé").is_ok());
+    }
+
+    #[test]
+    fn complexity_needs_a_letter_and_a_digit() {
+        let policy = LinkPasswordPolicy {
+            min_length: 6,
+            require_letter_and_digit: true,
+        };
+        assert!(policy.check("abcdef").is_err());
+        assert!(policy.check("123456").is_err());
+        assert!(policy.check("abcde1").is_ok());
+    }
+}
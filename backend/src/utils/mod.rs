@@ -1,15 +1,29 @@
+pub mod account_deletion;
 pub mod backup;
 pub mod cache;
 pub mod click_buffer;
+pub mod click_source;
 pub mod email;
 pub mod email_domain_policy;
+pub mod email_queue;
+pub mod expired_page;
 pub mod geoip;
+pub mod integrations;
 pub mod jwt;
+pub mod landing_page;
+pub mod link_activation;
+pub mod link_expiry;
+pub mod link_health;
+pub mod link_password;
 pub mod link_unlock;
 pub mod privacy;
 pub mod rate_limiter;
 pub mod routing;
+pub mod short_code;
+pub mod timezone;
 pub mod url_policy;
+pub mod validation;
+pub mod webhook;
 
 pub use backup::BackupService;
 pub use click_buffer::ClickBuffer;
@@ -5,6 +5,8 @@
 //! city granularity, so we truncate before storage (IPv4 to /24, IPv6 to /48)
 //! and null the remaining identifier columns after a retention window while
 //! keeping the aggregate dimensions (country, city, device, browser, referer).
+//! Deployments that must not keep IPs at all can set `STORE_RAW_IP=false`, in
+//! which case only a keyed hash of the address is stored.
 
 use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
 use std::net::{IpAddr, Ipv6Addr};
@@ -32,6 +34,52 @@ pub fn anonymize_ip(ip_str: &str) -> Option<String> {
     }
 }
 
+/// Whether click events keep the (truncated) visitor IP (STORE_RAW_IP,
+/// default: true). When false only [`hash_ip`] is stored; GeoIP still runs on
+/// the full address in memory first.
+pub fn store_raw_ip() -> bool {
+    std::env::var("STORE_RAW_IP")
+        .map(|v| v != "false")
+        .unwrap_or(true)
+}
+
+/// Whether a `DNT: 1` request header is honored on redirects (RESPECT_DNT,
+/// default: false). Honored clicks still count towards the link's total but
+/// leave no per-visitor event row.
+pub fn respect_dnt() -> bool {
+    std::env::var("RESPECT_DNT")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+}
+
+/// True when [`respect_dnt`] is on and the visitor sent `DNT: 1`.
+pub fn dnt_requested(headers: &axum::http::HeaderMap) -> bool {
+    respect_dnt()
+        && headers
+            .get("dnt")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.trim() == "1")
+}
+
+/// HMAC-SHA256 of the full IP keyed with `JWT_SECRET`, hex-encoded. Stable per
+/// visitor so unique-visitor counts work, but not reversible by enumerating the
+/// address space without the server secret. Unparsable input is dropped.
+pub fn hash_ip(ip_str: &str) -> Option<String> {
+    let ip = ip_str.trim().parse::<IpAddr>().ok()?;
+    let secret = std::env::var("JWT_SECRET").unwrap_or_default();
+    Some(keyed_ip_hash(secret.as_bytes(), ip))
+}
+
+fn keyed_ip_hash(key: &[u8], ip: IpAddr) -> String {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(ip.to_string().as_bytes());
+    let digest = mac.finalize().into_bytes();
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 /// Reduce a `Referer` header to just its host before storage. The full referring
 /// URL can carry personal data in its path/query (search terms, session IDs,
 /// tokens), which analytics does not need and we should not retain. Returns e.g.
@@ -56,7 +104,7 @@ pub fn pii_retention_days() -> Option<i32> {
     }
 }
 
-/// Null `ip_address` and `user_agent` on click events older than `days`.
+/// Null `ip_address`, `ip_hash` and `user_agent` on click events older than `days`.
 /// Aggregate columns (country, city, region, device, browser, os, referer,
 /// coordinates) are kept so historical analytics stay useful.
 pub async fn scrub_expired_click_pii(
@@ -66,9 +114,9 @@ pub async fn scrub_expired_click_pii(
     let res = db
         .execute(Statement::from_sql_and_values(
             sea_orm::DatabaseBackend::Postgres,
-            "UPDATE click_events SET ip_address = NULL, user_agent = NULL \
+            "UPDATE click_events SET ip_address = NULL, ip_hash = NULL, user_agent = NULL \
              WHERE created_at < NOW() - make_interval(days => $1) \
-               AND (ip_address IS NOT NULL OR user_agent IS NOT NULL)",
+               AND (ip_address IS NOT NULL OR ip_hash IS NOT NULL OR user_agent IS NOT NULL)",
             [days.into()],
         ))
         .await?;
@@ -87,7 +135,7 @@ pub async fn purge_click_pii_for_user<C: ConnectionTrait>(
     let res = db
         .execute(Statement::from_sql_and_values(
             sea_orm::DatabaseBackend::Postgres,
-            "UPDATE click_events SET ip_address = NULL, user_agent = NULL, referer = NULL \
+            "UPDATE click_events SET ip_address = NULL, ip_hash = NULL, user_agent = NULL, referer = NULL \
              WHERE link_id IN (SELECT id FROM links WHERE user_id = $1 AND org_id IS NULL)",
             [user_id.into()],
         ))
@@ -157,6 +205,19 @@ mod tests {
         assert_eq!(anonymize_ip("203.0.113.0").as_deref(), Some("203.0.113.0"));
     }
 
+    #[test]
+    fn ip_hash_is_stable_and_not_the_ip() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        let a = keyed_ip_hash(b"k", ip("203.0.113.195"));
+        assert_eq!(a.len(), 64);
+        assert_eq!(keyed_ip_hash(b"k", ip("203.0.113.195")), a);
+        // Same /24, different visitor: distinct hashes.
+        assert_ne!(keyed_ip_hash(b"k", ip("203.0.113.7")), a);
+        // Different server secret: unlinkable.
+        assert_ne!(keyed_ip_hash(b"other", ip("203.0.113.195")), a);
+        assert_eq!(hash_ip("not-an-ip"), None);
+    }
+
     #[test]
     fn referer_reduced_to_host_only() {
         // Path and query (potential PII) are dropped; only the host is kept.
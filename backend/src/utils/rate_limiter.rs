@@ -53,6 +53,12 @@ impl RateLimiter {
 
     /// Check if a request is allowed and increment counter
     pub fn check(&self, key: &str) -> RateLimitResult {
+        self.check_with_limit(key, self.config.max_requests)
+    }
+
+    /// Like [`check`](Self::check), but with a per-key request budget in place
+    /// of the configured one. The window is still the configured duration.
+    pub fn check_with_limit(&self, key: &str, max_requests: u32) -> RateLimitResult {
         let now = Instant::now();
 
         // Get or create entry
@@ -72,7 +78,7 @@ impl RateLimiter {
         }
 
         // Check limit
-        if entry.count >= self.config.max_requests {
+        if entry.count >= max_requests {
             let retry_after = self
                 .config
                 .window_duration
@@ -81,17 +87,17 @@ impl RateLimiter {
 
             return RateLimitResult::Limited {
                 retry_after_secs: retry_after.as_secs(),
-                limit: self.config.max_requests,
+                limit: max_requests,
                 remaining: 0,
             };
         }
 
         // Increment and allow
         entry.count += 1;
-        let remaining = self.config.max_requests.saturating_sub(entry.count);
+        let remaining = max_requests.saturating_sub(entry.count);
 
         RateLimitResult::Allowed {
-            limit: self.config.max_requests,
+            limit: max_requests,
             remaining,
         }
     }
@@ -141,6 +147,12 @@ pub struct RateLimiters {
     /// Contact form limiter (a few per hour per IP). The contact endpoint sends
     /// email, so it must be strict regardless of the general API tier.
     pub contact: Arc<RateLimiter>,
+    /// Per-link redirect budget (`links.redirect_rate_limit` per second, across
+    /// all visitors). Used when Redis is not configured; the limit is passed
+    /// per check.
+    pub link_redirect: Arc<RateLimiter>,
+    /// Networks that bypass every tier above (RATE_LIMIT_ALLOWLIST).
+    pub allowlist: Arc<IpAllowlist>,
 }
 
 impl Default for RateLimiters {
@@ -159,10 +171,86 @@ impl Default for RateLimiters {
             password_verify_ip: Arc::new(RateLimiter::new(RateLimitConfig::new(20, 60))),
             // Contact form sends email: cap at 10 per hour per IP.
             contact: Arc::new(RateLimiter::new(RateLimitConfig::new(10, 3600))),
+            link_redirect: Arc::new(RateLimiter::new(RateLimitConfig::new(u32::MAX, 1))),
+            allowlist: Arc::new(IpAllowlist::from_env()),
         }
     }
 }
 
+/// Client IPs exempt from rate limiting, e.g. internal services and health
+/// checkers. Parsed once from `RATE_LIMIT_ALLOWLIST`, a comma-separated list
+/// of addresses or CIDR blocks (`10.0.0.0/8, 2001:db8::/32, 203.0.113.7`).
+/// Entries that don't parse are logged and skipped.
+#[derive(Debug, Clone, Default)]
+pub struct IpAllowlist {
+    networks: Vec<(IpAddr, u8)>,
+}
+
+impl IpAllowlist {
+    pub fn from_env() -> Self {
+        let allowlist = Self::parse(&std::env::var("RATE_LIMIT_ALLOWLIST").unwrap_or_default());
+        if !allowlist.networks.is_empty() {
+            tracing::info!(
+                "Rate limiting disabled for {} allowlisted network(s)",
+                allowlist.networks.len()
+            );
+        }
+        allowlist
+    }
+
+    pub fn parse(spec: &str) -> Self {
+        let networks = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let network = parse_network(entry);
+                if network.is_none() {
+                    tracing::warn!("Ignoring invalid RATE_LIMIT_ALLOWLIST entry {:?}", entry);
+                }
+                network
+            })
+            .collect();
+        Self { networks }
+    }
+
+    /// Whether `ip` (as produced by [`extract_ip`]) falls in any listed network.
+    pub fn contains(&self, ip: &str) -> bool {
+        let Ok(ip) = ip.parse::<IpAddr>() else {
+            return false;
+        };
+        let ip = ip.to_canonical();
+        self.networks
+            .iter()
+            .any(|&(network, prefix)| network_contains(network, prefix, ip))
+    }
+}
+
+fn parse_network(entry: &str) -> Option<(IpAddr, u8)> {
+    let (addr, prefix) = match entry.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix.parse::<u8>().ok()?)),
+        None => (entry, None),
+    };
+    let addr = addr.parse::<IpAddr>().ok()?.to_canonical();
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max);
+    (prefix <= max).then_some((addr, prefix))
+}
+
+fn network_contains(network: IpAddr, prefix: u8, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(net), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(net) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(net), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(net) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
 impl RateLimiters {
     pub fn new() -> Self {
         Self::default()
@@ -182,6 +270,7 @@ impl RateLimiters {
                 limiters.password_verify.cleanup();
                 limiters.password_verify_ip.cleanup();
                 limiters.contact.cleanup();
+                limiters.link_redirect.cleanup();
                 tracing::debug!("Rate limiter cleanup completed");
             }
         });
@@ -248,8 +337,33 @@ fn client_ip_config() -> &'static ClientIpConfig {
 }
 
 /// Parse a single header/XFF token into a canonical IP string.
+///
+/// Proxies don't agree on a format, so besides a bare address this accepts a
+/// quoted token, a bracketed IPv6 (`[2001:db8::1]`, optionally with `:port`),
+/// an IPv4 `addr:port`, and an IPv6 zone suffix (`fe80::1%eth0`). IPv4-mapped
+/// IPv6 (`::ffff:203.0.113.7`) comes back as plain IPv4 so one client gets one
+/// rate-limit bucket and one GeoIP answer whichever stack it arrived on.
 fn parse_ip(token: &str) -> Option<String> {
-    token.trim().parse::<IpAddr>().ok().map(|ip| ip.to_string())
+    let token = token.trim().trim_matches('"');
+    let addr = if let Some(rest) = token.strip_prefix('[') {
+        let (inner, after) = rest.split_once(']')?;
+        if !after.is_empty() && after.strip_prefix(':')?.parse::<u16>().is_err() {
+            return None;
+        }
+        inner
+    } else if let Some((host, port)) = token
+        .split_once(':')
+        .filter(|(host, _)| host.parse::<std::net::Ipv4Addr>().is_ok())
+    {
+        port.parse::<u16>().ok()?;
+        host
+    } else {
+        token
+    };
+    let addr = addr.split_once('%').map_or(addr, |(ip, _zone)| ip);
+    addr.parse::<IpAddr>()
+        .ok()
+        .map(|ip| ip.to_canonical().to_string())
 }
 
 /// Resolve the real client IP from forwarding headers per `config`.
@@ -325,7 +439,9 @@ const API_PREFIXES: &[&str] = &[
     "ws",
     "sse",
     "health",
+    "version",
     "api",
+    "u",
     "swagger-ui",
     "api-docs",
 ];
@@ -351,6 +467,9 @@ pub async fn rate_limit_middleware(
     next: Next,
 ) -> Response {
     let ip = extract_ip(&req);
+    if limiters.allowlist.contains(&ip) {
+        return next.run(req).await;
+    }
     let path = req.uri().path();
 
     // Redirects are high-volume and skip the strict per-second gate; everything
@@ -460,6 +579,25 @@ pub async fn rate_limit_middleware(
 mod tests {
     use super::*;
 
+    #[test]
+    fn allowlist_matches_addresses_and_cidr_blocks() {
+        let allowlist =
+            IpAllowlist::parse("10.0.0.0/8, 192.168.1.5 ,2001:db8::/32, bogus, 1.2.3.4/33");
+        assert!(allowlist.contains("10.20.30.40"));
+        assert!(allowlist.contains("192.168.1.5"));
+        assert!(!allowlist.contains("192.168.1.6"));
+        assert!(allowlist.contains("2001:db8:ffff::1"));
+        assert!(!allowlist.contains("2001:db9::1"));
+        assert!(!allowlist.contains("11.0.0.1"));
+        // IPv4-mapped IPv6 and IPv4 are the same client.
+        assert!(allowlist.contains("::ffff:10.1.1.1"));
+        assert!(!allowlist.contains("unknown"));
+        assert_eq!(allowlist.networks.len(), 3, "invalid entries are skipped");
+
+        assert!(IpAllowlist::parse("0.0.0.0/0").contains("203.0.113.9"));
+        assert!(!IpAllowlist::default().contains("127.0.0.1"));
+    }
+
     #[test]
     fn redirect_classifier_separates_codes_from_api_routes() {
         // Short-code redirect routes: first segment is the code.
@@ -532,6 +670,28 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_rate_limiter_per_key_limit() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(u32::MAX, 60));
+
+        assert!(matches!(
+            limiter.check_with_limit("link:1", 2),
+            RateLimitResult::Allowed {
+                limit: 2,
+                remaining: 1
+            }
+        ));
+        limiter.check_with_limit("link:1", 2);
+        assert!(matches!(
+            limiter.check_with_limit("link:1", 2),
+            RateLimitResult::Limited { limit: 2, .. }
+        ));
+        assert!(matches!(
+            limiter.check_with_limit("link:2", 5),
+            RateLimitResult::Allowed { .. }
+        ));
+    }
+
     #[tokio::test]
     async fn password_ip_budget_cannot_be_bypassed_by_rotating_codes() {
         use axum::{middleware, routing::post, Router};
@@ -545,6 +705,8 @@ mod tests {
             password_verify: Arc::new(RateLimiter::new(RateLimitConfig::new(100, 60))),
             password_verify_ip: Arc::new(RateLimiter::new(RateLimitConfig::new(2, 60))),
             contact: Arc::new(RateLimiter::new(RateLimitConfig::new(10_000, 3600))),
+            link_redirect: Arc::new(RateLimiter::new(RateLimitConfig::new(u32::MAX, 1))),
+            allowlist: Arc::new(IpAllowlist::default()),
         });
         let app = Router::new()
             .route("/:code/verify", post(|| async { "ok" }))
@@ -694,6 +856,37 @@ mod tests {
             );
         }
 
+        #[test]
+        fn ipv6_forms_from_proxies_are_cleaned() {
+            for (xff, expected) in [
+                ("2001:db8::1", "2001:db8::1"),
+                ("[2001:db8::1]", "2001:db8::1"),
+                ("[2001:db8::1]:443", "2001:db8::1"),
+                ("\"[2001:db8::1]:443\"", "2001:db8::1"),
+                ("6.6.6.6, [2001:db8::1]", "2001:db8::1"),
+                ("2001:db8::2, 2001:db8::1", "2001:db8::1"),
+                ("fe80::1%eth0", "fe80::1"),
+                ("::ffff:203.0.113.7", "203.0.113.7"),
+                ("203.0.113.7:51234", "203.0.113.7"),
+                ("[::1]", "::1"),
+            ] {
+                let headers = headers_of(&[("x-forwarded-for", xff)]);
+                assert_eq!(
+                    client_ip_with(&headers, &cfg(true, None, 0)).as_deref(),
+                    Some(expected),
+                    "{xff}"
+                );
+            }
+            for bad in [
+                "[2001:db8::1",
+                "[2001:db8::1]x",
+                "[2001:db8::1]:port",
+                "1.2.3.4:",
+            ] {
+                assert_eq!(parse_ip(bad), None, "{bad}");
+            }
+        }
+
         #[test]
         fn ipv6_is_canonicalized_for_stable_bucket_keys() {
             let headers = headers_of(&[("cf-connecting-ip", "2001:DB8:0:0:0:0:0:1")]);
@@ -718,6 +911,10 @@ mod tests {
         use axum::{middleware, routing::post, Router};
 
         async fn spawn_test_server() -> SocketAddr {
+            spawn_test_server_with(IpAllowlist::default()).await
+        }
+
+        async fn spawn_test_server_with(allowlist: IpAllowlist) -> SocketAddr {
             // Matches the production deployment: proxy headers are trusted.
             // REAL_IP_HEADER is left unset so the cf-connecting-ip default
             // applies.
@@ -733,6 +930,8 @@ mod tests {
                 password_verify: Arc::new(RateLimiter::new(RateLimitConfig::new(10_000, 60))),
                 password_verify_ip: Arc::new(RateLimiter::new(RateLimitConfig::new(10_000, 60))),
                 contact: Arc::new(RateLimiter::new(RateLimitConfig::new(10_000, 3600))),
+                link_redirect: Arc::new(RateLimiter::new(RateLimitConfig::new(u32::MAX, 1))),
+                allowlist: Arc::new(allowlist),
             });
 
             let app = Router::new()
@@ -820,5 +1019,35 @@ mod tests {
                 .unwrap();
             assert_eq!(res.status().as_u16(), 429);
         }
+
+        #[tokio::test]
+        async fn allowlisted_clients_are_never_limited() {
+            let addr =
+                spawn_test_server_with(IpAllowlist::parse("198.51.100.0/24, 2001:db8::1")).await;
+            let client = reqwest::Client::new();
+            let status = |ip: &'static str| {
+                let client = client.clone();
+                async move {
+                    client
+                        .post(format!("http://{addr}/auth/login"))
+                        .header("cf-connecting-ip", ip)
+                        .send()
+                        .await
+                        .unwrap()
+                        .status()
+                        .as_u16()
+                }
+            };
+
+            for ip in ["198.51.100.20", "2001:db8::1"] {
+                for i in 0..5 {
+                    assert_eq!(status(ip).await, 200, "allowlisted {ip} request {i}");
+                }
+            }
+            // Outside the allowlist the 2-per-minute budget still applies.
+            assert_eq!(status("198.51.101.20").await, 200);
+            assert_eq!(status("198.51.101.20").await, 200);
+            assert_eq!(status("198.51.101.20").await, 429);
+        }
     }
 }
@@ -0,0 +1,111 @@
+//! Auto-generated short codes.
+//!
+//! `SHORT_CODE_MODE=random` (default) draws 6 random alphanumerics and retries
+//! on collision. `SHORT_CODE_MODE=sequential` encodes the next value of the
+//! `link_code_seq` Postgres sequence in base62 instead: every value is unique,
+//! so no lookup is needed before insert. The sequence starts at 62^4, so
+//! codes begin at five characters and grow with the link count. Custom
+//! aliases are unaffected by either mode.
+
+use sea_orm::{ConnectionTrait, DbErr, Statement};
+
+/// Digits, then upper, then lower case: ASCII order, so equal-length codes
+/// sort the same way as the numbers they encode.
+const ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShortCodeMode {
+    Random,
+    Sequential,
+}
+
+impl ShortCodeMode {
+    /// From SHORT_CODE_MODE; anything other than "sequential" is random.
+    pub fn from_env() -> Self {
+        match std::env::var("SHORT_CODE_MODE") {
+            Ok(v) if v.trim().eq_ignore_ascii_case("sequential") => Self::Sequential,
+            _ => Self::Random,
+        }
+    }
+}
+
+pub fn encode_base62(mut n: u64) -> String {
+    if n == 0 {
+        return "0".to_string();
+    }
+    let mut out = Vec::new();
+    while n > 0 {
+        out.push(ALPHABET[(n % 62) as usize]);
+        n /= 62;
+    }
+    out.reverse();
+    String::from_utf8(out).expect("base62 alphabet is ASCII")
+}
+
+/// Next code from `link_code_seq`, skipping values that spell a reserved word.
+pub async fn next_sequential_code<C: ConnectionTrait>(db: &C) -> Result<String, DbErr> {
+    loop {
+        let row = db
+            .query_one(Statement::from_string(
+                db.get_database_backend(),
+                "SELECT nextval('link_code_seq') AS n",
+            ))
+            .await?
+            .ok_or_else(|| DbErr::Custom("nextval returned no row".to_string()))?;
+        let n: i64 = row.try_get("", "n")?;
+        let code = encode_base62(n as u64);
+        if !crate::handlers::links::is_reserved_code(&code) {
+            return Ok(code);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_known_values() {
+        assert_eq!(encode_base62(0), "0");
+        assert_eq!(encode_base62(9), "9");
+        assert_eq!(encode_base62(10), "A");
+        assert_eq!(encode_base62(61), "z");
+        assert_eq!(encode_base62(62), "10");
+        assert_eq!(encode_base62(3843), "zz");
+    }
+
+    #[test]
+    fn encoding_is_monotonic() {
+        let mut prev = encode_base62(0);
+        for n in 1..20_000u64 {
+            let code = encode_base62(n);
+            assert!(
+                (code.len(), code.as_str()) > (prev.len(), prev.as_str()),
+                "{} ({}) should sort after {}",
+                code,
+                n,
+                prev
+            );
+            prev = code;
+        }
+    }
+
+    #[test]
+    fn length_grows_at_powers_of_62() {
+        assert_eq!(encode_base62(61).len(), 1);
+        assert_eq!(encode_base62(62).len(), 2);
+        assert_eq!(encode_base62(62 * 62 - 1).len(), 2);
+        assert_eq!(encode_base62(62 * 62).len(), 3);
+        assert_eq!(encode_base62(62u64.pow(6) - 1), "zzzzzz");
+        assert_eq!(encode_base62(62u64.pow(6)).len(), 7);
+        assert_eq!(encode_base62(u64::MAX).len(), 11);
+    }
+
+    #[test]
+    fn reserved_words_are_reachable_and_must_be_skipped() {
+        // "api" is a real base62 value, so the sequence will land on it.
+        let api = 36 * 62 * 62 + 51 * 62 + 44;
+        assert_eq!(encode_base62(api), "api");
+        assert!(crate::handlers::links::is_reserved_code("api"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Timestamps are stored and served in UTC. A caller can ask for them in a
+//! timezone instead — either one given on the request or the `timezone`
+//! saved on their profile (`?tz=profile`). IANA zones such as `Europe/Paris`
+//! follow daylight saving time; fixed UTC offsets are accepted too.
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+use crate::entity::users;
+
+/// Where timestamps are shown: an IANA zone or a fixed UTC offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Named(chrono_tz::Tz),
+    Offset(FixedOffset),
+}
+
+impl Zone {
+    /// Parse an IANA zone name or anything [`parse_utc_offset`] accepts.
+    pub fn parse(tz: &str) -> Option<Self> {
+        parse_utc_offset(tz)
+            .map(Self::Offset)
+            .or_else(|| tz.trim().parse().ok().map(Self::Named))
+    }
+
+    /// A stored UTC timestamp as local time, with the offset in effect then.
+    pub fn localize(self, ts: NaiveDateTime) -> DateTime<FixedOffset> {
+        let ts = ts.and_utc();
+        match self {
+            Self::Named(tz) => ts.with_timezone(&ts.with_timezone(&tz).offset().fix()),
+            Self::Offset(offset) => ts.with_timezone(&offset),
+        }
+    }
+}
+
+/// The zone name, or the offset as `±HH:MM`; what the profile stores.
+impl std::fmt::Display for Zone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Named(tz) => f.write_str(tz.name()),
+            Self::Offset(offset) => offset.fmt(f),
+        }
+    }
+}
+
+/// Parse `Z`/`UTC` or a `±HH`, `±HHMM` or `±HH:MM` offset, up to ±14 hours.
+pub fn parse_utc_offset(tz: &str) -> Option<FixedOffset> {
+    let tz = tz.trim();
+    if tz.eq_ignore_ascii_case("z") || tz.eq_ignore_ascii_case("utc") {
+        return FixedOffset::east_opt(0);
+    }
+    let (sign, rest) = match tz.as_bytes().first()? {
+        b'+' => (1, &tz[1..]),
+        b'-' => (-1, &tz[1..]),
+        _ => return None,
+    };
+    let digits = rest.replacen(':', "", 1);
+    if !(digits.len() == 2 || digits.len() == 4) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = if digits.len() == 4 {
+        digits[2..].parse().ok()?
+    } else {
+        0
+    };
+    if hours > 14 || minutes > 59 || (hours == 14 && minutes > 0) {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Resolve a `tz` query value: absent means UTC (`None`), `profile` means
+/// the user's saved timezone (UTC when they haven't set one), anything else
+/// must be a zone [`Zone::parse`] accepts.
+pub async fn resolve_tz(
+    db: &DatabaseConnection,
+    user_id: i32,
+    tz: Option<&str>,
+) -> Result<Option<Zone>, String> {
+    match tz {
+        None => Ok(None),
+        Some("profile") => Ok(users::Entity::find_by_id(user_id)
+            .one(db)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|user| user.timezone)
+            .and_then(|tz| Zone::parse(&tz))),
+        Some(tz) => Zone::parse(tz).map(Some).ok_or_else(|| {
+            "tz must be `profile`, a timezone such as Europe/Paris or a UTC offset such as +02:00"
+                .to_string()
+        }),
+    }
+}
+
+/// Render a stored UTC timestamp. Without a zone this is the plain UTC form
+/// every response already uses; with one the local time is followed by its
+/// offset, e.g. `2024-01-02 01:30:00 +02:00`.
+pub fn format_timestamp(ts: NaiveDateTime, zone: Option<Zone>) -> String {
+    match zone {
+        Some(zone) => zone.localize(ts).to_string(),
+        None => ts.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn utc_is_the_default_rendering() {
+        assert_eq!(
+            format_timestamp(ts("2024-01-01 23:30:00"), None),
+            "2024-01-01 23:30:00"
+        );
+    }
+
+    #[test]
+    fn converts_into_a_sample_offset() {
+        let ts = ts("2024-01-01 23:30:00");
+        assert_eq!(
+            format_timestamp(ts, Zone::parse("+02:00")),
+            "2024-01-02 01:30:00 +02:00"
+        );
+        assert_eq!(
+            format_timestamp(ts, Zone::parse("-0530")),
+            "2024-01-01 18:00:00 -05:30"
+        );
+    }
+
+    #[test]
+    fn named_zones_follow_daylight_saving_time() {
+        let paris = Zone::parse("Europe/Paris");
+        assert_eq!(
+            format_timestamp(ts("2024-01-15 12:00:00"), paris),
+            "2024-01-15 13:00:00 +01:00"
+        );
+        assert_eq!(
+            format_timestamp(ts("2024-07-15 12:00:00"), paris),
+            "2024-07-15 14:00:00 +02:00"
+        );
+    }
+
+    #[test]
+    fn parses_offsets_and_zone_names() {
+        assert_eq!(parse_utc_offset("UTC").unwrap().local_minus_utc(), 0);
+        assert_eq!(parse_utc_offset("+09").unwrap().local_minus_utc(), 9 * 3600);
+        assert!(parse_utc_offset("Europe/Paris").is_none());
+        assert!(parse_utc_offset("+14:30").is_none());
+        assert_eq!(
+            Zone::parse("Europe/Paris").unwrap().to_string(),
+            "Europe/Paris"
+        );
+        assert_eq!(Zone::parse("+0200").unwrap().to_string(), "+02:00");
+        assert!(Zone::parse("Mars/Olympus").is_none());
+    }
+}
@@ -90,6 +90,29 @@ pub fn normalize_domain_input(input: &str) -> Option<String> {
         .flatten()
 }
 
+/// Canonical form of a destination URL, for storing and for spotting two
+/// links that point at the same place. Parsing already lowercases the scheme
+/// and host, drops a default port (`:443` on https, `:80` on http) and turns an
+/// empty path into `/`; on top of that a trailing dot on the host and an empty
+/// `?` or `#` are removed.
+///
+/// Only the root path gets a trailing slash: `/docs` and `/docs/` are left
+/// alone because servers are free to answer them differently.
+pub fn normalize_destination_url(url: &str) -> Option<String> {
+    let mut parsed = url::Url::parse(url.trim()).ok()?;
+    if let Some(host) = parsed.host_str().filter(|h| h.ends_with('.')) {
+        let host = normalize_hostname(host)?;
+        parsed.set_host(Some(&host)).ok()?;
+    }
+    if parsed.query() == Some("") {
+        parsed.set_query(None);
+    }
+    if parsed.fragment() == Some("") {
+        parsed.set_fragment(None);
+    }
+    Some(parsed.to_string())
+}
+
 pub fn domain_matches(blocked_domain: &str, host: &str) -> bool {
     let Some(blocked) = normalize_hostname(blocked_domain) else {
         return false;
@@ -248,4 +271,41 @@ mod tests {
     fn accepts_normal_https() {
         assert!(validate_http_https_url("https://iana.org/me").is_ok());
     }
+
+    #[test]
+    fn normalizes_host_port_and_root_path() {
+        for input in [
+            "https://iana.org",
+            "https://iana.org/",
+            "HTTPS://IANA.ORG",
+            "https://iana.org:443/",
+            "https://iana.org./",
+            "https://iana.org/?",
+            "https://iana.org/#",
+        ] {
+            assert_eq!(
+                normalize_destination_url(input).as_deref(),
+                Some("https://iana.org/"),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            normalize_destination_url("http://iana.org:80/a").as_deref(),
+            Some("http://iana.org/a")
+        );
+    }
+
+    #[test]
+    fn normalization_keeps_meaningful_parts() {
+        // Non-default ports, path case, query and fragment all matter.
+        assert_eq!(
+            normalize_destination_url("https://iana.org:8443/Docs?q=1#top").as_deref(),
+            Some("https://iana.org:8443/Docs?q=1#top")
+        );
+        assert_ne!(
+            normalize_destination_url("https://iana.org/docs"),
+            normalize_destination_url("https://iana.org/docs/")
+        );
+        assert_eq!(normalize_destination_url("not a url"), None);
+    }
 }
@@ -0,0 +1,132 @@
+//! Turns `validator` failures into a body forms can use: the usual `error`
+//! summary plus the messages for each offending field.
+//!
+//! ```json
+//! { "error": "email: Must be a valid email address",
+//!   "fields": { "email": ["Must be a valid email address"] } }
+//! ```
+
+use std::collections::BTreeMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+use validator::{ValidationError, ValidationErrors};
+
+/// 400 body for a request that failed validation.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    /// Every field message in one line, for clients that only show `error`.
+    pub error: String,
+    /// Messages per request field.
+    pub fields: BTreeMap<String, Vec<String>>,
+}
+
+impl ValidationErrorResponse {
+    /// A single failing field, for checks done outside `validate()`. `error`
+    /// stays the bare message, as it was before these had a field map.
+    pub fn field(field: &str, message: impl Into<String>) -> Self {
+        let error = message.into();
+        let mut fields = BTreeMap::new();
+        fields.insert(field.to_string(), vec![error.clone()]);
+        Self { error, fields }
+    }
+
+    fn from_fields(fields: BTreeMap<String, Vec<String>>) -> Self {
+        let error = fields
+            .iter()
+            .flat_map(|(field, messages)| messages.iter().map(move |m| format!("{field}: {m}")))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Self { error, fields }
+    }
+}
+
+impl From<&ValidationErrors> for ValidationErrorResponse {
+    fn from(errors: &ValidationErrors) -> Self {
+        Self::from_fields(field_errors(errors))
+    }
+}
+
+impl IntoResponse for ValidationErrorResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, Json(self)).into_response()
+    }
+}
+
+/// Messages per field. A rule's own `message` wins; otherwise one is built
+/// from its code and parameters.
+pub fn field_errors(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    errors
+        .field_errors()
+        .into_iter()
+        .map(|(field, errs)| (field.to_string(), errs.iter().map(message).collect()))
+        .collect()
+}
+
+fn message(err: &ValidationError) -> String {
+    if let Some(message) = &err.message {
+        return message.to_string();
+    }
+    let param = |name: &str| err.params.get(name).map(|v| v.to_string());
+    match err.code.as_ref() {
+        "email" => "Must be a valid email address".to_string(),
+        "length" => match (param("min"), param("max")) {
+            (Some(min), Some(max)) => format!("Must be between {min} and {max} characters"),
+            (Some(min), None) => format!("Must be at least {min} characters"),
+            (None, Some(max)) => format!("Must be at most {max} characters"),
+            (None, None) => "Has an invalid length".to_string(),
+        },
+        "url" => "Must be a valid URL".to_string(),
+        _ => "Is invalid".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct Form {
+        #[validate(email)]
+        email: String,
+        #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+        password: String,
+        #[validate(length(min = 1, max = 5))]
+        name: String,
+    }
+
+    #[test]
+    fn groups_messages_by_field() {
+        let form = Form {
+            email: "nope".into(),
+            password: "short".into(),
+            name: "much too long".into(),
+        };
+        let body = ValidationErrorResponse::from(&form.validate().unwrap_err());
+        assert_eq!(body.fields["email"], ["Must be a valid email address"]);
+        assert_eq!(
+            body.fields["password"],
+            ["Password must be at least 8 characters"]
+        );
+        assert_eq!(body.fields["name"], ["Must be between 1 and 5 characters"]);
+        assert_eq!(
+            body.error,
+            "email: Must be a valid email address; \
+             name: Must be between 1 and 5 characters; \
+             password: Password must be at least 8 characters"
+        );
+    }
+
+    #[test]
+    fn single_field_error() {
+        let body = ValidationErrorResponse::field("original_url", "Invalid URL format");
+        assert_eq!(body.error, "Invalid URL format");
+        assert_eq!(body.fields["original_url"], ["Invalid URL format"]);
+    }
+}
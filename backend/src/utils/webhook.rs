@@ -0,0 +1,458 @@
+//! Outgoing webhooks: event fan-out, payload signing, and delivery.
+//!
+//! Each delivery is a `POST` of a JSON envelope
+//! `{"event": "...", "timestamp": "<RFC 3339>", "data": {...}}` with headers:
+//! - `X-Opn-Event`: the event name
+//! - `X-Opn-Timestamp`: unix seconds at send time
+//! - `X-Opn-Signature`: `sha256=<hex HMAC-SHA256(secret, "{timestamp}.{body}")>`
+//!
+//! The same events also fan out to chat integrations (see
+//! [`crate::utils::integrations`]), which receive a platform-formatted message
+//! instead of the signed envelope.
+//!
+//! Every webhook attempt is logged in `webhook_deliveries` (status code, the
+//! start of the response body, and the exact payload sent) so failures can be
+//! inspected and replayed with [`redeliver`].
+//!
+//! Delivery goes through the same SSRF guard as other server-side fetches: the
+//! target is resolved, rejected if internal, and the connection is pinned to
+//! the validated addresses. Redirects are not followed.
+
+use hmac::{Hmac, Mac};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QuerySelect, Set, Statement,
+};
+use sha2::Sha256;
+
+use crate::entity::{integrations, users, webhook_deliveries, webhooks};
+use crate::handlers::links::{build_pinned_client, resolve_and_validate};
+use crate::utils::integrations::{format_message, Platform};
+
+pub const EVENT_USER_REGISTERED: &str = "user.registered";
+pub const EVENT_LINK_CREATED: &str = "link.created";
+pub const EVENT_LINK_MILESTONE: &str = "link.milestone";
+
+/// Every event a webhook may subscribe to.
+pub const SUPPORTED_EVENTS: &[&str] = &[
+    EVENT_USER_REGISTERED,
+    EVENT_LINK_CREATED,
+    EVENT_LINK_MILESTONE,
+];
+
+/// Delivery log rows kept per webhook; older attempts are pruned.
+pub const MAX_DELIVERIES_PER_WEBHOOK: i64 = 100;
+
+/// Bytes of the endpoint's response body kept in the delivery log.
+const RESPONSE_SNIPPET_BYTES: usize = 512;
+
+/// Click totals that fire `link.milestone` when a link reaches them.
+pub const CLICK_MILESTONES: &[i32] = &[100, 1_000, 10_000];
+
+/// Who receives an event.
+#[derive(Clone, Copy, Debug)]
+pub enum Audience {
+    /// A link's owner and, for org links, the org: the owner's webhooks and
+    /// personal integrations plus the org's integrations.
+    Link {
+        user_id: Option<i32>,
+        org_id: Option<i32>,
+    },
+    /// Webhooks owned by any active instance admin (ops-level events).
+    Admins,
+}
+
+/// Milestones crossed when a link's click_count moves from `before` to `after`
+/// in one step. A single flush can apply many clicks at once, so this reports
+/// every threshold in `(before, after]` rather than checking for equality.
+pub fn crossed_milestones(before: i32, after: i32) -> Vec<i32> {
+    CLICK_MILESTONES
+        .iter()
+        .copied()
+        .filter(|&m| before < m && after >= m)
+        .collect()
+}
+
+/// HMAC-SHA256 keyed with the webhook secret over `"{timestamp}.{body}"`.
+fn signing_mac(secret: &str, timestamp: i64, body: &str) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    mac
+}
+
+/// `sha256=<hex>` signature for a payload sent at `timestamp`.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let digest = signing_mac(secret, timestamp, body).finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+/// Check an `X-Opn-Signature` header against a received delivery.
+///
+/// This is the receiving half of [`sign`], kept public so integrators can
+/// verify deliveries with the exact scheme the server uses: the header is
+/// `sha256=` followed by the hex HMAC-SHA256, keyed with the webhook secret,
+/// of the `X-Opn-Timestamp` value, a `.`, and the raw request body (verify the
+/// bytes as received, before any JSON re-encoding). The digest comparison is
+/// constant-time. Callers should also reject timestamps too far from their
+/// own clock to stop replays.
+pub fn verify_signature(secret: &str, timestamp: i64, body: &str, header: &str) -> bool {
+    let Some(hex) = header.trim().strip_prefix("sha256=") else {
+        return false;
+    };
+    let Some(expected) = decode_hex(hex) else {
+        return false;
+    };
+    signing_mac(secret, timestamp, body)
+        .verify_slice(&expected)
+        .is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Fire `event` to every matching webhook in the background. Never blocks or
+/// fails the caller; delivery errors are logged.
+pub fn dispatch(
+    db: DatabaseConnection,
+    audience: Audience,
+    event: &'static str,
+    data: serde_json::Value,
+) {
+    tokio::spawn(async move {
+        let hooks = subscribers(&db, audience, event).await;
+        if !hooks.is_empty() {
+            let body = serde_json::json!({
+                "event": event,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "data": data,
+            })
+            .to_string();
+            for hook in hooks {
+                match deliver_and_record(&db, &hook, event, &body).await {
+                    Ok(d) if d.success => {}
+                    Ok(d) => tracing::warn!(
+                        "Webhook {} delivery of {} failed: {}",
+                        hook.id,
+                        event,
+                        d.error.unwrap_or_default()
+                    ),
+                    Err(e) => tracing::error!(
+                        "Webhook {} delivery of {} not recorded: {}",
+                        hook.id,
+                        event,
+                        e
+                    ),
+                }
+            }
+        }
+
+        for integration in integration_subscribers(&db, audience, event).await {
+            let Some(platform) = Platform::parse(&integration.platform) else {
+                continue;
+            };
+            let Some(message) = format_message(platform, event, &data) else {
+                continue;
+            };
+            if let Err(e) = post_json(&integration.webhook_url, &[], &message.to_string()).await {
+                tracing::warn!(
+                    "Integration {} ({}) delivery of {} failed: {}",
+                    integration.id,
+                    platform.as_str(),
+                    event,
+                    e
+                );
+            }
+        }
+    });
+}
+
+async fn subscribers(
+    db: &DatabaseConnection,
+    audience: Audience,
+    event: &str,
+) -> Vec<webhooks::Model> {
+    let owners: Vec<i32> = match audience {
+        Audience::Link { user_id, .. } => user_id.into_iter().collect(),
+        Audience::Admins => users::Entity::find()
+            .select_only()
+            .column(users::Column::Id)
+            .filter(users::Column::IsAdmin.eq(true))
+            .filter(users::Column::DeletedAt.is_null())
+            .filter(users::Column::DisabledAt.is_null())
+            .into_tuple()
+            .all(db)
+            .await
+            .unwrap_or_default(),
+    };
+    if owners.is_empty() {
+        return Vec::new();
+    }
+    webhooks::Entity::find()
+        .filter(webhooks::Column::UserId.is_in(owners))
+        .filter(webhooks::Column::IsActive.eq(true))
+        .all(db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|h| h.subscribes_to(event))
+        .collect()
+}
+
+/// Integrations reached by a link event: the owner's personal ones and, for
+/// org links, the org's. Ops-level events never go to chat integrations.
+async fn integration_subscribers(
+    db: &DatabaseConnection,
+    audience: Audience,
+    event: &str,
+) -> Vec<integrations::Model> {
+    let Audience::Link { user_id, org_id } = audience else {
+        return Vec::new();
+    };
+    if user_id.is_none() && org_id.is_none() {
+        return Vec::new();
+    }
+    let mut scope = sea_orm::Condition::any();
+    if let Some(user_id) = user_id {
+        scope = scope.add(
+            sea_orm::Condition::all()
+                .add(integrations::Column::UserId.eq(user_id))
+                .add(integrations::Column::OrgId.is_null()),
+        );
+    }
+    if let Some(org_id) = org_id {
+        scope = scope.add(integrations::Column::OrgId.eq(org_id));
+    }
+    integrations::Entity::find()
+        .filter(scope)
+        .filter(integrations::Column::IsActive.eq(true))
+        .all(db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|i| i.subscribes_to(event))
+        .collect()
+}
+
+/// POST a signed payload to one webhook. Returns the response status code and
+/// the start of the response body; non-2xx responses are not errors here.
+pub async fn deliver(
+    hook: &webhooks::Model,
+    event: &str,
+    body: &str,
+) -> Result<(u16, String), String> {
+    let timestamp = chrono::Utc::now().timestamp();
+    let signature = sign(&hook.secret, timestamp, body);
+    send_json(
+        &hook.url,
+        &[
+            ("X-Opn-Event", event.to_string()),
+            ("X-Opn-Timestamp", timestamp.to_string()),
+            ("X-Opn-Signature", signature),
+        ],
+        body,
+    )
+    .await
+}
+
+/// Deliver `body` to `hook` and log the attempt in `webhook_deliveries`.
+pub async fn deliver_and_record(
+    db: &DatabaseConnection,
+    hook: &webhooks::Model,
+    event: &str,
+    body: &str,
+) -> Result<webhook_deliveries::Model, DbErr> {
+    let (status_code, response_snippet, error) = match deliver(hook, event, body).await {
+        Ok((status, snippet)) => {
+            let error =
+                (!(200..300).contains(&status)).then(|| format!("endpoint responded {}", status));
+            (Some(status as i32), Some(snippet), error)
+        }
+        Err(e) => (None, None, Some(e)),
+    };
+    let delivery = webhook_deliveries::ActiveModel {
+        webhook_id: Set(hook.id),
+        event: Set(event.to_string()),
+        payload: Set(body.to_string()),
+        status_code: Set(status_code),
+        response_snippet: Set(response_snippet),
+        success: Set(error.is_none()),
+        error: Set(error),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    db.execute(Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Postgres,
+        "DELETE FROM webhook_deliveries WHERE webhook_id = $1 AND id NOT IN \
+         (SELECT id FROM webhook_deliveries WHERE webhook_id = $1 ORDER BY id DESC LIMIT $2)",
+        [hook.id.into(), MAX_DELIVERIES_PER_WEBHOOK.into()],
+    ))
+    .await?;
+
+    Ok(delivery)
+}
+
+/// Replay a logged delivery: the same payload, freshly timestamped and signed,
+/// recorded as a new attempt.
+pub async fn redeliver(
+    db: &DatabaseConnection,
+    hook: &webhooks::Model,
+    delivery: &webhook_deliveries::Model,
+) -> Result<webhook_deliveries::Model, DbErr> {
+    deliver_and_record(db, hook, &delivery.event, &delivery.payload).await
+}
+
+/// SSRF-guarded JSON POST. Non-2xx responses are errors.
+async fn post_json(url: &str, headers: &[(&str, String)], body: &str) -> Result<u16, String> {
+    let (status, _) = send_json(url, headers, body).await?;
+    if (200..300).contains(&status) {
+        Ok(status)
+    } else {
+        Err(format!("endpoint responded {}", status))
+    }
+}
+
+/// SSRF-guarded JSON POST returning the status and up to
+/// [`RESPONSE_SNIPPET_BYTES`] of the response body. Only transport failures
+/// are errors.
+async fn send_json(
+    url: &str,
+    headers: &[(&str, String)],
+    body: &str,
+) -> Result<(u16, String), String> {
+    let target = resolve_and_validate(url).await?;
+    let client = build_pinned_client(&target, Some("opn.onl-webhooks/1.0"))?;
+    let mut request = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json");
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+    let mut resp = request
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = resp.status().as_u16();
+
+    let mut snippet = Vec::new();
+    while snippet.len() < RESPONSE_SNIPPET_BYTES {
+        match resp.chunk().await {
+            Ok(Some(chunk)) => snippet.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+    snippet.truncate(RESPONSE_SNIPPET_BYTES);
+    Ok((status, String::from_utf8_lossy(&snippet).into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_crossing_one_threshold() {
+        assert_eq!(crossed_milestones(95, 105), vec![100]);
+        assert_eq!(crossed_milestones(999, 1_000), vec![1_000]);
+    }
+
+    #[test]
+    fn batch_landing_exactly_on_threshold() {
+        assert_eq!(crossed_milestones(90, 100), vec![100]);
+        // Already at the threshold before the batch: not crossed again.
+        assert!(crossed_milestones(100, 150).is_empty());
+    }
+
+    #[test]
+    fn large_batch_crossing_several_thresholds() {
+        assert_eq!(crossed_milestones(99, 10_001), vec![100, 1_000, 10_000]);
+        assert_eq!(crossed_milestones(0, 1_500), vec![100, 1_000]);
+    }
+
+    #[test]
+    fn batch_between_thresholds() {
+        assert!(crossed_milestones(0, 99).is_empty());
+        assert!(crossed_milestones(101, 999).is_empty());
+        assert!(crossed_milestones(10_000, 50_000).is_empty());
+    }
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let sig = sign("secret", 1_700_000_000, r#"{"a":1}"#);
+        assert!(sig.starts_with("sha256="));
+        assert_eq!(sig.len(), "sha256=".len() + 64);
+        assert_ne!(sig, sign("secret", 1_700_000_001, r#"{"a":1}"#));
+        assert_ne!(sig, sign("secret", 1_700_000_000, r#"{"a":2}"#));
+        assert_ne!(sig, sign("other", 1_700_000_000, r#"{"a":1}"#));
+    }
+
+    #[test]
+    fn verifies_known_vectors() {
+        let body = r#"{"event":"link.created"}"#;
+        let header = "sha256=157c90f250cb20ef0f8f798ef6b985d7bf78bcf43128ad5325212589883c33e8";
+        assert_eq!(sign("whsec_test", 1_700_000_000, body), header);
+        assert!(verify_signature("whsec_test", 1_700_000_000, body, header));
+        assert!(verify_signature(
+            "whsec_test",
+            1_700_000_000,
+            body,
+            &header.to_ascii_uppercase().replace("SHA256=", "sha256=")
+        ));
+        assert!(verify_signature(
+            "secret",
+            0,
+            "",
+            "sha256=3445798a051818ef95def46c2eb62b43d377ce6e3c29b4d0aec3da0e59577f79"
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_or_malformed_signatures() {
+        let body = r#"{"event":"link.created"}"#;
+        let header = sign("whsec_test", 1_700_000_000, body);
+        assert!(!verify_signature(
+            "whsec_other",
+            1_700_000_000,
+            body,
+            &header
+        ));
+        assert!(!verify_signature(
+            "whsec_test",
+            1_700_000_001,
+            body,
+            &header
+        ));
+        assert!(!verify_signature(
+            "whsec_test",
+            1_700_000_000,
+            "{}",
+            &header
+        ));
+        for bad in [
+            "",
+            "sha256=",
+            &header["sha256=".len()..],
+            &header[..header.len() - 2],
+            "sha256=zz7c90f250cb20ef0f8f798ef6b985d7bf78bcf43128ad5325212589883c33e8",
+            "sha1=157c90f250cb20ef0f8f798ef6b985d7bf78bcf43128ad5325212589883c33e8",
+        ] {
+            assert!(
+                !verify_signature("whsec_test", 1_700_000_000, body, bad),
+                "{bad}"
+            );
+        }
+    }
+}
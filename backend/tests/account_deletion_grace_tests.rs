@@ -0,0 +1,131 @@
+//! ACCOUNT_DELETION_GRACE_DAYS: self-deletion only schedules the account and
+//! suspends its sessions and API keys, signing in during the grace period
+//! cancels it, and the sweep deletes accounts whose date has passed. Lives in
+//! its own test binary because it sets process-wide env vars.
+
+mod common;
+
+use chrono::{Duration, Utc};
+use opn_onl_backend::entity::{links, users};
+use opn_onl_backend::utils::account_deletion::finalize_due_deletions;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DatabaseConnection, EntityTrait};
+use serde_json::{json, Value};
+
+async fn load(db: &DatabaseConnection, user_id: i32) -> users::Model {
+    users::Entity::find_by_id(user_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn login_during_grace_period_cancels_account_deletion() {
+    std::env::set_var("ENABLE_ACCOUNT_DELETION", "true");
+    std::env::set_var("ACCOUNT_DELETION_GRACE_DAYS", "14");
+    let (server, db) = common::spawn_real_app().await;
+
+    let email = common::unique_email();
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": email, "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    let user_id = body["user_id"].as_i64().unwrap() as i32;
+    common::mark_email_verified(&db, user_id).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/grace" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let link_id = res.json::<Value>()["id"].as_i64().unwrap() as i32;
+    let res = server
+        .post("/auth/api-keys")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "grace" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "api key: {}", res.text());
+    let api_key = res.json::<Value>()["key"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/auth/delete-account")
+        .authorization_bearer(&token)
+        .json(&json!({ "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "delete: {}", res.text());
+    assert!(res.json::<Value>()["message"]
+        .as_str()
+        .unwrap()
+        .contains("scheduled for deletion"));
+
+    // Scheduled, not deleted; existing sessions are signed out.
+    let user = load(&db, user_id).await;
+    assert!(user.deleted_at.is_none());
+    let scheduled = user.deletion_scheduled_at.expect("deletion is scheduled");
+    assert!(scheduled > Utc::now().naive_utc() + Duration::days(13));
+    let res = server.get("/auth/me").authorization_bearer(&token).await;
+    assert_eq!(res.status_code(), 401);
+    // API keys are suspended too.
+    let res = server.get("/links").authorization_bearer(&api_key).await;
+    assert_eq!(res.status_code(), 401, "{}", res.text());
+
+    // Signing in keeps the account.
+    let res = server
+        .post("/auth/login")
+        .json(&json!({ "email": email, "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "login: {}", res.text());
+    let body: Value = res.json();
+    assert_eq!(body["deletion_cancelled"], true);
+    let token = body["token"].as_str().unwrap().to_string();
+    assert!(load(&db, user_id).await.deletion_scheduled_at.is_none());
+    let res = server.get("/links").authorization_bearer(&api_key).await;
+    assert_eq!(res.status_code(), 200, "key works again: {}", res.text());
+
+    // Nothing left for the sweep to delete.
+    finalize_due_deletions(&db, scheduled + Duration::hours(1))
+        .await
+        .unwrap();
+    assert!(load(&db, user_id).await.deleted_at.is_none());
+
+    let res = server
+        .post("/auth/login")
+        .json(&json!({ "email": email, "password": "password123" }))
+        .await;
+    assert_eq!(res.json::<Value>()["deletion_cancelled"], false);
+
+    // Once the grace period passes without a sign-in, the sweep deletes it.
+    let res = server
+        .post("/auth/delete-account")
+        .authorization_bearer(&token)
+        .json(&json!({ "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "delete again: {}", res.text());
+    let mut active: users::ActiveModel = load(&db, user_id).await.into();
+    active.deletion_scheduled_at = Set(Some(Utc::now().naive_utc() - Duration::minutes(1)));
+    active.update(&db).await.unwrap();
+
+    finalize_due_deletions(&db, Utc::now().naive_utc())
+        .await
+        .unwrap();
+    let user = load(&db, user_id).await;
+    assert!(user.deleted_at.is_some());
+    assert!(user.deletion_scheduled_at.is_none());
+    let link = links::Entity::find_by_id(link_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(link.deleted_at.is_some());
+
+    let res = server
+        .post("/auth/login")
+        .json(&json!({ "email": email, "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 401);
+
+    std::env::remove_var("ACCOUNT_DELETION_GRACE_DAYS");
+}
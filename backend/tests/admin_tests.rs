@@ -230,6 +230,85 @@ async fn admin_can_delete_and_restore_any_link() {
     );
 }
 
+#[tokio::test]
+async fn admin_can_disable_and_enable_a_link() {
+    use opn_onl_backend::entity::audit_log;
+    use sea_orm::{ColumnTrait, QueryFilter, QueryOrder};
+
+    let (server, db) = spawn_real_app().await;
+    let (admin_token, admin_id) = register_admin(&server, &db).await;
+    let (user_token, _, _) = register_verified(&server, &db).await;
+
+    let (link_id, code) = create_link(&server, &user_token, "https://iana.org/abuse").await;
+
+    let res = server
+        .post(&format!("/admin/links/{link_id}/disable"))
+        .authorization_bearer(&user_token)
+        .await;
+    assert_eq!(
+        res.status_code(),
+        403,
+        "owners can't use the admin takedown"
+    );
+
+    let res = server
+        .post(&format!("/admin/links/{link_id}/disable"))
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+
+    let res = server.get(&format!("/{code}")).await;
+    assert_eq!(res.status_code(), 410);
+    assert!(res.text().contains("policies"), "{}", res.text());
+
+    // Not a delete: the owner still has the link, flagged with the reason.
+    let owned: Vec<Value> = server
+        .get("/links")
+        .authorization_bearer(&user_token)
+        .await
+        .json();
+    let body = owned
+        .iter()
+        .find(|l| l["id"] == link_id)
+        .expect("owner still lists the link");
+    assert_eq!(body["is_active"], false);
+    assert!(body["inactive_reason"]
+        .as_str()
+        .is_some_and(|r| r.contains("policies")));
+
+    let res = server
+        .post(&format!("/admin/links/{link_id}/disable"))
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 400);
+
+    let res = server
+        .post(&format!("/admin/links/{link_id}/enable"))
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+
+    let res = server.get(&format!("/{code}")).await;
+    assert!(
+        res.status_code().is_redirection(),
+        "re-enabled link must redirect again, got {}",
+        res.status_code()
+    );
+
+    let actions: Vec<String> = audit_log::Entity::find()
+        .filter(audit_log::Column::ResourceType.eq("link"))
+        .filter(audit_log::Column::ResourceId.eq(link_id as i32))
+        .filter(audit_log::Column::UserId.eq(admin_id))
+        .order_by_asc(audit_log::Column::Id)
+        .all(&db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.action)
+        .collect();
+    assert_eq!(actions, ["link_disabled", "link_enabled"]);
+}
+
 #[tokio::test]
 async fn admin_users_list_is_paginated_with_aggregates() {
     let (server, db) = spawn_real_app().await;
@@ -423,3 +502,310 @@ async fn admin_orgs_list_shows_owner_and_member_count() {
     assert_eq!(org["owner_email"].as_str(), Some(user_email.as_str()));
     assert!(org["member_count"].as_i64().unwrap() >= 1);
 }
+
+#[tokio::test]
+async fn impersonation_token_acts_as_target_and_is_audited() {
+    use opn_onl_backend::entity::audit_log;
+    use sea_orm::{ColumnTrait, QueryFilter};
+
+    let (server, db) = spawn_real_app().await;
+    let (admin_token, admin_id) = register_admin(&server, &db).await;
+    let (user_token, user_id, email) = register_verified(&server, &db).await;
+
+    // Regular users can't impersonate.
+    let res = server
+        .post(&format!("/admin/users/{admin_id}/impersonate"))
+        .authorization_bearer(&user_token)
+        .await;
+    assert_eq!(res.status_code(), 403);
+
+    let res = server
+        .post(&format!("/admin/users/{user_id}/impersonate"))
+        .authorization_bearer(&admin_token)
+        .json(&json!({ "reason": "ticket 42" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let body: Value = res.json();
+    assert_eq!(body["user_id"], user_id);
+    assert_eq!(body["impersonated_by"], admin_id);
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let claims = opn_onl_backend::utils::decode_jwt(&token).unwrap();
+    assert_eq!(claims.user_id, user_id);
+    assert_eq!(claims.impersonated_by, Some(admin_id));
+
+    let me: Value = server
+        .get("/auth/me")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(me["id"], user_id);
+    assert_eq!(me["email"], email.as_str());
+    assert_eq!(me["impersonated_by"], admin_id);
+    // Ordinary sessions don't carry the marker.
+    let me: Value = server
+        .get("/auth/me")
+        .authorization_bearer(&user_token)
+        .await
+        .json();
+    assert!(me.get("impersonated_by").is_none());
+
+    // The session acts as the user: links are created under their account.
+    let (link_id, _) = create_link(&server, &token, "https://iana.org/impersonated").await;
+    let link = opn_onl_backend::entity::links::Entity::find_by_id(link_id as i32)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(link.user_id, Some(user_id));
+
+    // ...but never as an admin.
+    let res = server
+        .get("/admin/users")
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.status_code(), 403);
+
+    let entry = audit_log::Entity::find()
+        .filter(audit_log::Column::Action.eq("impersonate"))
+        .filter(audit_log::Column::ResourceId.eq(user_id))
+        .one(&db)
+        .await
+        .unwrap()
+        .expect("impersonation audited");
+    assert_eq!(entry.user_id, Some(admin_id));
+    assert_eq!(entry.details.unwrap()["reason"], "ticket 42");
+}
+
+#[tokio::test]
+async fn impersonation_token_cannot_mint_lasting_credentials() {
+    let (server, db) = spawn_real_app().await;
+    let (admin_token, _) = register_admin(&server, &db).await;
+    let (user_token, user_id, _) = register_verified(&server, &db).await;
+
+    let res = server
+        .post(&format!("/admin/users/{user_id}/impersonate"))
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let token = res.json::<Value>()["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/auth/api-keys")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "left behind" }))
+        .await;
+    assert_eq!(res.status_code(), 403, "{}", res.text());
+    let res = server
+        .post("/auth/change-password")
+        .authorization_bearer(&token)
+        .json(&json!({ "current_password": "password123", "new_password": "password456" }))
+        .await;
+    assert_eq!(res.status_code(), 403, "{}", res.text());
+
+    // The user's own session is unaffected.
+    let res = server
+        .post("/auth/api-keys")
+        .authorization_bearer(&user_token)
+        .json(&json!({ "name": "mine" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "{}", res.text());
+}
+
+#[tokio::test]
+async fn impersonating_an_admin_requires_confirmation() {
+    let (server, db) = spawn_real_app().await;
+    let (admin_token, admin_id) = register_admin(&server, &db).await;
+    let (_, other_admin_id) = register_admin(&server, &db).await;
+
+    let url = format!("/admin/users/{other_admin_id}/impersonate");
+    let res = server.post(&url).authorization_bearer(&admin_token).await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+
+    let res = server
+        .post(&url)
+        .authorization_bearer(&admin_token)
+        .json(&json!({ "confirm_admin": true }))
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+
+    let res = server
+        .post(&format!("/admin/users/{admin_id}/impersonate"))
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 400, "self-impersonation");
+}
+
+#[tokio::test]
+async fn admin_search_matches_user_email_and_link_code() {
+    let (server, db) = spawn_real_app().await;
+    let (admin_token, _) = register_admin(&server, &db).await;
+    let needle = common::unique_code().to_lowercase();
+    let (_, user_id) = register(&server, &format!("{needle}-{}", unique_email())).await;
+    let (owner_token, _, _) = register_verified(&server, &db).await;
+    let mut link_ids = Vec::new();
+    for suffix in ["a", "b"] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&owner_token)
+            .json(&json!({ "original_url": "https://iana.org/search", "custom_alias": format!("{needle}{suffix}") }))
+            .await;
+        assert_eq!(res.status_code(), 201, "create link failed: {}", res.text());
+        link_ids.push(res.json::<Value>()["id"].as_i64().unwrap());
+    }
+
+    let res = server
+        .get("/admin/search")
+        .add_query_param("q", needle.to_uppercase())
+        .add_query_param("per_page", 1)
+        .add_query_param("links_page", 2)
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let body: Value = res.json();
+    assert_eq!(body["users"]["total"], 1);
+    assert_eq!(body["users"]["users"][0]["id"], user_id);
+    assert_eq!(body["links"]["total"], 2);
+    assert_eq!(body["links"]["page"], 2);
+    let page: Vec<i64> = body["links"]["links"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["id"].as_i64().unwrap())
+        .collect();
+    // Newest first, so the second page holds the first link.
+    assert_eq!(page, [link_ids[0]]);
+
+    let res = server
+        .get("/admin/search?q=%20")
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+    let res = server
+        .get(&format!("/admin/search?q={needle}"))
+        .authorization_bearer(&owner_token)
+        .await;
+    assert_eq!(res.status_code(), 403, "{}", res.text());
+}
+
+#[tokio::test]
+async fn admin_top_links_rank_by_clicks_and_honor_limit() {
+    use opn_onl_backend::entity::{click_events, links};
+
+    use sea_orm::{ColumnTrait, QueryFilter};
+
+    let (server, db) = spawn_real_app().await;
+    let (admin_token, _) = register_admin(&server, &db).await;
+    let (user_token, _, user_email) = register_verified(&server, &db).await;
+
+    // The database outlives test runs: retire what earlier runs of this test
+    // left in the ranges it ranks below.
+    links::Entity::update_many()
+        .col_expr(
+            links::Column::ClickCount,
+            sea_orm::sea_query::Expr::value(0),
+        )
+        .filter(links::Column::ClickCount.gte(2_000_000_000))
+        .exec(&db)
+        .await
+        .unwrap();
+    click_events::Entity::delete_many()
+        .filter(
+            click_events::Column::CreatedAt
+                .gt((chrono::Utc::now() + chrono::Duration::days(3000)).naive_utc()),
+        )
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let mut ids = Vec::new();
+    for i in 0..3 {
+        let (id, _) = create_link(&server, &user_token, &format!("https://iana.org/top-{i}")).await;
+        ids.push(id as i32);
+    }
+
+    // Lifetime ranking. The database is shared, so the counts sit far above
+    // anything other tests produce.
+    for (id, clicks) in ids
+        .iter()
+        .zip([2_000_000_001, 2_000_000_003, 2_000_000_002])
+    {
+        let link = links::Entity::find_by_id(*id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        let mut active: links::ActiveModel = link.into();
+        active.click_count = Set(clicks);
+        active.update(&db).await.unwrap();
+    }
+
+    let res = server
+        .get("/admin/links/top")
+        .add_query_param("limit", "2")
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let body: Value = res.json();
+    let top = body["links"].as_array().unwrap();
+    assert_eq!(top.len(), 2, "limit applies");
+    assert_eq!(top[0]["id"].as_i64(), Some(ids[1] as i64));
+    assert_eq!(top[0]["clicks"].as_i64(), Some(2_000_000_003));
+    assert_eq!(top[0]["owner_email"].as_str(), Some(user_email.as_str()));
+    assert_eq!(top[0]["destination_host"].as_str(), Some("iana.org"));
+    assert_eq!(top[1]["id"].as_i64(), Some(ids[2] as i64));
+
+    // Windowed ranking counts click events from `since` on. Events dated far
+    // ahead keep other tests' clicks out of the window.
+    let since = chrono::Utc::now() + chrono::Duration::days(3650);
+    for (id, sample_rates) in ids.iter().zip([&[1, 1, 1][..], &[1][..], &[5][..]]) {
+        for sample_rate in sample_rates {
+            click_events::ActiveModel {
+                link_id: Set(*id),
+                created_at: Set((since + chrono::Duration::hours(1)).naive_utc()),
+                sample_rate: Set(*sample_rate),
+                ..Default::default()
+            }
+            .insert(&db)
+            .await
+            .unwrap();
+        }
+    }
+    // Before the window: not counted.
+    click_events::ActiveModel {
+        link_id: Set(ids[1]),
+        created_at: Set((since - chrono::Duration::hours(1)).naive_utc()),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    let res = server
+        .get("/admin/links/top")
+        .add_query_param(
+            "since",
+            since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        )
+        .authorization_bearer(&admin_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let body: Value = res.json();
+    let ranked: Vec<(i64, i64)> = body["links"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| (l["id"].as_i64().unwrap(), l["clicks"].as_i64().unwrap()))
+        .collect();
+    assert_eq!(
+        ranked,
+        vec![(ids[2] as i64, 5), (ids[0] as i64, 3), (ids[1] as i64, 1)]
+    );
+
+    let (user_token, _, _) = register_verified(&server, &db).await;
+    let res = server
+        .get("/admin/links/top")
+        .authorization_bearer(&user_token)
+        .await;
+    assert_eq!(res.status_code(), 403);
+}
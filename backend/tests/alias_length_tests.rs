@@ -0,0 +1,78 @@
+//! MIN_ALIAS_LENGTH / MAX_ALIAS_LENGTH are enforced at link creation with the
+//! same values GET /auth/settings advertises. Lives in its own test binary
+//! because it sets process-wide env vars.
+
+mod common;
+
+use common::{mark_email_verified, spawn_real_app};
+use serde_json::{json, Value};
+
+fn set_alias_bounds() {
+    std::env::set_var("MIN_ALIAS_LENGTH", "3");
+    std::env::set_var("MAX_ALIAS_LENGTH", "8");
+}
+
+/// Random alphanumeric alias of exactly `len` characters.
+fn alias_of_len(len: usize) -> String {
+    use rand::distributions::Alphanumeric;
+    use rand::{thread_rng, Rng};
+
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+async fn register_verified(
+    server: &axum_test::TestServer,
+    db: &sea_orm::DatabaseConnection,
+) -> String {
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "register: {}", res.text());
+    let body: Value = res.json();
+    mark_email_verified(db, body["user_id"].as_i64().unwrap() as i32).await;
+    body["token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn settings_advertise_configured_alias_bounds() {
+    set_alias_bounds();
+    let (server, _db) = spawn_real_app().await;
+
+    let settings: Value = server.get("/auth/settings").await.json();
+    assert_eq!(settings["min_alias_length"], 3);
+    assert_eq!(settings["max_alias_length"], 8);
+}
+
+#[tokio::test]
+async fn alias_length_boundaries_follow_env() {
+    set_alias_bounds();
+    let (server, db) = spawn_real_app().await;
+    let token = register_verified(&server, &db).await;
+
+    for len in [3, 8] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": "https://iana.org", "custom_alias": alias_of_len(len) }))
+            .await;
+        assert_eq!(res.status_code(), 201, "len {len}: {}", res.text());
+    }
+
+    for len in [2, 9] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": "https://iana.org", "custom_alias": alias_of_len(len) }))
+            .await;
+        assert_eq!(res.status_code(), 400, "len {len}: {}", res.text());
+        assert_eq!(
+            res.json::<Value>()["error"],
+            "Alias must be between 3 and 8 characters"
+        );
+    }
+}
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 mod aggregation_tests {
     use super::*;
 
+    #[allow(dead_code)]
     #[derive(Clone)]
     struct ClickEvent {
         link_id: i32,
@@ -368,6 +369,7 @@ mod referer_tests {
 mod dashboard_tests {
     use chrono::{Duration, NaiveDateTime, Utc};
 
+    #[allow(dead_code)]
     struct Link {
         id: i32,
         click_count: i32,
@@ -375,6 +377,7 @@ mod dashboard_tests {
         created_at: NaiveDateTime,
     }
 
+    #[allow(dead_code)]
     struct DashboardStats {
         total_links: i64,
         total_clicks: i64,
@@ -18,6 +18,269 @@ mod tests {
             response.text()
         );
     }
+
+    async fn owner_with_clicks(
+        server: &axum_test::TestServer,
+        db: &sea_orm::DatabaseConnection,
+        clicks: i64,
+    ) -> (String, i64) {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+        use serde_json::{json, Value};
+
+        let res = server
+            .post("/auth/register")
+            .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+            .await;
+        let body: Value = res.json();
+        common::mark_email_verified(db, body["user_id"].as_i64().unwrap() as i32).await;
+        let token = body["token"].as_str().unwrap().to_string();
+
+        let link_id = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": "https://iana.org/clicks" }))
+            .await
+            .json::<Value>()["id"]
+            .as_i64()
+            .unwrap();
+
+        // Click i is i minutes old and carries its index in `city`.
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "INSERT INTO click_events (link_id, created_at, city) \
+             SELECT $1, NOW() - make_interval(mins => i), 'c' || i FROM generate_series(0, $2 - 1) AS i",
+            [(link_id as i32).into(), (clicks as i32).into()],
+        ))
+        .await
+        .unwrap();
+        (token, link_id)
+    }
+
+    #[tokio::test]
+    async fn click_events_are_paged_newest_first() {
+        use serde_json::Value;
+
+        let (server, db) = common::spawn_real_app().await;
+        let (token, link_id) = owner_with_clicks(&server, &db, 5).await;
+
+        let page: Value = server
+            .get(&format!("/links/{link_id}/clicks?limit=2"))
+            .authorization_bearer(&token)
+            .await
+            .json();
+        assert_eq!(page["total"], 5);
+        let cities: Vec<&str> = page["clicks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["city"].as_str().unwrap())
+            .collect();
+        assert_eq!(cities, ["c0", "c1"]);
+
+        // Last partial page, then past the end.
+        let page: Value = server
+            .get(&format!("/links/{link_id}/clicks?limit=2&offset=4"))
+            .authorization_bearer(&token)
+            .await
+            .json();
+        assert_eq!(page["clicks"].as_array().unwrap().len(), 1);
+        assert_eq!(page["clicks"][0]["city"], "c4");
+        let page: Value = server
+            .get(&format!("/links/{link_id}/clicks?offset=5"))
+            .authorization_bearer(&token)
+            .await
+            .json();
+        assert!(page["clicks"].as_array().unwrap().is_empty());
+        assert_eq!(page["total"], 5);
+
+        // Out-of-range page sizes are clamped.
+        let page: Value = server
+            .get(&format!("/links/{link_id}/clicks?limit=0"))
+            .authorization_bearer(&token)
+            .await
+            .json();
+        assert_eq!(page["limit"], 1);
+    }
+
+    #[tokio::test]
+    async fn click_events_are_private_to_the_owner() {
+        let (server, db) = common::spawn_real_app().await;
+        let (_, link_id) = owner_with_clicks(&server, &db, 1).await;
+        let (other, _) = owner_with_clicks(&server, &db, 0).await;
+
+        let res = server
+            .get(&format!("/links/{link_id}/clicks"))
+            .authorization_bearer(&other)
+            .await;
+        assert_eq!(res.status_code(), 403);
+        let res = server.get(&format!("/links/{link_id}/clicks")).await;
+        assert_eq!(res.status_code(), 401);
+    }
+
+    #[tokio::test]
+    async fn stats_break_clicks_down_by_source() {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+        use serde_json::Value;
+
+        let (server, db) = common::spawn_real_app().await;
+        let (token, link_id) = owner_with_clicks(&server, &db, 2).await;
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "INSERT INTO click_events (link_id, created_at, source) \
+             SELECT $1, NOW(), s FROM unnest(ARRAY['twitter', 'newsletter', 'twitter', 'twitter']) AS s",
+            [(link_id as i32).into()],
+        ))
+        .await
+        .unwrap();
+
+        let stats: Value = server
+            .get(&format!("/links/{link_id}/stats"))
+            .authorization_bearer(&token)
+            .await
+            .json();
+        let by_source: Vec<(&str, i64)> = stats["clicks_by_source"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| (s["source"].as_str().unwrap(), s["count"].as_i64().unwrap()))
+            .collect();
+        assert_eq!(
+            by_source,
+            [("twitter", 3), ("Untagged", 2), ("newsletter", 1)]
+        );
+        assert_eq!(stats["clicks_by_source"][0]["percentage"], 50.0);
+    }
+
+    #[tokio::test]
+    async fn heatmap_buckets_clicks_by_local_weekday_and_hour() {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+        use serde_json::Value;
+
+        let (server, db) = common::spawn_real_app().await;
+        let (token, link_id) = owner_with_clicks(&server, &db, 0).await;
+        // Monday 2024-01-01 23:30 UTC twice, Sunday 2024-01-07 03:15 UTC once.
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "INSERT INTO click_events (link_id, created_at) SELECT $1, t::timestamp FROM \
+             unnest(ARRAY['2024-01-01 23:30', '2024-01-01 23:30', '2024-01-07 03:15']) AS t",
+            [(link_id as i32).into()],
+        ))
+        .await
+        .unwrap();
+
+        let heatmap = |tz: &'static str| {
+            let server = &server;
+            let token = token.clone();
+            async move {
+                let res = server
+                    .get(&format!("/links/{link_id}/heatmap?days=100000&tz={tz}"))
+                    .authorization_bearer(&token)
+                    .await;
+                assert_eq!(res.status_code(), 200, "{tz}: {}", res.text());
+                res.json::<Value>()
+            }
+        };
+
+        let utc = heatmap("UTC").await;
+        assert_eq!(utc["total"], 3);
+        assert_eq!(utc["tz"], "+00:00");
+        assert_eq!(utc["matrix"].as_array().unwrap().len(), 7);
+        assert_eq!(utc["matrix"][0].as_array().unwrap().len(), 24);
+        assert_eq!(utc["matrix"][0][23], 2);
+        assert_eq!(utc["matrix"][6][3], 1);
+
+        // East of UTC the Monday-night clicks roll over into Tuesday.
+        let east = heatmap("%2B02:00").await;
+        assert_eq!(east["tz"], "+02:00");
+        assert_eq!(east["matrix"][1][1], 2);
+        assert_eq!(east["matrix"][6][5], 1);
+        assert_eq!(east["matrix"][0][23], 0);
+
+        // West of UTC the early-Sunday click falls back to Saturday evening.
+        let west = heatmap("-0500").await;
+        assert_eq!(west["matrix"][0][18], 2);
+        assert_eq!(west["matrix"][5][22], 1);
+
+        let res = server
+            .get(&format!("/links/{link_id}/heatmap?tz=Europe/Paris"))
+            .authorization_bearer(&token)
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let (other, _) = owner_with_clicks(&server, &db, 0).await;
+        let res = server
+            .get(&format!("/links/{link_id}/heatmap"))
+            .authorization_bearer(&other)
+            .await;
+        assert_eq!(res.status_code(), 403);
+    }
+
+    #[tokio::test]
+    async fn compare_reports_change_against_the_previous_period() {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+        use serde_json::Value;
+
+        let (server, db) = common::spawn_real_app().await;
+        let (token, link_id) = owner_with_clicks(&server, &db, 0).await;
+        // Ages in hours: three clicks this week (one of them today), two in
+        // the week before and one older than both.
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "INSERT INTO click_events (link_id, created_at) \
+             SELECT $1, NOW() - make_interval(hours => h) \
+             FROM unnest(ARRAY[1, 30, 100, 200, 300, 400]) AS h",
+            [(link_id as i32).into()],
+        ))
+        .await
+        .unwrap();
+
+        let compare = |period: &'static str| {
+            let server = &server;
+            let token = token.clone();
+            async move {
+                let res = server
+                    .get(&format!("/links/{link_id}/compare?period={period}"))
+                    .authorization_bearer(&token)
+                    .await;
+                assert_eq!(res.status_code(), 200, "{period}: {}", res.text());
+                res.json::<Value>()
+            }
+        };
+
+        let week = compare("week").await;
+        assert_eq!(week["period"], "week");
+        assert_eq!(week["current_clicks"], 3);
+        assert_eq!(week["previous_clicks"], 2);
+        assert_eq!(week["change"], 1);
+        assert_eq!(week["change_percent"], 50.0);
+
+        // 1h is today, 30h yesterday: 1 vs 1 is no change.
+        let day = compare("day").await;
+        assert_eq!(day["current_clicks"], 1);
+        assert_eq!(day["previous_clicks"], 1);
+        assert_eq!(day["change_percent"], 0.0);
+
+        // Everything falls in the current 30 days, so there is no baseline.
+        let month = compare("month").await;
+        assert_eq!(month["current_clicks"], 6);
+        assert_eq!(month["previous_clicks"], 0);
+        assert!(month["change_percent"].is_null());
+
+        let res = server
+            .get(&format!("/links/{link_id}/compare?period=year"))
+            .authorization_bearer(&token)
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let (other, _) = owner_with_clicks(&server, &db, 0).await;
+        let res = server
+            .get(&format!("/links/{link_id}/compare"))
+            .authorization_bearer(&other)
+            .await;
+        assert_eq!(res.status_code(), 403);
+        let res = server.get(&format!("/links/{link_id}/compare")).await;
+        assert_eq!(res.status_code(), 401);
+    }
 }
 
 // Unit tests for analytics processing
@@ -158,7 +421,7 @@ mod unit_tests {
 mod stats_tests {
     #[test]
     fn test_total_clicks_calculation() {
-        let clicks = vec![5, 10, 3, 7, 15];
+        let clicks = [5, 10, 3, 7, 15];
         let total: i32 = clicks.iter().sum();
 
         assert_eq!(total, 40);
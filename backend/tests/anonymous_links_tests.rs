@@ -0,0 +1,79 @@
+//! ALLOW_ANONYMOUS_LINKS / ANONYMOUS_LINK_EXPIRY_DAYS policy for links created
+//! without an account. Lives in its own test binary because it sets
+//! process-wide env vars; the steps run in one test so they can't interleave.
+
+mod common;
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use common::spawn_real_app;
+use serde_json::{json, Value};
+
+/// Link timestamps are served as stored: UTC without an offset.
+fn utc(value: &Value) -> DateTime<Utc> {
+    NaiveDateTime::parse_from_str(value.as_str().unwrap(), "%Y-%m-%d %H:%M:%S%.f")
+        .unwrap()
+        .and_utc()
+}
+
+#[tokio::test]
+async fn anonymous_link_policy() {
+    std::env::remove_var("ALLOW_ANONYMOUS_LINKS");
+    std::env::set_var("ANONYMOUS_LINK_EXPIRY_DAYS", "7");
+    let (server, _db) = spawn_real_app().await;
+
+    let settings: Value = server.get("/auth/settings").await.json();
+    assert_eq!(settings["anonymous_links_enabled"], true);
+    assert_eq!(settings["anonymous_link_expiry_days"], 7);
+
+    // No expiry requested: the policy default applies.
+    let res = server
+        .post("/links")
+        .json(&json!({ "original_url": "https://iana.org/anon" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let body: Value = res.json();
+    assert!(
+        body["expires_at"].is_string(),
+        "anonymous link gets an expiry"
+    );
+    let expires_at = utc(&body["expires_at"]);
+    let expected = Utc::now() + Duration::days(7);
+    assert!((expected - expires_at).num_seconds().abs() < 60);
+
+    // A later expiry is clamped to the policy; an earlier one is kept.
+    let res = server
+        .post("/links")
+        .json(&json!({
+            "original_url": "https://iana.org/anon-later",
+            "expires_at": (Utc::now() + Duration::days(30)).to_rfc3339(),
+        }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let clamped = utc(&res.json::<Value>()["expires_at"]);
+    assert!(clamped <= Utc::now() + Duration::days(7));
+
+    let sooner = Utc::now() + Duration::days(1);
+    let res = server
+        .post("/links")
+        .json(&json!({
+            "original_url": "https://iana.org/anon-sooner",
+            "expires_at": sooner.to_rfc3339(),
+        }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let kept = utc(&res.json::<Value>()["expires_at"]);
+    assert_eq!(kept.timestamp(), sooner.timestamp());
+
+    std::env::set_var("ALLOW_ANONYMOUS_LINKS", "false");
+    let settings: Value = server.get("/auth/settings").await.json();
+    assert_eq!(settings["anonymous_links_enabled"], false);
+
+    let res = server
+        .post("/links")
+        .json(&json!({ "original_url": "https://iana.org/anon-denied" }))
+        .await;
+    assert_eq!(res.status_code(), 401, "{}", res.text());
+
+    std::env::remove_var("ALLOW_ANONYMOUS_LINKS");
+    std::env::remove_var("ANONYMOUS_LINK_EXPIRY_DAYS");
+}
@@ -96,6 +96,43 @@ async fn tag_link_count_excludes_soft_deleted_links() {
     );
 }
 
+/// Re-adding a tag (sequentially, twice in one request, or concurrently) must
+/// leave exactly one association; only the first insert counts as added.
+#[tokio::test]
+async fn adding_same_tag_twice_keeps_single_association() {
+    let (server, db) = spawn_real_app().await;
+    let token = register_verified(&server, &db).await;
+
+    let tag_id = server
+        .post("/tags")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "dupe" }))
+        .await
+        .json::<Value>()["id"]
+        .as_i64()
+        .unwrap();
+    let id = create_link(&server, &token, "https://iana.org/dupe-tag").await;
+
+    let add = || {
+        server
+            .post(&format!("/links/{id}/tags"))
+            .authorization_bearer(&token)
+            .json(&json!({ "tag_ids": [tag_id, tag_id] }))
+    };
+    let first: Value = add().await.json();
+    assert_eq!(first["added"], 1);
+    let (a, b) = tokio::join!(add(), add());
+    assert_eq!(a.json::<Value>()["added"], 0);
+    assert_eq!(b.json::<Value>()["added"], 0);
+
+    let tags: Value = server
+        .get("/tags")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(tag_link_count(&tags, tag_id), 1);
+}
+
 /// A custom alias previously used by a now-deleted link cannot be reused: the
 /// global UNIQUE on links.code still holds it. Reuse must be a clean 409 (the
 /// old ALLOW_DELETED_SLUG_REUSE path 500'd), and check-code must report it taken.
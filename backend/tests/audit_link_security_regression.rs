@@ -285,6 +285,7 @@ fn click(link_id: i32) -> ClickData {
     ClickData {
         link_id,
         ip_address: None,
+        ip_hash: None,
         user_agent: None,
         referer: None,
         country: None,
@@ -295,6 +296,9 @@ fn click(link_id: i32) -> ClickData {
         device: None,
         browser: None,
         os: None,
+        source: None,
+        is_preview: false,
+        sample_rate: 1,
     }
 }
 
@@ -334,3 +338,45 @@ async fn click_flush_isolates_orphan_without_dropping_valid_link_batch() {
         "valid aggregate count must survive an orphan in the same flush"
     );
 }
+
+#[tokio::test]
+async fn concurrent_flushes_for_same_link_sum_counts() {
+    let (server, db) = spawn_real_app().await;
+    let (token, _) = register_verified(&server, &db).await;
+    let (link_id, _) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/concurrent-flush" }),
+    )
+    .await;
+
+    // Separate buffers stand in for several app instances flushing at once;
+    // each must add its batch to whatever the row holds at commit time.
+    let batches = [3, 5, 7, 11, 13, 17, 19, 23];
+    let mut tasks = Vec::new();
+    for size in batches {
+        let buffer = ClickBuffer::new();
+        for _ in 0..size {
+            buffer.add_click(click(link_id));
+        }
+        let db = db.clone();
+        tasks.push(tokio::spawn(async move { buffer.flush(&db).await }));
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let expected: i32 = batches.iter().sum();
+    let stored = links::Entity::find_by_id(link_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.click_count, expected, "no increment may be lost");
+    let events = click_events::Entity::find()
+        .filter(click_events::Column::LinkId.eq(link_id))
+        .count(&db)
+        .await
+        .unwrap();
+    assert_eq!(events, expected as u64);
+}
@@ -71,3 +71,81 @@ async fn openapi_spec_serves_and_documents_newly_registered_handlers() {
         "verify-email success must $ref MessageResponse"
     );
 }
+
+/// Collect every `$ref` in the document so dangling schema references show up.
+fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map {
+                match (key.as_str(), v) {
+                    ("$ref", Value::String(r)) => refs.push(r),
+                    _ => collect_refs(v, refs),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_refs(v, refs)),
+        _ => {}
+    }
+}
+
+#[tokio::test]
+async fn openapi_spec_lists_account_and_admin_operations() {
+    let (server, _db) = spawn_real_app().await;
+    let spec: Value = server.get("/api-docs/openapi.json").await.json();
+
+    let operations: Vec<(String, String, &str)> = spec["paths"]
+        .as_object()
+        .expect("spec has a paths object")
+        .iter()
+        .flat_map(|(path, item)| {
+            item.as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(move |(method, op)| {
+                    Some((path.clone(), method.clone(), op["operationId"].as_str()?))
+                })
+        })
+        .collect();
+
+    for (operation_id, method, path) in [
+        ("preview_link", "get", "/{code}/preview"),
+        ("change_password", "post", "/auth/change-password"),
+        ("delete_account", "post", "/auth/delete-account"),
+        ("get_app_settings", "get", "/auth/settings"),
+        ("get_current_user", "get", "/auth/me"),
+        ("update_profile", "put", "/auth/profile"),
+        ("get_admin_stats", "get", "/admin/stats"),
+        ("get_blocked_links", "get", "/admin/blocked/links"),
+        ("get_blocked_domains", "get", "/admin/blocked/domains"),
+        (
+            "remove_admin",
+            "post",
+            "/admin/users/{user_id}/remove-admin",
+        ),
+    ] {
+        assert!(
+            operations
+                .iter()
+                .any(|(p, m, id)| *id == operation_id && m == method && p == path),
+            "OpenAPI spec must document {operation_id} as {method} {path}; got {:?}",
+            operations
+                .iter()
+                .filter(|(_, _, id)| *id == operation_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    let schemas = spec["components"]["schemas"]
+        .as_object()
+        .expect("spec must have components.schemas");
+    let mut refs = Vec::new();
+    collect_refs(&spec, &mut refs);
+    let dangling: Vec<_> = refs
+        .into_iter()
+        .filter(|r| {
+            r.strip_prefix("#/components/schemas/")
+                .is_none_or(|name| !schemas.contains_key(name))
+        })
+        .collect();
+    assert!(dangling.is_empty(), "unregistered schemas: {dangling:?}");
+}
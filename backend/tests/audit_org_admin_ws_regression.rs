@@ -547,3 +547,896 @@ async fn self_delete_revokes_credentials_and_preserves_org_links() {
         0
     );
 }
+
+#[tokio::test]
+async fn last_owner_cannot_be_demoted() {
+    use opn_onl_backend::entity::organizations;
+
+    let (server, db) = spawn_real_app().await;
+    let (owner_token, owner_id) = register_verified(&server, &db).await;
+    let (second_token, second_id) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &owner_token).await;
+    let owner_member = org_members::Entity::find()
+        .filter(org_members::Column::OrgId.eq(org_id))
+        .filter(org_members::Column::UserId.eq(owner_id))
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .id;
+
+    // Sole owner: refused, and the role is unchanged.
+    let res = server
+        .put(&format!("/orgs/{org_id}/members/{owner_member}"))
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "role": "admin" }))
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+    let stored = org_members::Entity::find_by_id(owner_member)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.role, "owner");
+
+    // With a second owner, one of them can step down.
+    let second_member = add_member(&db, org_id, second_id, "admin").await;
+    let res = server
+        .put(&format!("/orgs/{org_id}/members/{second_member}"))
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "role": "owner" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+
+    let res = server
+        .put(&format!("/orgs/{org_id}/members/{owner_member}"))
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "role": "admin" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let org = organizations::Entity::find_by_id(org_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        org.owner_id, second_id,
+        "owner_id moves to the remaining owner"
+    );
+
+    // The remaining owner is now the last one.
+    let res = server
+        .put(&format!("/orgs/{org_id}/members/{second_member}"))
+        .authorization_bearer(&second_token)
+        .json(&json!({ "role": "editor" }))
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+
+    // Admins cannot touch ownership.
+    let res = server
+        .put(&format!("/orgs/{org_id}/members/{second_member}"))
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "role": "editor" }))
+        .await;
+    assert_eq!(res.status_code(), 403, "{}", res.text());
+}
+
+#[tokio::test]
+async fn org_links_export_requires_editor_and_covers_only_org_links() {
+    let (server, db) = spawn_real_app().await;
+    let (owner_token, _) = register_verified(&server, &db).await;
+    let (editor_token, editor_id) = register_verified(&server, &db).await;
+    let (viewer_token, viewer_id) = register_verified(&server, &db).await;
+    let (outsider_token, _) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &owner_token).await;
+    add_member(&db, org_id, editor_id, "editor").await;
+    add_member(&db, org_id, viewer_id, "viewer").await;
+
+    let folder_id = create_folder(&server, &owner_token, org_id).await;
+    let tag_id = create_tag(&server, &owner_token, Some(org_id), "launch").await;
+    let org_link = create_link(&server, &owner_token, Some(org_id)).await;
+    let personal_link = create_link(&server, &owner_token, None).await;
+    let res = server
+        .put(&format!("/links/{org_link}"))
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "folder_id": folder_id }))
+        .await;
+    assert_eq!(res.status_code(), 200, "move: {}", res.text());
+    let res = server
+        .post(&format!("/links/{org_link}/tags"))
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "tag_ids": [tag_id] }))
+        .await;
+    assert!(res.status_code().is_success(), "tag: {}", res.text());
+
+    // Stay under the per-IP burst limit for the checks below.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let url = format!("/orgs/{org_id}/links/export");
+    for token in [&viewer_token, &outsider_token] {
+        let res = server.get(&url).authorization_bearer(token).await;
+        assert_eq!(res.status_code(), 403, "{}", res.text());
+    }
+    assert_eq!(server.get(&url).await.status_code(), 401);
+    let res = server
+        .get(&format!("{url}?format=xml"))
+        .authorization_bearer(&editor_token)
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+
+    let res = server
+        .get(&format!("{url}?format=json"))
+        .authorization_bearer(&editor_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let rows: Vec<Value> = res.json();
+    assert_eq!(rows.len(), 1, "only the org link: {rows:?}");
+    assert_eq!(rows[0]["id"], org_link);
+    assert_eq!(rows[0]["folder_name"], "Audit Folder");
+    assert_eq!(rows[0]["tags"], json!(["launch"]));
+
+    let res = server.get(&url).authorization_bearer(&editor_token).await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), "text/csv");
+    let csv = res.text();
+    let mut lines = csv.lines();
+    assert!(lines.next().unwrap().ends_with(",Folder Name,Tags"));
+    let row = lines.next().unwrap();
+    assert!(row.starts_with(&format!("{org_link},")), "{row}");
+    assert!(row.ends_with(r#","Audit Folder","launch""#), "{row}");
+    assert!(lines.next().is_none());
+    assert!(!csv.contains(&format!("\n{personal_link},")));
+}
+
+#[tokio::test]
+async fn org_invitation_email_uses_org_branding() {
+    use opn_onl_backend::entity::organizations;
+    use opn_onl_backend::utils::email::{EmailService, OrgEmailBranding};
+
+    let (server, db) = spawn_real_app().await;
+    let (token, _) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &token).await;
+    let email = EmailService::new();
+
+    // Unbranded: the org's own name, instance name in the header.
+    let org = organizations::Entity::find_by_id(org_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    let (subject, body) =
+        email.org_invitation_email(&OrgEmailBranding::for_org(&org), "alice@iana.org", "editor");
+    assert_eq!(subject, format!("You've been added to {}", org.name));
+    assert!(body.contains(&org.name), "{body}");
+    assert!(!body.contains("<img"));
+
+    let res = server
+        .put(&format!("/orgs/{org_id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "brand_logo_url": "javascript:alert(1)" }))
+        .await;
+    assert_eq!(res.status_code(), 400);
+
+    let res = server
+        .put(&format!("/orgs/{org_id}"))
+        .authorization_bearer(&token)
+        .json(&json!({
+            "brand_name": "Acme <Links>",
+            "brand_logo_url": "https://iana.org/logo.png",
+        }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    let body: Value = res.json();
+    assert_eq!(body["brand_name"], "Acme <Links>");
+    assert_eq!(body["brand_logo_url"], "https://iana.org/logo.png");
+
+    let org = organizations::Entity::find_by_id(org_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    let (subject, body) =
+        email.org_invitation_email(&OrgEmailBranding::for_org(&org), "alice@iana.org", "editor");
+    assert_eq!(subject, "You've been added to Acme <Links>");
+    assert!(body.contains("Acme &lt;Links&gt;"), "{body}");
+    assert!(
+        body.contains(r#"<img src="https://iana.org/logo.png""#),
+        "{body}"
+    );
+    assert!(body.contains("alice@iana.org"));
+
+    // Clearing falls back to the org name again.
+    let res = server
+        .put(&format!("/orgs/{org_id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "brand_name": "", "brand_logo_url": "" }))
+        .await;
+    assert_eq!(res.status_code(), 200);
+    assert!(res.json::<Value>()["brand_name"].is_null());
+}
+
+#[tokio::test]
+async fn org_code_prefix_brands_generated_org_codes_only() {
+    let (server, db) = spawn_real_app().await;
+    let (token, _) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &token).await;
+
+    for bad in ["a", "acme-co", "waytoolongprefix", "admin"] {
+        let res = server
+            .put(&format!("/orgs/{org_id}"))
+            .authorization_bearer(&token)
+            .json(&json!({ "code_prefix": bad }))
+            .await;
+        assert_eq!(res.status_code(), 400, "{bad}: {}", res.text());
+    }
+    let res = server
+        .put(&format!("/orgs/{org_id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "code_prefix": "Acme" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "set prefix: {}", res.text());
+    assert_eq!(res.json::<Value>()["code_prefix"], "acme");
+
+    // Stay under the per-second API rate limit.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let code_of = |id: i32| {
+        let db = db.clone();
+        async move {
+            links::Entity::find_by_id(id)
+                .one(&db)
+                .await
+                .unwrap()
+                .unwrap()
+                .code
+        }
+    };
+    let org_link = create_link(&server, &token, Some(org_id)).await;
+    let code = code_of(org_link).await;
+    assert!(
+        code.starts_with("acme-") && code.len() > "acme-".len(),
+        "{code}"
+    );
+    assert_eq!(
+        server.get(&format!("/{code}")).await.status_code(),
+        307,
+        "prefixed code must redirect"
+    );
+
+    let personal = create_link(&server, &token, None).await;
+    assert!(!code_of(personal).await.starts_with("acme-"));
+
+    // An explicit alias is used as given.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "original_url": "https://iana.org/alias",
+            "org_id": org_id,
+            "custom_alias": format!("plain{}", uuid::Uuid::new_v4().simple()),
+        }))
+        .await;
+    assert_eq!(res.status_code(), 201, "alias: {}", res.text());
+    assert!(!res.json::<Value>()["code"]
+        .as_str()
+        .unwrap()
+        .starts_with("acme-"));
+
+    let res = server
+        .put(&format!("/orgs/{org_id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "code_prefix": "" }))
+        .await;
+    assert!(res.json::<Value>()["code_prefix"].is_null());
+    let cleared = create_link(&server, &token, Some(org_id)).await;
+    assert!(!code_of(cleared).await.starts_with("acme-"));
+}
+
+#[tokio::test]
+async fn bulk_folder_moves_stay_in_org_and_can_be_cleared() {
+    let (server, db) = spawn_real_app().await;
+    let (owner_token, _) = register_verified(&server, &db).await;
+    let (viewer_token, viewer_id) = register_verified(&server, &db).await;
+    let org_a = create_org(&server, &owner_token).await;
+    let org_b = create_org(&server, &owner_token).await;
+    add_member(&db, org_a, viewer_id, "viewer").await;
+    let folder_a = create_folder(&server, &owner_token, org_a).await;
+    let link_a = create_link(&server, &owner_token, Some(org_a)).await;
+    let link_b = create_link(&server, &owner_token, Some(org_b)).await;
+    let personal = create_link(&server, &owner_token, None).await;
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // Only the org A link may enter the org A folder.
+    let res = server
+        .post(&format!("/folders/{folder_a}/links"))
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "link_ids": [link_a, link_b, personal] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "move: {}", res.text());
+    assert_eq!(res.json::<Value>()["moved"], 1);
+    let folder_of = |id: i32| {
+        let db = db.clone();
+        async move {
+            links::Entity::find_by_id(id)
+                .one(&db)
+                .await
+                .unwrap()
+                .unwrap()
+                .folder_id
+        }
+    };
+    assert_eq!(folder_of(link_a).await, Some(folder_a));
+    assert_eq!(folder_of(link_b).await, None);
+    assert_eq!(folder_of(personal).await, None);
+
+    let res = server
+        .post("/folders")
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "name": "Mine" }))
+        .await;
+    let personal_folder = res.json::<Value>()["id"].as_i64().unwrap() as i32;
+    let res = server
+        .post(&format!("/folders/{personal_folder}/links"))
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "link_ids": [personal, link_a] }))
+        .await;
+    assert_eq!(res.json::<Value>()["moved"], 1);
+    assert_eq!(folder_of(personal).await, Some(personal_folder));
+    assert_eq!(folder_of(link_a).await, Some(folder_a));
+
+    // A viewer can't clear org links, nor anyone else's personal ones.
+    let res = server
+        .post("/folders/none/links")
+        .authorization_bearer(&viewer_token)
+        .json(&json!({ "link_ids": [link_a, personal] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "viewer clear: {}", res.text());
+    assert_eq!(res.json::<Value>()["cleared"], 0);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let res = server
+        .post("/folders/none/links")
+        .authorization_bearer(&owner_token)
+        .json(&json!({ "link_ids": [link_a, link_b, personal] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "clear: {}", res.text());
+    assert_eq!(res.json::<Value>()["cleared"], 2);
+    for id in [link_a, link_b, personal] {
+        assert_eq!(folder_of(id).await, None);
+    }
+
+    let res = server
+        .post("/folders/none/links")
+        .json(&json!({ "link_ids": [link_a] }))
+        .await;
+    assert_eq!(res.status_code(), 401);
+}
+
+#[tokio::test]
+async fn org_members_list_teammates_org_links() {
+    let (server, db) = spawn_real_app().await;
+    let (owner_token, _) = register_verified(&server, &db).await;
+    let (member_token, member_id) = register_verified(&server, &db).await;
+    let (outsider_token, _) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &owner_token).await;
+    add_member(&db, org_id, member_id, "viewer").await;
+    let org_link = create_link(&server, &owner_token, Some(org_id)).await;
+    let personal = create_link(&server, &owner_token, None).await;
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let ids = |res: axum_test::TestResponse| -> Vec<i64> {
+        res.json::<Vec<Value>>()
+            .iter()
+            .map(|l| l["id"].as_i64().unwrap())
+            .collect()
+    };
+
+    let res = server
+        .get(&format!("/links?org_id={org_id}"))
+        .authorization_bearer(&member_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "member list: {}", res.text());
+    assert_eq!(ids(res), vec![org_link as i64]);
+
+    // Without org_id the listing stays personal.
+    let res = server
+        .get("/links")
+        .authorization_bearer(&member_token)
+        .await;
+    let own = ids(res);
+    assert!(!own.contains(&(org_link as i64)) && !own.contains(&(personal as i64)));
+
+    let res = server
+        .get(&format!("/links?org_id={org_id}"))
+        .authorization_bearer(&outsider_token)
+        .await;
+    assert_eq!(res.status_code(), 403);
+}
+
+#[tokio::test]
+async fn org_tag_suggest_matches_names_and_ranks_by_use() {
+    let (server, db) = spawn_real_app().await;
+    let (owner_token, _) = register_verified(&server, &db).await;
+    let (viewer_token, viewer_id) = register_verified(&server, &db).await;
+    let (outsider_token, _) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &owner_token).await;
+    add_member(&db, org_id, viewer_id, "viewer").await;
+
+    let launch = create_tag(&server, &owner_token, Some(org_id), "Launch").await;
+    let relaunch = create_tag(&server, &owner_token, Some(org_id), "relaunch").await;
+    create_tag(&server, &owner_token, Some(org_id), "lunch").await;
+    create_tag(&server, &owner_token, None, "launch-personal").await;
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // relaunch is on two links, Launch on one.
+    for tag_ids in [vec![relaunch], vec![relaunch, launch]] {
+        let link_id = create_link(&server, &owner_token, Some(org_id)).await;
+        link_tags::Entity::insert_many(tag_ids.into_iter().map(|tag_id| link_tags::ActiveModel {
+            link_id: Set(link_id),
+            tag_id: Set(tag_id),
+            ..Default::default()
+        }))
+        .exec(&db)
+        .await
+        .unwrap();
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let suggest = |q: &str| format!("/orgs/{org_id}/tags/suggest?q={q}");
+    let names = |res: axum_test::TestResponse| -> Vec<String> {
+        assert_eq!(res.status_code(), 200, "{}", res.text());
+        res.json::<Vec<Value>>()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let res = server
+        .get(&suggest("lau"))
+        .authorization_bearer(&viewer_token)
+        .await;
+    assert_eq!(names(res), ["relaunch", "Launch"]);
+    let res = server
+        .get(&suggest("LUN"))
+        .authorization_bearer(&viewer_token)
+        .await;
+    assert_eq!(names(res), ["lunch"]);
+    let res = server
+        .get(&suggest("re"))
+        .authorization_bearer(&owner_token)
+        .await;
+    assert_eq!(names(res), ["relaunch"]);
+    // LIKE wildcards in the query are matched literally.
+    let res = server
+        .get(&suggest("%25"))
+        .authorization_bearer(&owner_token)
+        .await;
+    assert!(names(res).is_empty());
+
+    let res = server
+        .get(&format!("/orgs/{org_id}/tags/suggest?limit=2"))
+        .authorization_bearer(&owner_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let top: Vec<Value> = res.json();
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0]["link_count"], 2);
+    assert_eq!(top[1]["link_count"], 1);
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let res = server
+        .get(&suggest("lau"))
+        .authorization_bearer(&outsider_token)
+        .await;
+    assert_eq!(res.status_code(), 403, "{}", res.text());
+    assert_eq!(server.get(&suggest("lau")).await.status_code(), 401);
+}
+
+#[tokio::test]
+async fn org_export_round_trips_through_import() {
+    let (server, db) = spawn_real_app().await;
+    let (owner_token, _) = register_verified(&server, &db).await;
+    let (editor_token, editor_id) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &owner_token).await;
+    add_member(&db, org_id, editor_id, "editor").await;
+
+    let folder_id = create_folder(&server, &owner_token, org_id).await;
+    let alpha = create_tag(&server, &owner_token, Some(org_id), "alpha").await;
+    let beta = create_tag(&server, &owner_token, Some(org_id), "beta").await;
+    let filed = create_link(&server, &owner_token, Some(org_id)).await;
+    let loose = create_link(&server, &owner_token, Some(org_id)).await;
+    let mut link: links::ActiveModel = links::Entity::find_by_id(filed)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    link.folder_id = Set(Some(folder_id));
+    link.update(&db).await.unwrap();
+    link_tags::Entity::insert_many([(filed, alpha), (filed, beta), (loose, beta)].map(
+        |(link_id, tag_id)| link_tags::ActiveModel {
+            link_id: Set(link_id),
+            tag_id: Set(tag_id),
+            ..Default::default()
+        },
+    ))
+    .exec(&db)
+    .await
+    .unwrap();
+
+    let res = server
+        .get(&format!("/orgs/{org_id}/export"))
+        .authorization_bearer(&editor_token)
+        .await;
+    assert_eq!(res.status_code(), 403, "export is owner-only");
+    let res = server
+        .get(&format!("/orgs/{org_id}/export"))
+        .authorization_bearer(&owner_token)
+        .await;
+    assert_eq!(res.status_code(), 200, "export: {}", res.text());
+    let bundle: Value = res.json();
+    assert_eq!(bundle["links"].as_array().unwrap().len(), 2);
+
+    // Same instance: the slug and codes are still taken.
+    let res = server
+        .post("/orgs/import")
+        .authorization_bearer(&owner_token)
+        .json(&bundle)
+        .await;
+    assert_eq!(res.status_code(), 409, "{}", res.text());
+
+    // Fresh rate-limiters: on a fast run the requests above already use up
+    // the per-second budget.
+    let (server, _) = spawn_real_app().await;
+    let res = server
+        .delete(&format!("/orgs/{org_id}"))
+        .authorization_bearer(&owner_token)
+        .await;
+    assert_eq!(res.status_code(), 204, "delete: {}", res.text());
+
+    let mut with_stranger = bundle.clone();
+    with_stranger["members"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({ "email": unique_email(), "role": "viewer" }));
+    let res = server
+        .post("/orgs/import")
+        .authorization_bearer(&owner_token)
+        .json(&with_stranger)
+        .await;
+    assert_eq!(res.status_code(), 201, "import: {}", res.text());
+    let imported: Value = res.json();
+    assert_eq!(imported["members_added"], 1);
+    assert_eq!(imported["unresolved_members"].as_array().unwrap().len(), 1);
+    assert_eq!(imported["links_created"], 2);
+    let new_org = imported["organization"]["id"].as_i64().unwrap() as i32;
+    assert_ne!(new_org, org_id);
+
+    let editor = org_members::Entity::find()
+        .filter(org_members::Column::OrgId.eq(new_org))
+        .filter(org_members::Column::UserId.eq(editor_id))
+        .one(&db)
+        .await
+        .unwrap()
+        .expect("editor resolved by email");
+    assert_eq!(editor.role, "editor");
+
+    // Links come back under their codes with the same folder and tag names.
+    let summary = |bundle: &Value| -> Vec<(String, String, Option<String>, Vec<String>)> {
+        let name_of = |list: &str, id: &Value| {
+            bundle[list]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|item| &item["id"] == id)
+                .map(|item| item["name"].as_str().unwrap().to_string())
+        };
+        let mut links: Vec<_> = bundle["links"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|l| {
+                let mut tags: Vec<String> = l["tag_ids"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .filter_map(|t| name_of("tags", t))
+                    .collect();
+                tags.sort();
+                (
+                    l["code"].as_str().unwrap().to_string(),
+                    l["original_url"].as_str().unwrap().to_string(),
+                    name_of("folders", &l["folder_id"]),
+                    tags,
+                )
+            })
+            .collect();
+        links.sort();
+        links
+    };
+    let res = server
+        .get(&format!("/orgs/{new_org}/export"))
+        .authorization_bearer(&owner_token)
+        .await;
+    assert_eq!(res.status_code(), 200);
+    let again: Value = res.json();
+    assert_eq!(summary(&again), summary(&bundle));
+    assert_eq!(again["organization"], bundle["organization"]);
+    let redirect = server
+        .get(&format!(
+            "/{}",
+            bundle["links"][0]["code"].as_str().unwrap()
+        ))
+        .await;
+    assert_eq!(redirect.status_code(), 307);
+
+    let res = server
+        .get(&format!("/orgs/{new_org}/audit"))
+        .authorization_bearer(&owner_token)
+        .await;
+    let actions: Vec<String> = res
+        .json::<Vec<Value>>()
+        .iter()
+        .map(|e| e["action"].as_str().unwrap().to_string())
+        .collect();
+    assert!(actions.contains(&"import".to_string()), "{actions:?}");
+    assert!(actions.contains(&"export".to_string()), "{actions:?}");
+}
+
+#[tokio::test]
+async fn org_viewer_can_list_but_not_change_tags_and_folders() {
+    let (server, db) = spawn_real_app().await;
+    let (owner, _) = register_verified(&server, &db).await;
+    let (editor, editor_id) = register_verified(&server, &db).await;
+    let (viewer, viewer_id) = register_verified(&server, &db).await;
+    let (outsider, _) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &owner).await;
+    add_member(&db, org_id, editor_id, "editor").await;
+    add_member(&db, org_id, viewer_id, "viewer").await;
+
+    let tag_id = create_tag(&server, &editor, Some(org_id), "shared").await;
+    let folder_id = create_folder(&server, &editor, org_id).await;
+
+    for path in [
+        format!("/tags?org_id={org_id}"),
+        format!("/folders?org_id={org_id}"),
+        format!("/orgs/{org_id}/tags/suggest"),
+    ] {
+        let res = server.get(&path).authorization_bearer(&viewer).await;
+        assert_eq!(res.status_code(), 200, "viewer GET {path}: {}", res.text());
+        assert_eq!(res.json::<Value>().as_array().unwrap().len(), 1, "{path}");
+        let res = server.get(&path).authorization_bearer(&outsider).await;
+        assert_eq!(res.status_code(), 403, "outsider GET {path}");
+    }
+    // Stay under the per-second API rate limit.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    for path in [format!("/tags/{tag_id}"), format!("/folders/{folder_id}")] {
+        let res = server.get(&path).authorization_bearer(&viewer).await;
+        assert_eq!(res.status_code(), 200, "viewer GET {path}: {}", res.text());
+    }
+
+    for path in ["/tags", "/folders"] {
+        let res = server
+            .post(path)
+            .authorization_bearer(&viewer)
+            .json(&json!({ "name": "viewer-made", "org_id": org_id }))
+            .await;
+        assert_eq!(res.status_code(), 403, "viewer POST {path}: {}", res.text());
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    for path in [format!("/tags/{tag_id}"), format!("/folders/{folder_id}")] {
+        let res = server
+            .put(&path)
+            .authorization_bearer(&viewer)
+            .json(&json!({ "name": "renamed" }))
+            .await;
+        assert_eq!(res.status_code(), 403, "viewer PUT {path}: {}", res.text());
+        let res = server.delete(&path).authorization_bearer(&viewer).await;
+        assert_eq!(
+            res.status_code(),
+            403,
+            "viewer DELETE {path}: {}",
+            res.text()
+        );
+    }
+    assert_eq!(
+        tags::Entity::find_by_id(tag_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .name,
+        "shared"
+    );
+    assert!(folders::Entity::find_by_id(folder_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn org_branding_change_invalidates_cached_qr_codes() {
+    let (server, db) = spawn_real_app().await;
+    let (owner, _) = register_verified(&server, &db).await;
+    let (editor, editor_id) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &owner).await;
+    add_member(&db, org_id, editor_id, "editor").await;
+    let link_id = create_link(&server, &owner, Some(org_id)).await;
+    let qr = format!("/links/{link_id}/qr");
+
+    let res = server.get(&qr).authorization_bearer(&owner).await;
+    assert_eq!(res.status_code(), 200);
+    let etag = res.header("etag").to_str().unwrap().to_string();
+    assert_eq!(res.header("cache-control"), "private, no-cache");
+    let res = server
+        .get(&qr)
+        .authorization_bearer(&owner)
+        .add_header("If-None-Match", etag.as_str())
+        .await;
+    assert_eq!(res.status_code(), 304);
+
+    // A branding update bumps the version, so the old ETag no longer matches.
+    let res = server
+        .put(&format!("/orgs/{org_id}"))
+        .authorization_bearer(&owner)
+        .json(&json!({ "brand_name": "Rebranded" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    let res = server
+        .get(&qr)
+        .authorization_bearer(&owner)
+        .add_header("If-None-Match", etag.as_str())
+        .await;
+    assert_eq!(res.status_code(), 200);
+    let rebranded = res.header("etag").to_str().unwrap().to_string();
+    assert_ne!(rebranded, etag);
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // Explicit regeneration is admin-only and bumps it again.
+    let regenerate = format!("/orgs/{org_id}/qr/regenerate");
+    let res = server.post(&regenerate).authorization_bearer(&editor).await;
+    assert_eq!(res.status_code(), 403);
+    let res = server.post(&regenerate).authorization_bearer(&owner).await;
+    assert_eq!(res.status_code(), 200, "regenerate: {}", res.text());
+    assert_eq!(res.json::<Value>()["qr_version"], 2);
+    let res = server.get(&qr).authorization_bearer(&owner).await;
+    let regenerated = res.header("etag").to_str().unwrap().to_string();
+    assert_ne!(regenerated, rebranded);
+    assert_ne!(regenerated, etag);
+}
+
+#[tokio::test]
+async fn org_editor_can_manage_org_links_they_do_not_own_but_viewer_cannot() {
+    let (server, db) = spawn_real_app().await;
+    let (owner, _) = register_verified(&server, &db).await;
+    let (editor, editor_id) = register_verified(&server, &db).await;
+    let (viewer, viewer_id) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &owner).await;
+    add_member(&db, org_id, editor_id, "editor").await;
+    add_member(&db, org_id, viewer_id, "viewer").await;
+    let first = create_link(&server, &owner, Some(org_id)).await;
+    let second = create_link(&server, &owner, Some(org_id)).await;
+    let third = create_link(&server, &owner, Some(org_id)).await;
+    let personal = create_link(&server, &owner, None).await;
+
+    // Viewers can't change anything.
+    let res = server
+        .put(&format!("/links/{first}"))
+        .authorization_bearer(&viewer)
+        .json(&json!({ "title": "viewer" }))
+        .await;
+    assert_eq!(res.status_code(), 403);
+    let res = server
+        .delete(&format!("/links/{first}"))
+        .authorization_bearer(&viewer)
+        .await;
+    assert_eq!(res.status_code(), 403);
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&viewer)
+        .json(&json!({ "ids": [first, second], "remove_expiration": true }))
+        .await;
+    assert_eq!(res.json::<Value>()["updated"], 0);
+    let res = server
+        .post("/links/bulk/delete")
+        .authorization_bearer(&viewer)
+        .json(&json!({ "ids": [first, second] }))
+        .await;
+    assert_eq!(res.json::<Value>()["deleted"], 0);
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // Editors manage org links, but not the owner's personal ones.
+    let res = server
+        .put(&format!("/links/{first}"))
+        .authorization_bearer(&editor)
+        .json(&json!({ "title": "edited by editor" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "editor update: {}", res.text());
+    assert_eq!(res.json::<Value>()["title"], "edited by editor");
+    let res = server
+        .put(&format!("/links/{personal}"))
+        .authorization_bearer(&editor)
+        .json(&json!({ "title": "nope" }))
+        .await;
+    assert_eq!(res.status_code(), 403);
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&editor)
+        .json(&json!({ "ids": [first, second, personal], "remove_expiration": true }))
+        .await;
+    assert_eq!(res.json::<Value>()["updated"], 2);
+    let res = server
+        .post("/links/bulk/delete")
+        .authorization_bearer(&editor)
+        .json(&json!({ "ids": [second, personal] }))
+        .await;
+    assert_eq!(res.json::<Value>()["deleted"], 1);
+    let res = server
+        .delete(&format!("/links/{third}"))
+        .authorization_bearer(&editor)
+        .await;
+    assert_eq!(res.status_code(), 200, "editor delete: {}", res.text());
+
+    for (id, deleted) in [
+        (first, false),
+        (second, true),
+        (third, true),
+        (personal, false),
+    ] {
+        let link = links::Entity::find_by_id(id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.deleted_at.is_some(), deleted, "link {id}");
+    }
+}
+
+/// Invitations take effect immediately: there is no separate acceptance
+/// step, so the invite itself must leave the membership and its audit trail.
+#[tokio::test]
+async fn invite_adds_membership_and_audit_entry() {
+    use opn_onl_backend::entity::audit_log;
+
+    let (server, db) = spawn_real_app().await;
+    let (admin_token, admin_id) = register_verified(&server, &db).await;
+    let (_, invitee_id) = register_verified(&server, &db).await;
+    let org_id = create_org(&server, &admin_token).await;
+    let invitee_email = users::Entity::find_by_id(invitee_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .email;
+
+    let res = server
+        .post(&format!("/orgs/{org_id}/members"))
+        .authorization_bearer(&admin_token)
+        .json(&json!({ "email": invitee_email, "role": "editor" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "invite: {}", res.text());
+    let member_id = res.json::<Value>()["id"].as_i64().unwrap() as i32;
+
+    let member = org_members::Entity::find_by_id(member_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .expect("membership row");
+    assert_eq!((member.org_id, member.user_id), (org_id, invitee_id));
+    assert_eq!(member.role, "editor");
+
+    let entry = audit_log::Entity::find()
+        .filter(audit_log::Column::OrgId.eq(org_id))
+        .filter(audit_log::Column::Action.eq("invite"))
+        .filter(audit_log::Column::ResourceId.eq(member_id))
+        .one(&db)
+        .await
+        .unwrap()
+        .expect("invite audit entry");
+    assert_eq!(entry.user_id, Some(admin_id));
+    assert_eq!(entry.resource_type, "member");
+    let details = entry.details.unwrap();
+    assert_eq!(details["email"], invitee_email.as_str());
+    assert_eq!(details["role"], "editor");
+}
@@ -4,11 +4,14 @@
 //! (The register_finish HTTP path also rejects a known cred_id with 409, but the
 //! WebAuthn ceremony can't run headless, so the DB constraint — the real safety
 //! net — is what's tested here.)
+//!
+//! Also pins the credential-id → account lookup the usernameless login uses.
 
 mod common;
 
 use common::{spawn_real_app, unique_email};
 use opn_onl_backend::entity::passkeys;
+use opn_onl_backend::handlers::passkeys::{credential_id_key, find_passkey_by_credential_id};
 use sea_orm::{ActiveModelTrait, ActiveValue::Set};
 use serde_json::{json, Value};
 
@@ -48,3 +51,74 @@ async fn passkey_cred_id_must_be_unique() {
         "duplicate cred_id must be rejected by the unique index, but the insert succeeded"
     );
 }
+
+/// Usernameless login resolves the account from the credential id the
+/// authenticator returns; each id must map back to the passkey's owner.
+#[tokio::test]
+async fn discoverable_login_resolves_user_from_credential_id() {
+    let (server, db) = spawn_real_app().await;
+
+    let mut owners = Vec::new();
+    for _ in 0..2 {
+        let reg = server
+            .post("/auth/register")
+            .json(&json!({ "email": unique_email(), "password": "password123" }))
+            .await;
+        assert_eq!(reg.status_code(), 201, "register: {}", reg.text());
+        let user_id = reg.json::<Value>()["user_id"].as_i64().unwrap() as i32;
+        let raw_id = uuid::Uuid::new_v4().as_bytes().to_vec();
+        passkey(user_id, &credential_id_key(&raw_id))
+            .insert(&db)
+            .await
+            .expect("insert passkey");
+        owners.push((user_id, raw_id));
+    }
+
+    for (user_id, raw_id) in &owners {
+        let found = find_passkey_by_credential_id(&db, raw_id)
+            .await
+            .expect("credential id resolves");
+        assert_eq!(found.user_id, *user_id);
+    }
+    assert!(
+        find_passkey_by_credential_id(&db, uuid::Uuid::new_v4().as_bytes())
+            .await
+            .is_none()
+    );
+
+    // No username: a challenge for any discoverable credential, plus the id
+    // the finish step needs.
+    let res = server
+        .post("/auth/passkey/login/start")
+        .json(&json!({}))
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let body: Value = res.json();
+    assert!(body["challenge_id"].as_str().is_some(), "{body}");
+    let allowed = &body["options"]["publicKey"]["allowCredentials"];
+    assert!(
+        allowed.is_null() || allowed.as_array().is_some_and(Vec::is_empty),
+        "{body}"
+    );
+
+    // A finish without a live challenge is refused before any lookup.
+    let res = server
+        .post("/auth/passkey/login/finish")
+        .json(&json!({
+            "challenge_id": uuid::Uuid::new_v4().to_string(),
+            "credential": {
+                "id": "AAAA",
+                "rawId": "AAAA",
+                "response": {
+                    "authenticatorData": "AAAA",
+                    "clientDataJSON": "AAAA",
+                    "signature": "AAAA",
+                    "userHandle": null
+                },
+                "extensions": {},
+                "type": "public-key"
+            }
+        }))
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+}
@@ -313,6 +313,168 @@ async fn self_delete_revokes_sessions_and_credentials() {
     );
 }
 
+#[tokio::test]
+async fn deleted_account_email_can_register_again() {
+    std::env::set_var("ENABLE_ACCOUNT_DELETION", "true");
+
+    let (server, db) = spawn_real_app().await;
+    let email = unique_email();
+    let (jwt, user_id) = register(&server, &email).await;
+    mark_email_verified(&db, user_id).await;
+    let res = server
+        .post("/links")
+        .authorization_bearer(&jwt)
+        .json(&json!({ "original_url": "https://iana.org/before" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create link: {}", res.text());
+    let user = users::Entity::find_by_id(user_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut active: users::ActiveModel = user.into();
+    active.display_name = Set(Some("Previous Owner".to_string()));
+    active.update(&db).await.unwrap();
+
+    let res = server
+        .post("/auth/delete-account")
+        .authorization_bearer(&jwt)
+        .json(&json!({ "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "self delete: {}", res.text());
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": email, "password": "different-pass1" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "re-register: {}", res.text());
+    let body: Value = res.json();
+    assert_eq!(body["user_id"], user_id);
+    assert_eq!(body["email_verified"], false);
+    let new_jwt = body["token"].as_str().unwrap().to_string();
+
+    let user = users::Entity::find_by_id(user_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(user.deleted_at.is_none());
+    assert!(!user.email_verified);
+    assert!(user.display_name.is_none());
+
+    // Only the new password works, the old session stays revoked and the
+    // deleted links are not handed to the new holder of the email.
+    for (password, status) in [("password123", 401), ("different-pass1", 200)] {
+        let res = server
+            .post("/auth/login")
+            .json(&json!({ "email": email, "password": password }))
+            .await;
+        assert_eq!(res.status_code(), status, "login: {}", res.text());
+    }
+    assert_eq!(
+        server
+            .get("/auth/me")
+            .authorization_bearer(&jwt)
+            .await
+            .status_code(),
+        401
+    );
+    let res = server.get("/links").authorization_bearer(&new_jwt).await;
+    assert_eq!(res.status_code(), 200);
+    assert_eq!(res.json::<Value>().as_array().unwrap().len(), 0);
+
+    // A deleted account that an admin had disabled keeps its email.
+    let email = unique_email();
+    let (_, user_id) = register(&server, &email).await;
+    let user = users::Entity::find_by_id(user_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut active: users::ActiveModel = user.into();
+    active.deleted_at = Set(Some(chrono::Utc::now().naive_utc()));
+    active.disabled_at = Set(Some(chrono::Utc::now().naive_utc()));
+    active.update(&db).await.unwrap();
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": email, "password": "password123" }))
+        .await;
+    assert_eq!(
+        res.status_code(),
+        409,
+        "disabled re-register: {}",
+        res.text()
+    );
+}
+
+#[tokio::test]
+async fn password_change_and_reset_revoke_every_older_session() {
+    let (server, db) = spawn_real_app().await;
+    let email = unique_email();
+    let (first_session, user_id) = register(&server, &email).await;
+    mark_email_verified(&db, user_id).await;
+
+    let login = |password: &'static str| {
+        let server = &server;
+        let email = email.clone();
+        async move {
+            let res = server
+                .post("/auth/login")
+                .json(&json!({ "email": email, "password": password }))
+                .await;
+            assert_eq!(res.status_code(), 200, "login: {}", res.text());
+            res.json::<Value>()["token"].as_str().unwrap().to_string()
+        }
+    };
+    // Probe outside /auth so the checks don't eat the auth rate-limit budget.
+    let session = |token: String| {
+        let server = &server;
+        async move {
+            server
+                .get("/links")
+                .authorization_bearer(&token)
+                .await
+                .status_code()
+        }
+    };
+
+    // A change made from one session logs out the others.
+    let second_session = login("password123").await;
+    let res = server
+        .post("/auth/change-password")
+        .authorization_bearer(&second_session)
+        .json(&json!({ "current_password": "password123", "new_password": "changed-pass1" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "change: {}", res.text());
+    let rotated = res.json::<Value>()["token"].as_str().unwrap().to_string();
+    assert_eq!(session(first_session).await, 401);
+    assert_eq!(session(second_session).await, 401);
+    assert_eq!(session(rotated.clone()).await, 200);
+
+    // A reset through the emailed token logs out everything minted before it.
+    let before_reset = login("changed-pass1").await;
+    let res = server
+        .post("/auth/forgot-password")
+        .json(&json!({ "email": email }))
+        .await;
+    assert_eq!(res.status_code(), 200, "forgot: {}", res.text());
+    let reset_token = users::Entity::find_by_id(user_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .password_reset_token
+        .unwrap();
+    let res = server
+        .post("/auth/reset-password")
+        .json(&json!({ "token": reset_token, "password": "reset-pass1" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "reset: {}", res.text());
+    assert_eq!(session(rotated).await, 401);
+    assert_eq!(session(before_reset).await, 401);
+    assert_eq!(session(login("reset-pass1").await).await, 200);
+}
+
 #[tokio::test]
 async fn admin_promotion_revokes_the_pre_promotion_jwt() {
     let (server, db) = spawn_real_app().await;
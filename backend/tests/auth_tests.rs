@@ -36,6 +36,42 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn register_validation_errors_are_listed_per_field() {
+        use serde_json::Value;
+        let (server, _db) = common::spawn_real_app().await;
+
+        let response = server
+            .post("/auth/register")
+            .json(&json!({ "email": "not-an-email", "password": "short" }))
+            .await;
+        assert_eq!(response.status_code(), 400, "{}", response.text());
+        let body: Value = response.json();
+        assert_eq!(
+            body["fields"]["email"],
+            json!(["Must be a valid email address"])
+        );
+        assert_eq!(
+            body["fields"]["password"],
+            json!(["Password must be at least 8 characters"])
+        );
+        let error = body["error"].as_str().unwrap();
+        assert!(
+            error.contains("email") && error.contains("password"),
+            "{error}"
+        );
+
+        // Only the failing field is listed.
+        let response = server
+            .post("/auth/register")
+            .json(&json!({ "email": common::unique_email(), "password": "short" }))
+            .await;
+        assert_eq!(response.status_code(), 400);
+        let body: Value = response.json();
+        assert!(body["fields"].get("email").is_none());
+        assert_eq!(body["fields"]["password"].as_array().unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_login_requires_credentials() {
         let (server, _db) = common::spawn_real_app().await;
@@ -51,6 +87,72 @@ mod tests {
             response.text()
         );
     }
+
+    #[tokio::test]
+    async fn login_and_me_list_org_memberships() {
+        use opn_onl_backend::entity::org_members;
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set};
+        use serde_json::Value;
+
+        let (server, db) = common::spawn_real_app().await;
+
+        // Two owners each create an org; the member joins both with different roles.
+        let mut orgs = Vec::new();
+        for name in ["Alpha Team", "Beta Team"] {
+            let res = server
+                .post("/auth/register")
+                .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+                .await;
+            let body: Value = res.json();
+            common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+            let slug = format!("team-{}", common::unique_code().to_lowercase());
+            let res = server
+                .post("/orgs")
+                .authorization_bearer(body["token"].as_str().unwrap())
+                .json(&json!({ "name": name, "slug": slug }))
+                .await;
+            assert_eq!(res.status_code(), 201, "create org: {}", res.text());
+            orgs.push((res.json::<Value>()["id"].as_i64().unwrap(), slug));
+        }
+
+        let email = common::unique_email();
+        let res = server
+            .post("/auth/register")
+            .json(&json!({ "email": email, "password": "password123" }))
+            .await;
+        let body: Value = res.json();
+        assert_eq!(body["organizations"], json!([]));
+        let user_id = body["user_id"].as_i64().unwrap() as i32;
+        for ((org_id, _), role) in orgs.iter().zip(["editor", "viewer"]) {
+            org_members::ActiveModel {
+                org_id: Set(*org_id as i32),
+                user_id: Set(user_id),
+                role: Set(role.to_string()),
+                ..Default::default()
+            }
+            .insert(&db)
+            .await
+            .unwrap();
+        }
+
+        let expected = json!([
+            { "id": orgs[0].0, "name": "Alpha Team", "slug": orgs[0].1, "role": "editor" },
+            { "id": orgs[1].0, "name": "Beta Team", "slug": orgs[1].1, "role": "viewer" },
+        ]);
+        let login: Value = server
+            .post("/auth/login")
+            .json(&json!({ "email": email, "password": "password123" }))
+            .await
+            .json();
+        assert_eq!(login["organizations"], expected);
+
+        let me: Value = server
+            .get("/auth/me")
+            .authorization_bearer(login["token"].as_str().unwrap())
+            .await
+            .json();
+        assert_eq!(me["organizations"], expected);
+    }
 }
 
 // Unit tests for JWT utilities
@@ -59,7 +161,6 @@ mod jwt_tests {
     use bcrypt::{hash, verify, DEFAULT_COST};
     use rand::distributions::Alphanumeric;
     use rand::{thread_rng, Rng};
-    use std::env;
 
     #[test]
     fn test_password_hashing_success() {
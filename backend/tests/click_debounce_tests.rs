@@ -0,0 +1,76 @@
+//! CLICK_DEBOUNCE_MS: repeat clicks on a link from the same visitor within
+//! the window count once; after it they count again. Lives in its own test
+//! binary because it sets process-wide env vars.
+
+mod common;
+
+use opn_onl_backend::entity::links;
+use sea_orm::EntityTrait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn repeat_clicks_within_the_window_count_once() {
+    std::env::set_var("CLICK_DEBOUNCE_MS", "1000");
+    std::env::set_var("FORCE_HTTPS", "false");
+    // The visitor is told apart by the proxy-supplied client IP.
+    std::env::set_var("TRUST_PROXY_HEADERS", "true");
+    if std::env::var("JWT_SECRET").is_err() {
+        std::env::set_var("JWT_SECRET", "integration-test-secret-0123456789abcdef");
+    }
+    // Build the app by hand to keep a handle on its click buffer.
+    let db = common::setup_test_db().await;
+    let state = opn_onl_backend::AppState::for_tests(db.clone()).await;
+    let buffer = Arc::clone(&state.click_buffer);
+    let server = axum_test::TestServer::new(opn_onl_backend::build_router(state)).unwrap();
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/debounce" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let link: Value = res.json();
+    let id = link["id"].as_i64().unwrap() as i32;
+    let code = link["code"].as_str().unwrap();
+
+    let click = |ip: &'static str| {
+        server
+            .get(&format!("/{code}"))
+            .add_header("cf-connecting-ip", ip)
+    };
+    let click_count = || async {
+        buffer.flush(&db).await;
+        links::Entity::find_by_id(id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .click_count
+    };
+
+    // A double-click counts once, but still redirects both times.
+    for _ in 0..2 {
+        assert_eq!(click("203.0.113.7").await.status_code(), 307);
+    }
+    assert_eq!(click_count().await, 1);
+
+    // Another visitor has their own window.
+    assert_eq!(click("203.0.113.8").await.status_code(), 307);
+    assert_eq!(click_count().await, 2);
+
+    // Once the window has passed the same visitor counts again.
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    assert_eq!(click("203.0.113.7").await.status_code(), 307);
+    assert_eq!(click_count().await, 3);
+
+    std::env::remove_var("CLICK_DEBOUNCE_MS");
+}
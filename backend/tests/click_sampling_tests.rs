@@ -0,0 +1,113 @@
+//! CLICK_SAMPLING_THRESHOLD / CLICK_SAMPLE_RATE: past the threshold only
+//! 1-in-N click events of a link are stored, each carrying its sample rate,
+//! while click_count stays exact and analytics totals extrapolate to match. Lives in its own test binary because it
+//! sets process-wide env vars.
+
+mod common;
+
+use opn_onl_backend::entity::{click_events, links};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn hot_link_stores_sampled_events_but_counts_every_click() {
+    std::env::set_var("CLICK_SAMPLING_THRESHOLD", "5");
+    std::env::set_var("CLICK_SAMPLE_RATE", "5");
+    std::env::set_var("FORCE_HTTPS", "false");
+    std::env::set_var("TRUST_PROXY_HEADERS", "false");
+    if std::env::var("JWT_SECRET").is_err() {
+        std::env::set_var("JWT_SECRET", "integration-test-secret-0123456789abcdef");
+    }
+    // Build the app by hand to keep a handle on its click buffer.
+    let db = common::setup_test_db().await;
+    let state = opn_onl_backend::AppState::for_tests(db.clone()).await;
+    let buffer = Arc::clone(&state.click_buffer);
+    let server = axum_test::TestServer::new(opn_onl_backend::build_router(state)).unwrap();
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/sampling" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let link: Value = res.json();
+    let id = link["id"].as_i64().unwrap() as i32;
+    let code = link["code"].as_str().unwrap();
+
+    for _ in 0..30 {
+        let res = server.get(&format!("/{code}")).await;
+        assert_eq!(res.status_code(), 307);
+    }
+    buffer.flush(&db).await;
+
+    let events = click_events::Entity::find()
+        .filter(click_events::Column::LinkId.eq(id))
+        .all(&db)
+        .await
+        .unwrap();
+    // 5 below the threshold, then 1 in 5 of the other 25.
+    assert_eq!(events.len(), 10, "only a fraction of events is stored");
+    assert_eq!(events.iter().filter(|e| e.sample_rate == 1).count(), 5);
+    assert_eq!(events.iter().filter(|e| e.sample_rate == 5).count(), 5);
+    assert_eq!(events.iter().map(|e| e.sample_rate).sum::<i32>(), 30);
+
+    let link = links::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(link.click_count, 30, "every click still counts");
+
+    // Analytics extrapolate from the sample rather than counting rows.
+    let stats: Value = server
+        .get(&format!("/links/{id}/stats"))
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(stats["total_clicks"], 30);
+    let by_day: i64 = stats["clicks_by_day"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["count"].as_i64().unwrap())
+        .sum();
+    assert_eq!(by_day, 30);
+    let page: Value = server
+        .get(&format!("/links/{id}/clicks"))
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(page["total"], 10);
+    assert_eq!(page["total_clicks"], 30);
+    let heatmap: Value = server
+        .get(&format!("/links/{id}/heatmap"))
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(heatmap["total"], 30);
+    let compare: Value = server
+        .get(&format!("/links/{id}/compare?period=day"))
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(compare["current_clicks"], 30);
+    let dashboard: Value = server
+        .get("/analytics/dashboard")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(dashboard["clicks_this_month"], 30);
+    assert_eq!(dashboard["total_clicks"], 30);
+
+    std::env::remove_var("CLICK_SAMPLING_THRESHOLD");
+    std::env::remove_var("CLICK_SAMPLE_RATE");
+}
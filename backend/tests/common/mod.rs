@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use sea_orm::{Database, DatabaseConnection};
 use std::env;
 
@@ -143,6 +145,22 @@ pub async fn spawn_real_app_ws_with_interval(
     spawn_real_app_ws_with_state(ws_state).await
 }
 
+/// WebSocket/SSE app with a short ping interval, for heartbeat tests.
+#[allow(dead_code)]
+pub async fn spawn_real_app_ws_with_ping(
+    ping_interval: std::time::Duration,
+) -> (
+    axum_test::TestServer,
+    DatabaseConnection,
+    std::sync::Arc<opn_onl_backend::handlers::websocket::WsState>,
+) {
+    use std::sync::Arc;
+    let ws_state = Arc::new(
+        opn_onl_backend::handlers::websocket::WsState::new().with_ping_interval(ping_interval),
+    );
+    spawn_real_app_ws_with_state(ws_state).await
+}
+
 async fn spawn_real_app_ws_with_state(
     ws_state: std::sync::Arc<opn_onl_backend::handlers::websocket::WsState>,
 ) -> (
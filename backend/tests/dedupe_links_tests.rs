@@ -0,0 +1,93 @@
+//! NORMALIZE_URLS / DEDUPE_LINKS: destinations are stored in canonical form
+//! and re-shortening one the caller already has returns the existing link.
+//! Lives in its own test binary because it sets process-wide env vars.
+
+mod common;
+
+use opn_onl_backend::entity::links;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn normalized_duplicate_returns_existing_link() {
+    std::env::set_var("NORMALIZE_URLS", "true");
+    std::env::set_var("DEDUPE_LINKS", "true");
+    let (server, db) = common::spawn_real_app().await;
+
+    let mut tokens = Vec::new();
+    for _ in 0..2 {
+        let res = server
+            .post("/auth/register")
+            .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+            .await;
+        let body: Value = res.json();
+        common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+        tokens.push(body["token"].as_str().unwrap().to_string());
+    }
+    let path = common::unique_code();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&tokens[0])
+        .json(&json!({ "original_url": format!("HTTPS://IANA.ORG:443/{path}?") }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let first: Value = res.json();
+    assert_eq!(first["original_url"], format!("https://iana.org/{path}"));
+
+    // Same destination, different spelling: the first link comes back.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&tokens[0])
+        .json(&json!({ "original_url": format!("https://iana.org./{path}") }))
+        .await;
+    assert_eq!(res.status_code(), 200, "dedupe: {}", res.text());
+    assert_eq!(res.header("x-duplicate-link"), "true");
+    assert_eq!(res.json::<Value>()["id"], first["id"]);
+
+    // A link stored before normalization was switched on still matches.
+    let id = first["id"].as_i64().unwrap() as i32;
+    let mut stored: links::ActiveModel = links::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    stored.original_url = Set(format!("https://IANA.org/{path}#"));
+    stored.update(&db).await.unwrap();
+    let res = server
+        .post("/links")
+        .authorization_bearer(&tokens[0])
+        .json(&json!({ "original_url": format!("https://iana.org/{path}") }))
+        .await;
+    assert_eq!(res.status_code(), 200, "legacy dedupe: {}", res.text());
+    assert_eq!(res.json::<Value>()["id"], first["id"]);
+
+    // An explicit alias, another user or another path all get a new link.
+    let alias = common::unique_code();
+    for (token, body) in [
+        (
+            &tokens[0],
+            json!({ "original_url": format!("https://iana.org/{path}"), "custom_alias": alias }),
+        ),
+        (
+            &tokens[1],
+            json!({ "original_url": format!("https://iana.org/{path}") }),
+        ),
+        (
+            &tokens[0],
+            json!({ "original_url": format!("https://iana.org/{path}/") }),
+        ),
+    ] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(token)
+            .json(&body)
+            .await;
+        assert_eq!(res.status_code(), 201, "{body}: {}", res.text());
+        assert_ne!(res.json::<Value>()["id"], first["id"]);
+    }
+
+    std::env::remove_var("NORMALIZE_URLS");
+    std::env::remove_var("DEDUPE_LINKS");
+}
@@ -0,0 +1,91 @@
+//! DELETED_SLUG_COOLDOWN_DAYS: an alias from a link deleted longer ago than
+//! the cooldown can be claimed again; a recently deleted one can't. Lives in
+//! its own test binary because it sets a process-wide env var.
+
+mod common;
+
+use opn_onl_backend::entity::links;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn old_deleted_alias_is_reusable_recent_one_is_not() {
+    std::env::set_var("DELETED_SLUG_COOLDOWN_DAYS", "30");
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let mut deleted = Vec::new();
+    for path in ["old", "recent"] {
+        let alias = common::unique_code();
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": format!("https://iana.org/{path}"), "custom_alias": alias }))
+            .await;
+        assert_eq!(res.status_code(), 201, "create: {}", res.text());
+        let id = res.json::<Value>()["id"].as_i64().unwrap() as i32;
+        let res = server
+            .delete(&format!("/links/{id}"))
+            .authorization_bearer(&token)
+            .await;
+        assert!(res.status_code().is_success(), "delete: {}", res.text());
+        deleted.push((id, alias));
+    }
+
+    // Age the first deletion past the cooldown.
+    let (old_id, old_alias) = &deleted[0];
+    let mut old: links::ActiveModel = links::Entity::find_by_id(*old_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    old.deleted_at = Set(Some(
+        (chrono::Utc::now() - chrono::Duration::days(31)).naive_utc(),
+    ));
+    old.update(&db).await.unwrap();
+
+    let (_, recent_alias) = &deleted[1];
+    for (alias, available) in [(old_alias, true), (recent_alias, false)] {
+        let res: Value = server
+            .get(&format!("/links/check-code?code={alias}"))
+            .await
+            .json();
+        assert_eq!(res["available"], available, "check-code {alias}: {res}");
+    }
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/again", "custom_alias": old_alias }))
+        .await;
+    assert_eq!(res.status_code(), 201, "reuse old alias: {}", res.text());
+    assert_eq!(res.json::<Value>()["code"], old_alias.as_str());
+    let redirect = server.get(&format!("/{old_alias}")).await;
+    assert_eq!(redirect.header("location"), "https://iana.org/again");
+
+    // The deleted link keeps its row under a freed-up code.
+    let old = links::Entity::find_by_id(*old_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(old.code, format!("{old_alias}~{old_id}"));
+    assert!(old.deleted_at.is_some());
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/again", "custom_alias": recent_alias }))
+        .await;
+    assert_eq!(res.status_code(), 409, "reuse recent alias: {}", res.text());
+
+    std::env::remove_var("DELETED_SLUG_COOLDOWN_DAYS");
+}
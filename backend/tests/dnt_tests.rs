@@ -0,0 +1,84 @@
+//! RESPECT_DNT: a visitor sending `DNT: 1` is still redirected and counted,
+//! but no per-visitor click event is stored. Lives in its own test binary
+//! because it sets a process-wide env var.
+
+mod common;
+
+use opn_onl_backend::entity::{click_events, links};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn dnt_click_is_counted_without_a_click_event() {
+    std::env::set_var("RESPECT_DNT", "true");
+    std::env::set_var("FORCE_HTTPS", "false");
+    std::env::set_var("TRUST_PROXY_HEADERS", "false");
+    if std::env::var("JWT_SECRET").is_err() {
+        std::env::set_var("JWT_SECRET", "integration-test-secret-0123456789abcdef");
+    }
+    // Build the app by hand to keep a handle on its click buffer.
+    let db = common::setup_test_db().await;
+    let state = opn_onl_backend::AppState::for_tests(db.clone()).await;
+    let buffer = Arc::clone(&state.click_buffer);
+    let server = axum_test::TestServer::new(opn_onl_backend::build_router(state)).unwrap();
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/dnt" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let link: Value = res.json();
+    let id = link["id"].as_i64().unwrap() as i32;
+    let code = link["code"].as_str().unwrap();
+
+    let res = server
+        .get(&format!("/{code}"))
+        .add_header("DNT", "1")
+        .add_header(
+            "User-Agent",
+            "Mozilla/5.0 (X11; Linux x86_64) Firefox/130.0",
+        )
+        .await;
+    assert_eq!(res.status_code(), 307);
+    assert_eq!(res.header("location"), "https://iana.org/dnt");
+    buffer.flush(&db).await;
+
+    let events = || {
+        click_events::Entity::find()
+            .filter(click_events::Column::LinkId.eq(id))
+            .count(&db)
+    };
+    let click_count = || async {
+        links::Entity::find_by_id(id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .click_count
+    };
+    assert_eq!(
+        events().await.unwrap(),
+        0,
+        "DNT click must not store an event"
+    );
+    assert_eq!(click_count().await, 1, "DNT click still counts");
+
+    // Without the header the click is recorded as usual.
+    let res = server.get(&format!("/{code}")).await;
+    assert_eq!(res.status_code(), 307);
+    buffer.flush(&db).await;
+    assert_eq!(events().await.unwrap(), 1);
+    assert_eq!(click_count().await, 2);
+
+    std::env::remove_var("RESPECT_DNT");
+}
@@ -1,12 +1,9 @@
 //! Edge case tests for comprehensive coverage
 
-use std::collections::HashMap;
-
 // ============= URL Validation Edge Cases =============
 
 #[cfg(test)]
 mod url_validation {
-    use super::*;
 
     fn is_valid_url(url: &str) -> bool {
         url::Url::parse(url).is_ok()
@@ -316,7 +313,7 @@ mod rate_limiting_edge_cases {
         fn is_allowed(&self, key: &str) -> bool {
             let now = Instant::now();
             let mut requests = self.requests.lock().unwrap();
-            let entry = requests.entry(key.to_string()).or_insert_with(Vec::new);
+            let entry = requests.entry(key.to_string()).or_default();
 
             // Remove old requests
             entry.retain(|t| now.duration_since(*t) < self.window);
@@ -692,6 +689,7 @@ mod tag_name_validation {
 
 #[cfg(test)]
 mod folder_hierarchy {
+    #[allow(dead_code)]
     struct Folder {
         id: i32,
         name: String,
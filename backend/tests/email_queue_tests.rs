@@ -0,0 +1,100 @@
+//! Persistent email queue: enqueue, claim (with lease), retry scheduling and
+//! removal once sent.
+
+mod common;
+
+use chrono::{Duration, Timelike, Utc};
+use opn_onl_backend::entity::email_queue;
+use opn_onl_backend::utils::email_queue::{
+    claim_due, enqueue, mark_attempt_failed, mark_sent, retry_delay,
+};
+use sea_orm::EntityTrait;
+
+#[tokio::test]
+async fn enqueue_claim_retry_and_send_cycle() {
+    let (_server, db) = common::spawn_real_app().await;
+
+    let to = common::unique_email();
+    let id = enqueue(&db, &to, "Hello", "<p>hi</p>", Some("reply@example.com"))
+        .await
+        .unwrap();
+    let ours = |rows: Vec<email_queue::Model>| -> Vec<email_queue::Model> {
+        rows.into_iter().filter(|r| r.id == id).collect()
+    };
+
+    // Whole seconds so round-tripped timestamps compare equal.
+    let now = (Utc::now() + Duration::seconds(1))
+        .naive_utc()
+        .with_nanosecond(0)
+        .unwrap();
+
+    // Due immediately; claiming counts the attempt.
+    let claimed = ours(claim_due(&db, now, 100).await.unwrap());
+    assert_eq!(claimed.len(), 1);
+    let row = &claimed[0];
+    assert_eq!(row.attempts, 1);
+    assert_eq!(row.recipient, to);
+    assert_eq!(row.reply_to.as_deref(), Some("reply@example.com"));
+
+    // Leased: a second worker doesn't get it.
+    assert!(ours(claim_due(&db, now, 100).await.unwrap()).is_empty());
+
+    // A failed attempt is retried after the backoff, not before.
+    mark_attempt_failed(&db, row, "connection refused", now)
+        .await
+        .unwrap();
+    let stored = email_queue::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.next_attempt_at, now + retry_delay(1));
+    assert_eq!(stored.last_error.as_deref(), Some("connection refused"));
+    assert!(stored.failed_at.is_none());
+    assert!(ours(
+        claim_due(&db, now + retry_delay(1) - Duration::seconds(1), 100)
+            .await
+            .unwrap()
+    )
+    .is_empty());
+    let retried = ours(claim_due(&db, now + retry_delay(1), 100).await.unwrap());
+    assert_eq!(retried.len(), 1);
+    assert_eq!(retried[0].attempts, 2);
+
+    // Delivered rows leave the queue.
+    mark_sent(&db, id).await.unwrap();
+    assert!(email_queue::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Out of attempts: marked failed and never claimed again.
+    let id = enqueue(&db, &common::unique_email(), "Hello", "<p>hi</p>", None)
+        .await
+        .unwrap();
+    let now = now + Duration::hours(1);
+    let mut row = claim_due(&db, now, 100)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.id == id)
+        .unwrap();
+    // Pretend this was the last allowed attempt (default EMAIL_MAX_ATTEMPTS=5).
+    row.attempts = 5;
+    mark_attempt_failed(&db, &row, "mailbox unavailable", now)
+        .await
+        .unwrap();
+
+    let stored = email_queue::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.failed_at, Some(now));
+    assert!(!claim_due(&db, now + Duration::days(1), 100)
+        .await
+        .unwrap()
+        .iter()
+        .any(|r| r.id == id));
+}
@@ -32,6 +32,19 @@ fn base_link() -> links::Model {
         bio_visible: false,
         bio_position: None,
         bio_label: None,
+        notify_on_start: false,
+        start_notified: false,
+        use_interstitial: false,
+        redirect_rate_limit: None,
+        favicon_url: None,
+        disabled_by_admin: false,
+        hide_click_count: false,
+        ttl_after_first_click_secs: None,
+        is_reserved: false,
+        last_checked: None,
+        last_status: None,
+        creation_token_hash: None,
+        expired_page_html: None,
     }
 }
 
@@ -133,3 +146,17 @@ fn one_time_link_at_cap_reports_burn_reason_over_max_clicks() {
         Some("This one-time link has already been opened")
     );
 }
+
+#[test]
+fn admin_disabled_link_reports_policy_reason_first() {
+    let link = links::Model {
+        disabled_by_admin: true,
+        expires_at: Some((Utc::now() - Duration::hours(1)).naive_utc()),
+        ..base_link()
+    };
+    assert!(!link.is_active());
+    assert_eq!(
+        link.inactive_reason(),
+        Some(links::DISABLED_BY_ADMIN_REASON)
+    );
+}
@@ -1,7 +1,5 @@
 //! Comprehensive folder integration tests
 
-use chrono::Utc;
-
 /// Mock folder for testing
 struct MockFolder {
     id: i32,
@@ -179,13 +177,12 @@ mod ownership_tests {
 // ============= Folder Operations Tests =============
 
 mod operations_tests {
-    use super::*;
 
     #[test]
     fn test_move_link_to_folder() {
         // Simulate moving a link to a folder
         let folder_id = 1;
-        let link_folder_id: Option<i32> = None;
+        let _link_folder_id: Option<i32> = None;
 
         // Move link to folder
         let new_folder_id = Some(folder_id);
@@ -197,7 +194,7 @@ mod operations_tests {
     #[test]
     fn test_remove_link_from_folder() {
         // Simulate removing a link from a folder
-        let link_folder_id: Option<i32> = Some(1);
+        let _link_folder_id: Option<i32> = Some(1);
 
         // Remove from folder
         let new_folder_id: Option<i32> = None;
@@ -209,7 +206,7 @@ mod operations_tests {
     fn test_bulk_move_links() {
         // Simulate bulk moving links to a folder
         let link_ids = vec![1, 2, 3, 4, 5];
-        let target_folder_id = 10;
+        let _target_folder_id = 10;
 
         let mut moved_count = 0;
         for _id in &link_ids {
@@ -1,9 +1,11 @@
 //! GeoIP and User Agent parsing tests
 
+#![allow(dead_code)]
+
 #[path = "../src/utils/geoip.rs"]
 mod geoip;
 
-use geoip::{lookup_ip, parse_user_agent, GeoLocation, UserAgentInfo};
+use geoip::{lookup_ip, parse_user_agent, GeoCache, GeoLocation, GeoPrecision};
 
 mod browser_detection {
     use super::*;
@@ -145,3 +147,106 @@ mod ip_lookup {
         let _ = result;
     }
 }
+
+mod geo_cache {
+    use super::*;
+    use std::cell::Cell;
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    fn counting_lookup<'a>(calls: &'a Cell<u32>) -> impl Fn(&str) -> GeoLocation + 'a {
+        move |ip| {
+            calls.set(calls.get() + 1);
+            GeoLocation {
+                city: Some(format!("city-of-{ip}")),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_ip_is_served_from_cache() {
+        let cache = GeoCache::new(NonZeroUsize::new(8).unwrap(), Duration::from_secs(60));
+        let calls = Cell::new(0);
+        let lookup = counting_lookup(&calls);
+
+        let first = cache.get_or_lookup("203.0.113.7", &lookup);
+        let second = cache.get_or_lookup("203.0.113.7", &lookup);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(first.city, second.city);
+
+        cache.get_or_lookup("203.0.113.8", &lookup);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn least_recently_used_ip_is_evicted() {
+        let cache = GeoCache::new(NonZeroUsize::new(2).unwrap(), Duration::from_secs(60));
+        let calls = Cell::new(0);
+        let lookup = counting_lookup(&calls);
+
+        cache.get_or_lookup("203.0.113.1", &lookup);
+        cache.get_or_lookup("203.0.113.2", &lookup);
+        cache.get_or_lookup("203.0.113.1", &lookup);
+        cache.get_or_lookup("203.0.113.3", &lookup);
+        assert_eq!(calls.get(), 3);
+
+        // .2 was the least recently used, so it's looked up again.
+        cache.get_or_lookup("203.0.113.1", &lookup);
+        cache.get_or_lookup("203.0.113.2", &lookup);
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn expired_entry_is_looked_up_again() {
+        let cache = GeoCache::new(NonZeroUsize::new(8).unwrap(), Duration::ZERO);
+        let calls = Cell::new(0);
+        let lookup = counting_lookup(&calls);
+
+        cache.get_or_lookup("203.0.113.9", &lookup);
+        cache.get_or_lookup("203.0.113.9", &lookup);
+        assert_eq!(calls.get(), 2);
+    }
+}
+
+mod geo_precision {
+    use super::*;
+
+    fn berlin() -> GeoLocation {
+        GeoLocation {
+            country: Some("Germany".to_string()),
+            country_code: Some("DE".to_string()),
+            city: Some("Berlin".to_string()),
+            region: Some("Land Berlin".to_string()),
+            latitude: Some(52.52),
+            longitude: Some(13.405),
+        }
+    }
+
+    #[test]
+    fn country_precision_nulls_city_region_and_coordinates() {
+        let geo = GeoPrecision::Country.apply(berlin());
+        assert_eq!(geo.country.as_deref(), Some("Germany"));
+        assert_eq!(geo.country_code.as_deref(), Some("DE"));
+        assert_eq!(geo.region, None);
+        assert_eq!(geo.city, None);
+        assert_eq!(geo.latitude, None);
+        assert_eq!(geo.longitude, None);
+    }
+
+    #[test]
+    fn region_precision_keeps_region_only() {
+        let geo = GeoPrecision::Region.apply(berlin());
+        assert_eq!(geo.region.as_deref(), Some("Land Berlin"));
+        assert_eq!(geo.city, None);
+        assert_eq!(geo.latitude, None);
+    }
+
+    #[test]
+    fn city_precision_keeps_everything() {
+        let geo = GeoPrecision::City.apply(berlin());
+        assert_eq!(geo.city.as_deref(), Some("Berlin"));
+        assert_eq!(geo.latitude, Some(52.52));
+        assert_eq!(geo.longitude, Some(13.405));
+    }
+}
@@ -0,0 +1,40 @@
+//! FORCE_HTTPS + ALLOWED_HOSTS: the HTTPS redirect only reflects allowlisted
+//! `Host` headers. Lives in its own test binary because it sets process-wide
+//! env vars.
+
+mod common;
+
+use axum::http::{header, HeaderValue};
+
+#[tokio::test]
+async fn spoofed_host_is_not_reflected_into_https_redirect() {
+    let (server, _db) = common::spawn_real_app().await;
+    std::env::set_var("FORCE_HTTPS", "true");
+    std::env::set_var("ALLOWED_HOSTS", "l.opn.onl, API.opn.onl");
+    std::env::set_var("BASE_URL", "https://api.opn.onl");
+
+    for (host, expected) in [
+        ("l.opn.onl", "https://l.opn.onl/health?x=1"),
+        ("api.OPN.onl", "https://api.opn.onl/health?x=1"),
+        ("evil.test", "https://api.opn.onl/health?x=1"),
+        ("l.opn.onl.evil.test", "https://api.opn.onl/health?x=1"),
+    ] {
+        let res = server
+            .get("/health?x=1")
+            .add_header(header::HOST, HeaderValue::from_static(host))
+            .await;
+        assert_eq!(res.status_code(), 308, "{host}");
+        assert_eq!(res.header("location"), expected, "{host}");
+    }
+
+    // With no allowlist every host falls back to BASE_URL.
+    std::env::remove_var("ALLOWED_HOSTS");
+    let res = server
+        .get("/health")
+        .add_header(header::HOST, HeaderValue::from_static("l.opn.onl"))
+        .await;
+    assert_eq!(res.header("location"), "https://api.opn.onl/health");
+
+    std::env::set_var("FORCE_HTTPS", "false");
+    std::env::remove_var("BASE_URL");
+}
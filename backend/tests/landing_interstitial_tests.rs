@@ -0,0 +1,65 @@
+//! Landing interstitial: a countdown page instead of the 302, per link or
+//! instance-wide via USE_INTERSTITIAL. Lives in its own test binary because it
+//! sets a process-wide env var; the steps run in one test so they can't
+//! interleave.
+
+mod common;
+
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn interstitial_page_versus_instant_redirect() {
+    std::env::remove_var("USE_INTERSTITIAL");
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let mut codes = Vec::new();
+    for (path, interstitial) in [("instant", false), ("landing", true)] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({
+                "original_url": format!("https://iana.org/{path}"),
+                "use_interstitial": interstitial,
+            }))
+            .await;
+        assert_eq!(res.status_code(), 201, "create: {}", res.text());
+        let body: Value = res.json();
+        assert_eq!(body["use_interstitial"], interstitial);
+        codes.push(body["code"].as_str().unwrap().to_string());
+    }
+
+    // Default: instant redirect.
+    let res = server.get(&format!("/{}", codes[0])).await;
+    assert_eq!(res.status_code(), 307);
+    assert_eq!(res.header("location"), "https://iana.org/instant");
+
+    // Per-link opt-in: countdown page pointing at the destination.
+    let res = server.get(&format!("/{}", codes[1])).await;
+    assert_eq!(res.status_code(), 200);
+    assert!(res
+        .header("content-type")
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+    let html = res.text();
+    assert!(
+        html.contains(r#"href="https://iana.org/landing""#),
+        "{html}"
+    );
+    assert!(html.contains("http-equiv=\"refresh\""));
+
+    // Instance-wide mode applies to every link.
+    std::env::set_var("USE_INTERSTITIAL", "true");
+    let res = server.get(&format!("/{}", codes[0])).await;
+    std::env::remove_var("USE_INTERSTITIAL");
+    assert_eq!(res.status_code(), 200);
+    assert!(res.text().contains("https://iana.org/instant"));
+}
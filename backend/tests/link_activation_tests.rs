@@ -0,0 +1,151 @@
+//! Scheduled activation sweep: links whose `starts_at` has just passed and
+//! that haven't been handled yet are selected once, then never again.
+
+mod common;
+
+use chrono::{Duration, Utc};
+use opn_onl_backend::entity::links;
+use opn_onl_backend::utils::link_activation::{claim_due_links, links_due_for_activation};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn selects_links_whose_start_just_passed_and_were_not_notified() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "register failed: {}", res.text());
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let in_an_hour = Utc::now() + Duration::hours(1);
+    let mut ids = Vec::new();
+    for path in ["due", "already-notified", "still-future", "deleted"] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({
+                "original_url": format!("https://iana.org/{path}"),
+                "starts_at": in_an_hour.to_rfc3339(),
+                "notify_on_start": true,
+            }))
+            .await;
+        assert_eq!(res.status_code(), 201, "create failed: {}", res.text());
+        let body: Value = res.json();
+        assert_eq!(body["notify_on_start"], true);
+        ids.push(body["id"].as_i64().unwrap() as i32);
+    }
+    let (due, notified, deleted) = (ids[0], ids[1], ids[3]);
+
+    // Simulate the clock passing the start time for all but "still-future".
+    let a_minute_ago = (Utc::now() - Duration::minutes(1)).naive_utc();
+    for id in [due, notified, deleted] {
+        links::ActiveModel {
+            id: Set(id),
+            starts_at: Set(Some(a_minute_ago)),
+            ..Default::default()
+        }
+        .update(&db)
+        .await
+        .unwrap();
+    }
+    links::ActiveModel {
+        id: Set(notified),
+        start_notified: Set(true),
+        ..Default::default()
+    }
+    .update(&db)
+    .await
+    .unwrap();
+    links::ActiveModel {
+        id: Set(deleted),
+        deleted_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    }
+    .update(&db)
+    .await
+    .unwrap();
+
+    let now = Utc::now().naive_utc();
+    let selected: Vec<i32> = links_due_for_activation(&db, now)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|l| l.id)
+        .filter(|id| ids.contains(id))
+        .collect();
+    assert_eq!(selected, vec![due]);
+
+    // Claiming flips the flag so the next sweep skips it.
+    let claimed: Vec<i32> = claim_due_links(&db, now)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|l| l.id)
+        .filter(|id| ids.contains(id))
+        .collect();
+    assert_eq!(claimed, vec![due]);
+    let link = links::Entity::find_by_id(due)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(link.start_notified);
+    assert!(!links_due_for_activation(&db, now)
+        .await
+        .unwrap()
+        .iter()
+        .any(|l| ids.contains(&l.id)));
+}
+
+#[tokio::test]
+async fn rescheduling_rearms_the_activation_notice() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    // Already-started schedules are born handled.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "original_url": "https://iana.org/rearm",
+            "starts_at": (Utc::now() - Duration::hours(1)).to_rfc3339(),
+        }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create failed: {}", res.text());
+    let id = res.json::<Value>()["id"].as_i64().unwrap() as i32;
+    let link = links::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(link.start_notified);
+
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({
+            "starts_at": (Utc::now() + Duration::hours(2)).to_rfc3339(),
+            "notify_on_start": true,
+        }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update failed: {}", res.text());
+    let link = links::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!link.start_notified);
+    assert!(link.notify_on_start);
+}
@@ -0,0 +1,430 @@
+//! Expired links: which rows count as expired, the admin listing, and the
+//! grace-period compaction that soft-deletes long-expired links.
+
+mod common;
+
+use chrono::{Duration, Utc};
+use opn_onl_backend::entity::{links, users};
+use opn_onl_backend::utils::link_expiry::{compact_expired_links, expired_links_query};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn selects_and_compacts_expired_links() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "register failed: {}", res.text());
+    let body: Value = res.json();
+    let user_id = body["user_id"].as_i64().unwrap() as i32;
+    common::mark_email_verified(&db, user_id).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let mut ids = Vec::new();
+    for path in [
+        "no-expiry",
+        "future",
+        "just-expired",
+        "long-expired",
+        "deleted",
+    ] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": format!("https://iana.org/{path}") }))
+            .await;
+        assert_eq!(res.status_code(), 201, "create failed: {}", res.text());
+        ids.push(res.json::<Value>()["id"].as_i64().unwrap() as i32);
+    }
+    let (future, just_expired, long_expired, deleted) = (ids[1], ids[2], ids[3], ids[4]);
+
+    let now = Utc::now().naive_utc();
+    for (id, expires_at) in [
+        (future, now + Duration::days(1)),
+        (just_expired, now - Duration::hours(1)),
+        (long_expired, now - Duration::days(30)),
+        (deleted, now - Duration::days(30)),
+    ] {
+        links::ActiveModel {
+            id: Set(id),
+            expires_at: Set(Some(expires_at)),
+            ..Default::default()
+        }
+        .update(&db)
+        .await
+        .unwrap();
+    }
+    links::ActiveModel {
+        id: Set(deleted),
+        deleted_at: Set(Some(now)),
+        ..Default::default()
+    }
+    .update(&db)
+    .await
+    .unwrap();
+
+    let selected: Vec<i32> = expired_links_query(now)
+        .all(&db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|l| l.id)
+        .filter(|id| ids.contains(id))
+        .collect();
+    assert_eq!(selected, vec![just_expired, long_expired]);
+
+    // Expired but present links still answer 410.
+    let code = links::Entity::find_by_id(just_expired)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .code;
+    assert_eq!(server.get(&format!("/{code}")).await.status_code(), 410);
+
+    // The admin listing is admin-only and shows the same rows.
+    assert_eq!(
+        server
+            .get("/admin/links/expired")
+            .authorization_bearer(&token)
+            .await
+            .status_code(),
+        403
+    );
+    users::ActiveModel {
+        id: Set(user_id),
+        is_admin: Set(true),
+        ..Default::default()
+    }
+    .update(&db)
+    .await
+    .unwrap();
+    let res = server
+        .get("/admin/links/expired?per_page=100")
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let listed: Vec<i64> = res.json::<Value>()["links"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["id"].as_i64().unwrap())
+        .filter(|id| ids.contains(&(*id as i32)))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![just_expired as i64, long_expired as i64],
+        "admin listing"
+    );
+
+    // A week's grace only takes the link that expired a month ago.
+    let compacted: Vec<i32> = compact_expired_links(&db, now, 7)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|l| l.id)
+        .filter(|id| ids.contains(id))
+        .collect();
+    assert_eq!(compacted, vec![long_expired]);
+    let link = links::Entity::find_by_id(long_expired)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(link.deleted_at.is_some());
+    let link = links::Entity::find_by_id(just_expired)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(link.deleted_at.is_none());
+}
+
+#[tokio::test]
+async fn expired_link_reports_inactive_reason() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let mut ids = Vec::new();
+    for path in ["live", "expired"] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": format!("https://iana.org/{path}") }))
+            .await;
+        assert_eq!(res.status_code(), 201, "create failed: {}", res.text());
+        let created: Value = res.json();
+        assert!(created["inactive_reason"].is_null(), "{created}");
+        ids.push(created["id"].as_i64().unwrap() as i32);
+    }
+
+    let mut expired: links::ActiveModel = links::Entity::find_by_id(ids[1])
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    expired.expires_at = Set(Some((Utc::now() - Duration::hours(1)).naive_utc()));
+    expired.update(&db).await.unwrap();
+
+    let listed: Vec<Value> = server
+        .get("/links")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    let find = |id: i32| {
+        listed
+            .iter()
+            .find(|l| l["id"] == id)
+            .unwrap_or_else(|| panic!("link {id} missing from {listed:?}"))
+    };
+    assert_eq!(find(ids[0])["is_active"], true);
+    assert!(find(ids[0])["inactive_reason"].is_null());
+    assert_eq!(find(ids[1])["is_active"], false);
+    assert_eq!(find(ids[1])["inactive_reason"], "Link has expired");
+
+    // A link scheduled for later is inactive from the moment it's created.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "original_url": "https://iana.org/scheduled",
+            "starts_at": (Utc::now() + Duration::days(1)).to_rfc3339(),
+        }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create failed: {}", res.text());
+    let scheduled: Value = res.json();
+    assert_eq!(scheduled["is_active"], false);
+    assert_eq!(
+        scheduled["inactive_reason"],
+        "Link is scheduled to activate later"
+    );
+}
+
+#[tokio::test]
+async fn expired_redirect_answers_json_clients_with_structured_410() {
+    use axum::http::{header, HeaderValue};
+    use chrono::Timelike;
+
+    let (server, db) = common::spawn_real_app().await;
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/gone" }))
+        .await;
+    let created: Value = res.json();
+    let code = created["code"].as_str().unwrap().to_string();
+    let expires_at = (Utc::now() - Duration::hours(1))
+        .naive_utc()
+        .with_nanosecond(0)
+        .unwrap();
+    let mut link: links::ActiveModel =
+        links::Entity::find_by_id(created["id"].as_i64().unwrap() as i32)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+    link.expires_at = Set(Some(expires_at));
+    link.update(&db).await.unwrap();
+
+    let res = server
+        .get(&format!("/{code}"))
+        .add_header(header::ACCEPT, HeaderValue::from_static("application/json"))
+        .await;
+    assert_eq!(res.status_code(), 410);
+    let gone: Value = res.json();
+    assert_eq!(gone["status"], "expired");
+    assert_eq!(gone["code"], code.as_str());
+    assert_eq!(gone["error"], "Link has expired");
+    assert_eq!(
+        gone["expired_at"],
+        json!(expires_at.format("%Y-%m-%dT%H:%M:%S").to_string())
+    );
+
+    // Browsers keep the plain-text reason.
+    let res = server
+        .get(&format!("/{code}"))
+        .add_header(header::ACCEPT, HeaderValue::from_static("text/html"))
+        .await;
+    assert_eq!(res.status_code(), 410);
+    assert_eq!(res.text(), "Link has expired");
+}
+
+#[tokio::test]
+async fn first_click_ttl_starts_on_first_click_and_then_expires() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/ttl", "ttl_after_first_click_secs": 0 }))
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(
+            &json!({ "original_url": "https://iana.org/ttl", "ttl_after_first_click_secs": 3600 }),
+        )
+        .await;
+    assert_eq!(res.status_code(), 201, "create failed: {}", res.text());
+    let created: Value = res.json();
+    assert_eq!(created["ttl_after_first_click_secs"], 3600);
+    assert!(created["expires_at"].is_null(), "{created}");
+    let id = created["id"].as_i64().unwrap() as i32;
+    let code = created["code"].as_str().unwrap().to_string();
+    let stored = || async {
+        links::Entity::find_by_id(id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+    };
+
+    // The first click starts the clock; later clicks inside the window don't
+    // move it.
+    let before = Utc::now().naive_utc();
+    assert_eq!(server.get(&format!("/{code}")).await.status_code(), 307);
+    let expires_at = stored().await.expires_at.expect("stamped on first click");
+    assert!(expires_at >= before + Duration::seconds(3600));
+    assert!(expires_at <= Utc::now().naive_utc() + Duration::seconds(3600));
+    assert_eq!(server.get(&format!("/{code}")).await.status_code(), 307);
+    assert_eq!(stored().await.expires_at, Some(expires_at));
+
+    // Once the window has passed the next click is refused.
+    let mut link: links::ActiveModel = stored().await.into();
+    link.expires_at = Set(Some((Utc::now() - Duration::seconds(1)).naive_utc()));
+    link.update(&db).await.unwrap();
+    let res = server.get(&format!("/{code}")).await;
+    assert_eq!(res.status_code(), 410, "{}", res.text());
+}
+
+#[tokio::test]
+async fn expired_and_capped_links_serve_their_custom_page() {
+    use axum::http::{header, HeaderValue};
+
+    let (server, db) = common::spawn_real_app().await;
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let page = r#"<h1 onclick="x()">Campaign over</h1><script>alert(1)</script>"#;
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/campaign", "expired_page_html": page }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let created: Value = res.json();
+    assert_eq!(created["expired_page_html"], "<h1>Campaign over</h1>");
+    let code = created["code"].as_str().unwrap().to_string();
+    let id = created["id"].as_i64().unwrap() as i32;
+
+    // Live links still redirect.
+    let res = server.get(&format!("/{code}")).await;
+    assert_eq!(res.status_code(), 307);
+
+    let mut link: links::ActiveModel = links::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    link.expires_at = Set(Some((Utc::now() - Duration::hours(1)).naive_utc()));
+    link.update(&db).await.unwrap();
+
+    let res = server.get(&format!("/{code}")).await;
+    assert_eq!(res.status_code(), 410);
+    assert_eq!(res.text(), "<h1>Campaign over</h1>");
+    assert!(res
+        .header(header::CONTENT_TYPE)
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+    assert!(res
+        .header(header::CONTENT_SECURITY_POLICY)
+        .to_str()
+        .unwrap()
+        .contains("sandbox"));
+
+    // JSON clients keep the structured body.
+    let res = server
+        .get(&format!("/{code}"))
+        .add_header(header::ACCEPT, HeaderValue::from_static("application/json"))
+        .await;
+    assert_eq!(res.json::<Value>()["status"], "expired");
+
+    // A click-capped link shows its page once the clicks are used up.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "original_url": "https://iana.org/capped",
+            "max_clicks": 1,
+            "expired_page_html": "<p>All gone</p>",
+        }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create capped: {}", res.text());
+    let capped: Value = res.json();
+    let capped_code = capped["code"].as_str().unwrap();
+    assert_eq!(
+        server.get(&format!("/{capped_code}")).await.status_code(),
+        307
+    );
+    let res = server.get(&format!("/{capped_code}")).await;
+    assert_eq!(res.status_code(), 410);
+    assert_eq!(res.text(), "<p>All gone</p>");
+
+    // An empty string removes the page; oversized pages are refused.
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "expired_page_html": "" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "clear: {}", res.text());
+    assert_eq!(res.json::<Value>()["expired_page_html"], Value::Null);
+    assert_eq!(
+        server.get(&format!("/{code}")).await.text(),
+        "Link has expired"
+    );
+
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "expired_page_html": "x".repeat(40 * 1024) }))
+        .await;
+    assert_eq!(res.status_code(), 400);
+    assert!(res.json::<Value>()["fields"]["expired_page_html"].is_array());
+}
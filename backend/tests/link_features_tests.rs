@@ -355,6 +355,7 @@ mod scheduled_tests {
 mod link_status_tests {
     use super::*;
 
+    #[allow(dead_code)]
     #[derive(Debug, PartialEq)]
     enum LinkStatus {
         Active,
@@ -0,0 +1,112 @@
+//! Per-link redirect rate limiting (`redirect_rate_limit`, requests per second
+//! across all visitors). Without Redis the budget is counted in-process.
+
+mod common;
+
+use serde_json::{json, Value};
+
+async fn create_link(server: &axum_test::TestServer, token: &str, body: Value) -> Value {
+    let res = server
+        .post("/links")
+        .authorization_bearer(token)
+        .json(&body)
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn exceeding_link_rate_returns_429_while_other_links_pass() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let limited = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/limited", "redirect_rate_limit": 2 }),
+    )
+    .await;
+    assert_eq!(limited["redirect_rate_limit"], 2);
+    let limited_code = limited["code"].as_str().unwrap().to_string();
+
+    let plain = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/plain" }),
+    )
+    .await;
+    assert!(plain["redirect_rate_limit"].is_null());
+    let plain_code = plain["code"].as_str().unwrap().to_string();
+
+    // Within the budget: normal redirects.
+    for _ in 0..2 {
+        let res = server.get(&format!("/{limited_code}")).await;
+        assert_eq!(res.status_code(), 307);
+    }
+    // Over it: 429 with a retry hint.
+    let res = server.get(&format!("/{limited_code}")).await;
+    assert_eq!(res.status_code(), 429);
+    assert_eq!(res.header("retry-after"), "1");
+
+    // Other links are unaffected.
+    for _ in 0..5 {
+        let res = server.get(&format!("/{plain_code}")).await;
+        assert_eq!(res.status_code(), 307);
+    }
+
+    // The window resets after a second.
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    let res = server.get(&format!("/{limited_code}")).await;
+    assert_eq!(res.status_code(), 307);
+}
+
+#[tokio::test]
+async fn redirect_rate_limit_is_validated_and_removable() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/zero", "redirect_rate_limit": 0 }))
+        .await;
+    assert_eq!(res.status_code(), 400);
+
+    let link = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/once", "redirect_rate_limit": 1 }),
+    )
+    .await;
+    let id = link["id"].as_i64().unwrap();
+    let code = link["code"].as_str().unwrap().to_string();
+
+    assert_eq!(server.get(&format!("/{code}")).await.status_code(), 307);
+    assert_eq!(server.get(&format!("/{code}")).await.status_code(), 429);
+
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "remove_redirect_rate_limit": true }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    assert!(res.json::<Value>()["redirect_rate_limit"].is_null());
+
+    for _ in 0..3 {
+        assert_eq!(server.get(&format!("/{code}")).await.status_code(), 307);
+    }
+}
@@ -1,8 +1,5 @@
 mod common;
 
-use axum_test::TestServer;
-use serde_json::json;
-
 // ============= URL Validation Tests =============
 
 #[cfg(test)]
@@ -546,7 +543,7 @@ mod qr_code_tests {
 mod bulk_operations_tests {
     #[test]
     fn test_bulk_url_validation() {
-        let urls = vec![
+        let urls = [
             "https://iana.org/1",
             "https://iana.org/2",
             "invalid-url",
@@ -560,7 +557,7 @@ mod bulk_operations_tests {
 
     #[test]
     fn test_bulk_ids_parsing() {
-        let ids = vec![1, 2, 3, 4, 5];
+        let ids = [1, 2, 3, 4, 5];
         assert_eq!(ids.len(), 5);
         assert!(ids.iter().all(|&id| id > 0));
     }
@@ -573,7 +570,7 @@ mod bulk_operations_tests {
 
     #[test]
     fn test_bulk_deduplication() {
-        let urls = vec![
+        let urls = [
             "https://iana.org/page",
             "https://iana.org/page",
             "https://iana.org/other",
@@ -1,7 +1,5 @@
 mod common;
 
-use serde_json::json;
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,6 +18,60 @@ mod tests {
             response.text()
         );
     }
+
+    /// MAX_URL_LENGTH (default 2048) applies to create, update, and bulk.
+    #[tokio::test]
+    async fn url_length_limit_is_enforced_at_the_boundary() {
+        use serde_json::{json, Value};
+
+        let (server, db) = common::spawn_real_app().await;
+        let res = server
+            .post("/auth/register")
+            .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+            .await;
+        let body: Value = res.json();
+        common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+        let token = body["token"].as_str().unwrap().to_string();
+
+        let settings: Value = server.get("/auth/settings").await.json();
+        assert_eq!(settings["max_url_length"], 2048);
+
+        let prefix = "https://iana.org/";
+        let at_limit = format!("{prefix}{}", "a".repeat(2048 - prefix.len()));
+        let over_limit = format!("{at_limit}b");
+
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": at_limit }))
+            .await;
+        assert_eq!(res.status_code(), 201, "{}", res.text());
+        let id = res.json::<Value>()["id"].as_i64().unwrap();
+
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": over_limit }))
+            .await;
+        assert_eq!(res.status_code(), 400, "{}", res.text());
+        assert!(res.text().contains("at most 2048"));
+
+        let res = server
+            .put(&format!("/links/{id}"))
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": over_limit }))
+            .await;
+        assert_eq!(res.status_code(), 400, "{}", res.text());
+
+        let res = server
+            .post("/links/bulk")
+            .authorization_bearer(&token)
+            .json(&json!({ "urls": [over_limit] }))
+            .await;
+        let body: Value = res.json();
+        assert!(body["links"].as_array().unwrap().is_empty());
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+    }
 }
 
 // Unit tests for link-related functionality
@@ -0,0 +1,133 @@
+//! MAX_TAGS_PER_LINK at the boundary, across create, add-tags, bulk create and
+//! bulk update. Lives in its own test binary because it sets a process-wide
+//! env var.
+
+mod common;
+
+use opn_onl_backend::entity::link_tags;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::{json, Value};
+
+async fn tag_count(db: &DatabaseConnection, link_id: i64) -> u64 {
+    link_tags::Entity::find()
+        .filter(link_tags::Column::LinkId.eq(link_id as i32))
+        .count(db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn tag_limit_is_enforced_everywhere() {
+    std::env::set_var("MAX_TAGS_PER_LINK", "3");
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let mut tags = Vec::new();
+    for i in 0..4 {
+        let res = server
+            .post("/tags")
+            .authorization_bearer(&token)
+            .json(&json!({ "name": format!("t{i}-{}", common::unique_code()) }))
+            .await;
+        assert_eq!(res.status_code(), 201, "tag: {}", res.text());
+        tags.push(res.json::<Value>()["id"].as_i64().unwrap());
+    }
+
+    // Requests below are paced to stay under the per-IP burst limit.
+    let pause = || tokio::time::sleep(std::time::Duration::from_secs(1));
+    pause().await;
+
+    // create_link: exactly the limit is fine, one more is not.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/four", "tag_ids": tags }))
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/three", "tag_ids": &tags[..3] }))
+        .await;
+    assert_eq!(res.status_code(), 201, "{}", res.text());
+    let full = res.json::<Value>()["id"].as_i64().unwrap();
+
+    // add_tags_to_link counts what's already attached.
+    let res = server
+        .post(&format!("/links/{full}/tags"))
+        .authorization_bearer(&token)
+        .json(&json!({ "tag_ids": [tags[3]] }))
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+    assert_eq!(tag_count(&db, full).await, 3);
+    // Re-adding a tag it already has doesn't grow the set.
+    let res = server
+        .post(&format!("/links/{full}/tags"))
+        .authorization_bearer(&token)
+        .json(&json!({ "tag_ids": [tags[0]] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+
+    pause().await;
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/two", "tag_ids": &tags[..2] }))
+        .await;
+    let two = res.json::<Value>()["id"].as_i64().unwrap();
+    let res = server
+        .post(&format!("/links/{two}/tags"))
+        .authorization_bearer(&token)
+        .json(&json!({ "tag_ids": [tags[3]] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    assert_eq!(tag_count(&db, two).await, 3);
+
+    // Bulk create reports the oversized item and creates the rest.
+    let res = server
+        .post("/links/bulk")
+        .authorization_bearer(&token)
+        .json(&json!({ "urls": [
+            { "url": "https://iana.org/bulk-four", "tag_ids": tags },
+            { "url": "https://iana.org/bulk-three", "tag_ids": &tags[..3] },
+        ] }))
+        .await;
+    assert!(res.status_code().is_success(), "{}", res.text());
+    let body: Value = res.json();
+    assert_eq!(body["links"].as_array().unwrap().len(), 1);
+    assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+
+    pause().await;
+    // Bulk update: too many in the request is a 400; a link that would go
+    // over is skipped while the others are tagged.
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": [full], "add_tag_ids": tags }))
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/none" }))
+        .await;
+    let bare = res.json::<Value>()["id"].as_i64().unwrap();
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": [full, bare], "add_tag_ids": [tags[3]] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    assert_eq!(res.json::<Value>()["updated"], 1);
+    assert_eq!(tag_count(&db, full).await, 3);
+    assert_eq!(tag_count(&db, bare).await, 1);
+
+    std::env::remove_var("MAX_TAGS_PER_LINK");
+}
@@ -213,6 +213,59 @@ async fn build_utm_url_appends_parameters() {
     );
 }
 
+#[tokio::test]
+async fn preview_url_merges_append_query() {
+    let (server, _db) = spawn_real_app().await;
+    for (original, append, expected) in [
+        (
+            "https://iana.org/landing",
+            "utm_source=x&ref=y",
+            "https://iana.org/landing?utm_source=x&ref=y",
+        ),
+        (
+            "https://iana.org/landing?page=2",
+            "?utm_source=x",
+            "https://iana.org/landing?page=2&utm_source=x",
+        ),
+        (
+            "https://iana.org/landing?page=2#top",
+            "utm_source=x",
+            "https://iana.org/landing?page=2&utm_source=x#top",
+        ),
+        (
+            "https://iana.org/landing?",
+            "utm_source=x",
+            "https://iana.org/landing?utm_source=x",
+        ),
+    ] {
+        let res = server
+            .post("/links/preview-url")
+            .json(&json!({ "original_url": original, "append_query": append }))
+            .await;
+        assert_eq!(res.status_code(), 200, "{original}: {}", res.text());
+        assert_eq!(res.json::<Value>()["final_url"], expected);
+    }
+}
+
+#[tokio::test]
+async fn preview_url_validates_inputs() {
+    let (server, _db) = spawn_real_app().await;
+    for (original, append) in [
+        ("not a url", "utm_source=x"),
+        ("javascript:alert(1)", "utm_source=x"),
+        ("https://iana.org/", ""),
+        ("https://iana.org/", "=novalue"),
+        ("https://iana.org/", "a=1#frag"),
+        ("https://iana.org/", "a=1 b=2"),
+    ] {
+        let res = server
+            .post("/links/preview-url")
+            .json(&json!({ "original_url": original, "append_query": append }))
+            .await;
+        assert_eq!(res.status_code(), 400, "{original} + {append}");
+    }
+}
+
 #[tokio::test]
 async fn build_utm_url_rejects_invalid_url() {
     let (server, _db) = spawn_real_app().await;
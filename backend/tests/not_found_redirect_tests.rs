@@ -0,0 +1,32 @@
+//! NOT_FOUND_REDIRECT_URL: unknown short codes redirect to the configured page
+//! instead of a bare 404; previews and reserved paths still 404. Lives in its
+//! own test binary because it sets a process-wide env var.
+
+mod common;
+
+#[tokio::test]
+async fn unknown_codes_redirect_only_when_configured() {
+    let (server, _db) = common::spawn_real_app().await;
+    let missing = common::unique_code();
+
+    std::env::remove_var("NOT_FOUND_REDIRECT_URL");
+    let res = server.get(&format!("/{missing}")).await;
+    assert_eq!(res.status_code(), 404);
+
+    // Not an http(s) URL: ignored.
+    std::env::set_var("NOT_FOUND_REDIRECT_URL", "javascript:alert(1)");
+    let res = server.get(&format!("/{missing}")).await;
+    assert_eq!(res.status_code(), 404);
+
+    std::env::set_var("NOT_FOUND_REDIRECT_URL", "https://iana.org/welcome");
+    let res = server.get(&format!("/{missing}")).await;
+    assert_eq!(res.status_code(), 302);
+    assert_eq!(res.header("location"), "https://iana.org/welcome");
+
+    let res = server.get(&format!("/{missing}/preview")).await;
+    assert_eq!(res.status_code(), 404);
+    let res = server.get("/dashboard").await;
+    assert_eq!(res.status_code(), 404);
+
+    std::env::remove_var("NOT_FOUND_REDIRECT_URL");
+}
@@ -0,0 +1,165 @@
+//! POST /orgs/import runs every bundle link through the checks create_link
+//! applies, and a hostile bundle is rejected whole. Lives in its own test
+//! binary because it sets MAX_LINKS_PER_USER, a process-wide env var.
+
+mod common;
+
+use opn_onl_backend::entity::{links, organizations};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::{json, Value};
+
+async fn register_verified(server: &axum_test::TestServer, db: &DatabaseConnection) -> String {
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(db, body["user_id"].as_i64().unwrap() as i32).await;
+    body["token"].as_str().unwrap().to_string()
+}
+
+fn bundle(slug: &str, links: Vec<Value>) -> Value {
+    json!({
+        "version": 1,
+        "organization": { "name": "Imported", "slug": slug },
+        "members": [],
+        "folders": [],
+        "tags": [],
+        "links": links,
+    })
+}
+
+fn link(code: &str) -> Value {
+    json!({ "code": code, "original_url": "https://iana.org/imported" })
+}
+
+async fn org_exists(db: &DatabaseConnection, slug: &str) -> bool {
+    organizations::Entity::find()
+        .filter(organizations::Column::Slug.eq(slug))
+        .count(db)
+        .await
+        .unwrap()
+        > 0
+}
+
+#[tokio::test]
+async fn hostile_bundles_are_rejected_whole() {
+    std::env::set_var("MAX_LINKS_PER_USER", "3");
+    let (server, db) = common::spawn_real_app().await;
+    let token = register_verified(&server, &db).await;
+    let slug = format!("imp-{}", common::unique_code().to_lowercase());
+    let good = format!("ok{}", common::unique_code());
+
+    let hostile = [
+        // Oversized note, after a valid link so the first one isn't kept.
+        vec![
+            link(&good),
+            json!({
+                "code": format!("nt{}", common::unique_code()),
+                "original_url": "https://iana.org/note",
+                "notes": "x".repeat(2001),
+            }),
+        ],
+        // Aliases create_link would refuse.
+        vec![link("a b!")],
+        vec![link("ab")],
+        vec![link(&format!("-{}", common::unique_code()))],
+        // A password hash the server never produced.
+        vec![json!({
+            "code": format!("pw{}", common::unique_code()),
+            "original_url": "https://iana.org/pw",
+            "password_hash": "plaintext",
+        })],
+    ];
+    for links in hostile {
+        let res = server
+            .post("/orgs/import")
+            .authorization_bearer(&token)
+            .json(&bundle(&slug, links))
+            .await;
+        assert_eq!(res.status_code(), 400, "{}", res.text());
+    }
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    // Four links against a cap of three.
+    let res = server
+        .post("/orgs/import")
+        .authorization_bearer(&token)
+        .json(&bundle(
+            &slug,
+            (0..4)
+                .map(|i| link(&format!("cap{i}{}", common::unique_code())))
+                .collect(),
+        ))
+        .await;
+    assert_eq!(res.status_code(), 403, "{}", res.text());
+
+    assert!(!org_exists(&db, &slug).await);
+    let kept = links::Entity::find()
+        .filter(links::Column::Code.eq(&good))
+        .count(&db)
+        .await
+        .unwrap();
+    assert_eq!(kept, 0);
+}
+
+#[tokio::test]
+async fn imported_links_start_clean() {
+    std::env::set_var("MAX_LINKS_PER_USER", "3");
+    let (server, db) = common::spawn_real_app().await;
+    let token = register_verified(&server, &db).await;
+    let slug = format!("imp-{}", common::unique_code().to_lowercase());
+    let code = format!("cl{}", common::unique_code());
+    let hash = bcrypt::hash("secret-pass", 4).unwrap();
+
+    let res = server
+        .post("/orgs/import")
+        .authorization_bearer(&token)
+        .json(&bundle(
+            &slug,
+            vec![json!({
+                "code": code,
+                "original_url": "https://iana.org/clean",
+                "notes": "<b>launch</b> plan",
+                "click_count": 999_999,
+                "created_at": "2999-01-01T00:00:00",
+                "password_hash": hash,
+            })],
+        ))
+        .await;
+    assert_eq!(res.status_code(), 201, "{}", res.text());
+
+    let stored = links::Entity::find()
+        .filter(links::Column::Code.eq(&code))
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.click_count, 0);
+    assert_eq!(stored.notes.as_deref(), Some("launch plan"));
+    assert!(stored.created_at <= chrono::Utc::now().naive_utc());
+    assert_eq!(stored.password_hash.as_deref(), Some(hash.as_str()));
+}
+
+#[tokio::test]
+async fn unverified_accounts_cannot_import() {
+    std::env::set_var("MAX_LINKS_PER_USER", "3");
+    let (server, db) = common::spawn_real_app().await;
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let token = res.json::<Value>()["token"].as_str().unwrap().to_string();
+    let slug = format!("imp-{}", common::unique_code().to_lowercase());
+
+    let res = server
+        .post("/orgs/import")
+        .authorization_bearer(&token)
+        .json(&bundle(
+            &slug,
+            vec![link(&format!("uv{}", common::unique_code()))],
+        ))
+        .await;
+    assert_eq!(res.status_code(), 403, "{}", res.text());
+    assert!(!org_exists(&db, &slug).await);
+}
@@ -0,0 +1,55 @@
+//! DEFAULT_PAGE_SIZE / MAX_PAGE_SIZE: `GET /links` is never unbounded and
+//! reports the page size it applied. Lives in its own test binary because it
+//! sets process-wide env vars.
+
+mod common;
+
+use opn_onl_backend::entity::links;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn link_list_defaults_and_clamps_its_page_size() {
+    std::env::set_var("DEFAULT_PAGE_SIZE", "3");
+    std::env::set_var("MAX_PAGE_SIZE", "5");
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    let user_id = body["user_id"].as_i64().unwrap() as i32;
+    let token = body["token"].as_str().unwrap().to_string();
+    for i in 0..7 {
+        links::ActiveModel {
+            code: Set(common::unique_code()),
+            original_url: Set(format!("https://iana.org/page-{i}")),
+            user_id: Set(Some(user_id)),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+    }
+
+    for (query, expected) in [("", 3), ("?limit=100", 5), ("?limit=2", 2)] {
+        let res = server
+            .get(&format!("/links{query}"))
+            .authorization_bearer(&token)
+            .await;
+        assert_eq!(res.status_code(), 200, "{query}: {}", res.text());
+        assert_eq!(res.header("x-page-limit"), expected.to_string().as_str());
+        assert_eq!(res.json::<Vec<Value>>().len(), expected, "{query}");
+    }
+
+    // Later pages are still reachable with offset.
+    let res = server
+        .get("/links?limit=5&offset=5")
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.json::<Vec<Value>>().len(), 2);
+
+    std::env::remove_var("DEFAULT_PAGE_SIZE");
+    std::env::remove_var("MAX_PAGE_SIZE");
+}
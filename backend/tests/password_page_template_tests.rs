@@ -0,0 +1,75 @@
+//! Password-protected links: browsers are redirected to the password page
+//! (PASSWORD_PAGE_TEMPLATE), JSON clients get a 401 they can act on. Lives in
+//! its own test binary because it sets a process-wide env var.
+
+mod common;
+
+use axum::http::{header, HeaderValue};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn password_prompt_negotiates_redirect_or_json() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let code = common::unique_code();
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "original_url": "https://iana.org/locked",
+            "custom_alias": code,
+            "password": "open-sesame-42",
+        }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+
+    let frontend =
+        std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:5173".to_string());
+    std::env::remove_var("PASSWORD_PAGE_TEMPLATE");
+    let res = server
+        .get(&format!("/{code}"))
+        .add_header(
+            header::ACCEPT,
+            HeaderValue::from_static("text/html,application/xhtml+xml,*/*;q=0.8"),
+        )
+        .await;
+    assert_eq!(res.status_code(), 307);
+    assert_eq!(
+        res.header("location"),
+        format!("{frontend}/password/{code}")
+    );
+
+    std::env::set_var("PASSWORD_PAGE_TEMPLATE", "/unlock?c={code}");
+    let res = server.get(&format!("/{code}")).await;
+    assert_eq!(res.status_code(), 307);
+    assert_eq!(
+        res.header("location"),
+        format!("{frontend}/unlock?c={code}")
+    );
+
+    std::env::set_var("PASSWORD_PAGE_TEMPLATE", "https://iana.org/gate/{code}");
+    let res = server.get(&format!("/{code}")).await;
+    assert_eq!(
+        res.header("location"),
+        format!("https://iana.org/gate/{code}")
+    );
+
+    let res = server
+        .get(&format!("/{code}"))
+        .add_header(header::ACCEPT, HeaderValue::from_static("application/json"))
+        .await;
+    assert_eq!(res.status_code(), 401);
+    let body: Value = res.json();
+    assert_eq!(body["password_required"], true);
+    assert_eq!(body["code"], code.as_str());
+
+    std::env::remove_var("PASSWORD_PAGE_TEMPLATE");
+}
@@ -0,0 +1,93 @@
+//! PASSWORD_PEPPER: account and link passwords are hashed with the pepper,
+//! and an account hash from before it was set still signs in and is replaced
+//! by a peppered one. Lives in its own test binary because it sets
+//! process-wide env vars.
+
+mod common;
+
+use opn_onl_backend::entity::{links, users};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DatabaseConnection, EntityTrait};
+use serde_json::{json, Value};
+
+async fn stored_hash(db: &DatabaseConnection, user_id: i32) -> String {
+    users::Entity::find_by_id(user_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .password_hash
+}
+
+#[tokio::test]
+async fn peppered_passwords_round_trip_and_old_hashes_are_rehashed_on_login() {
+    std::env::set_var("PASSWORD_PEPPER", "integration-test-pepper");
+    let (server, db) = common::spawn_real_app().await;
+
+    let email = common::unique_email();
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": email, "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "register: {}", res.text());
+    let body: Value = res.json();
+    let user_id = body["user_id"].as_i64().unwrap() as i32;
+    common::mark_email_verified(&db, user_id).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    // The stored hash can't be checked without the pepper.
+    assert!(!bcrypt::verify("password123", &stored_hash(&db, user_id).await).unwrap());
+    let login = |password: &'static str| {
+        server
+            .post("/auth/login")
+            .json(&json!({ "email": email, "password": password }))
+    };
+    assert_eq!(login("password123").await.status_code(), 200);
+    assert_eq!(login("password124").await.status_code(), 401);
+
+    // Link passwords are peppered too.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/pepper", "password": "open-sesame" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let link: Value = res.json();
+    let code = link["code"].as_str().unwrap();
+    let stored = links::Entity::find_by_id(link["id"].as_i64().unwrap() as i32)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!bcrypt::verify("open-sesame", stored.password_hash.as_deref().unwrap()).unwrap());
+    let res = server
+        .post(&format!("/{code}/verify"))
+        .json(&json!({ "password": "open-sesame" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "verify: {}", res.text());
+
+    // A hash from before the pepper still signs in, once, then is replaced.
+    let legacy = bcrypt::hash("password123", 4).unwrap();
+    let user = users::Entity::find_by_id(user_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut active: users::ActiveModel = user.into();
+    active.password_hash = Set(legacy.clone());
+    active.update(&db).await.unwrap();
+
+    assert_eq!(login("password124").await.status_code(), 401);
+    assert_eq!(
+        stored_hash(&db, user_id).await,
+        legacy,
+        "no rehash on failure"
+    );
+
+    assert_eq!(login("password123").await.status_code(), 200);
+    let rehashed = stored_hash(&db, user_id).await;
+    assert_ne!(rehashed, legacy);
+    assert!(!bcrypt::verify("password123", &rehashed).unwrap());
+    assert_eq!(login("password123").await.status_code(), 200);
+
+    std::env::remove_var("PASSWORD_PEPPER");
+}
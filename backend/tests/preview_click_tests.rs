@@ -0,0 +1,103 @@
+//! COUNT_PREVIEW_AS_CLICK: previews are free by default; when switched on a
+//! preview counts as a click and leaves an event flagged `is_preview`. Lives in
+//! its own test binary because it sets a process-wide env var.
+
+mod common;
+
+use opn_onl_backend::entity::{click_events, links};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn previews_count_only_when_enabled() {
+    std::env::remove_var("COUNT_PREVIEW_AS_CLICK");
+    std::env::set_var("FORCE_HTTPS", "false");
+    if std::env::var("JWT_SECRET").is_err() {
+        std::env::set_var("JWT_SECRET", "integration-test-secret-0123456789abcdef");
+    }
+    // Build the app by hand to keep a handle on its click buffer.
+    let db = common::setup_test_db().await;
+    let state = opn_onl_backend::AppState::for_tests(db.clone()).await;
+    let buffer = Arc::clone(&state.click_buffer);
+    let server = axum_test::TestServer::new(opn_onl_backend::build_router(state)).unwrap();
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let mut links_by_kind = Vec::new();
+    for body in [
+        json!({ "original_url": "https://iana.org/preview" }),
+        json!({ "original_url": "https://iana.org/limited", "max_clicks": 1 }),
+    ] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&body)
+            .await;
+        assert_eq!(res.status_code(), 201, "create: {}", res.text());
+        let link: Value = res.json();
+        links_by_kind.push((
+            link["id"].as_i64().unwrap() as i32,
+            link["code"].as_str().unwrap().to_string(),
+        ));
+    }
+    let (id, code) = &links_by_kind[0];
+    let (limited_id, limited_code) = &links_by_kind[1];
+
+    let db = &db;
+    let events = |id: i32| {
+        click_events::Entity::find()
+            .filter(click_events::Column::LinkId.eq(id))
+            .all(db)
+    };
+    let click_count = |id: i32| async move {
+        links::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap()
+            .click_count
+    };
+
+    let res = server.get(&format!("/{code}/preview")).await;
+    assert_eq!(res.status_code(), 200);
+    buffer.flush(db).await;
+    assert!(
+        events(*id).await.unwrap().is_empty(),
+        "previews are free by default"
+    );
+    assert_eq!(click_count(*id).await, 0);
+
+    std::env::set_var("COUNT_PREVIEW_AS_CLICK", "true");
+    for path in [
+        format!("/{code}/preview"),
+        format!("/{limited_code}/preview"),
+    ] {
+        let res = server.get(&path).await;
+        assert_eq!(res.status_code(), 200, "{path}");
+    }
+    buffer.flush(db).await;
+    let recorded = events(*id).await.unwrap();
+    assert_eq!(recorded.len(), 1);
+    assert!(recorded[0].is_preview);
+    assert_eq!(click_count(*id).await, 1);
+    // A preview never spends a link's click budget.
+    assert!(events(*limited_id).await.unwrap().is_empty());
+    assert_eq!(click_count(*limited_id).await, 0);
+
+    // Real redirects are still recorded as such.
+    let res = server.get(&format!("/{code}")).await;
+    assert_eq!(res.status_code(), 307);
+    buffer.flush(db).await;
+    let recorded = events(*id).await.unwrap();
+    assert_eq!(recorded.iter().filter(|e| !e.is_preview).count(), 1);
+    assert_eq!(click_count(*id).await, 2);
+
+    std::env::remove_var("COUNT_PREVIEW_AS_CLICK");
+}
@@ -3,6 +3,7 @@
 use std::thread;
 use std::time::Duration;
 
+#[allow(dead_code)]
 #[path = "../src/utils/rate_limiter.rs"]
 mod rate_limiter;
 
@@ -330,7 +331,6 @@ mod cleanup_tests {
 
 mod limiters_tests {
     use super::*;
-    use std::sync::Arc;
 
     #[test]
     fn test_default_limiters() {
@@ -1,8 +1,7 @@
 //! Rate limiter tests
 
-use std::time::Duration;
-
 // Import the rate limiter module
+#[allow(dead_code)]
 #[path = "../src/utils/rate_limiter.rs"]
 mod rate_limiter;
 
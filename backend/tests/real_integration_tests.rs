@@ -11,6 +11,8 @@
 
 mod common;
 
+use opn_onl_backend::entity::{link_tags, links};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
 use serde_json::{json, Value};
 
 /// Register a user through the real handler; returns (token, user_id).
@@ -174,6 +176,637 @@ async fn deleted_link_cannot_be_updated() {
     );
 }
 
+/// Every destination change is recorded, oldest first; edits that leave the
+/// URL alone add nothing.
+#[tokio::test]
+async fn destination_changes_are_recorded_in_history() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let (link_id, _code) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/v1" }),
+    )
+    .await;
+
+    for payload in [
+        json!({ "original_url": "https://iana.org/v2" }),
+        json!({ "title": "renamed only" }),
+        json!({ "original_url": "https://iana.org/v3" }),
+    ] {
+        let res = server
+            .put(&format!("/links/{link_id}"))
+            .authorization_bearer(&token)
+            .json(&payload)
+            .await;
+        assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    }
+
+    let res = server
+        .get(&format!("/links/{link_id}/history"))
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.status_code(), 200, "history: {}", res.text());
+    let history: Value = res.json();
+    let rows = history.as_array().expect("history array");
+    assert_eq!(rows.len(), 2, "one row per URL change: {history}");
+    assert_eq!(rows[0]["old_url"], "https://iana.org/v1");
+    assert_eq!(rows[0]["new_url"], "https://iana.org/v2");
+    assert_eq!(rows[1]["old_url"], "https://iana.org/v2");
+    assert_eq!(rows[1]["new_url"], "https://iana.org/v3");
+    assert_eq!(rows[0]["changed_by"].as_i64(), Some(user_id as i64));
+
+    // History is private to the owner.
+    let (other, _) = register(&server, &common::unique_email()).await;
+    let res = server
+        .get(&format!("/links/{link_id}/history"))
+        .authorization_bearer(&other)
+        .await;
+    assert_eq!(res.status_code(), 403);
+}
+
+/// Bulk create accepts plain strings and per-item objects side by side; a bad
+/// or colliding alias fails only its own entry.
+#[tokio::test]
+async fn bulk_create_with_per_item_aliases() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let (_, taken) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/taken" }),
+    )
+    .await;
+    let tag: Value = server
+        .post("/tags")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "bulk" }))
+        .await
+        .json();
+    let tag_id = tag["id"].as_i64().expect("tag id");
+
+    let fresh = format!("b{}", common::unique_code());
+    let res = server
+        .post("/links/bulk")
+        .authorization_bearer(&token)
+        .json(&json!({ "urls": [
+            "https://iana.org/plain",
+            { "url": "https://iana.org/aliased", "custom_alias": fresh, "notes": "batch", "tag_ids": [tag_id] },
+            { "url": "https://iana.org/collides", "custom_alias": taken },
+            { "url": "https://iana.org/reserved", "custom_alias": "dashboard" },
+            { "url": "https://iana.org/repeat", "custom_alias": fresh },
+        ] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "bulk: {}", res.text());
+    let body: Value = res.json();
+    let links = body["links"].as_array().expect("links array");
+    let errors = body["errors"].as_array().expect("errors array");
+    assert_eq!(links.len(), 2, "{body}");
+    assert_eq!(errors.len(), 3, "{body}");
+    assert_eq!(links[1]["code"], fresh.as_str());
+    assert!(errors[0].as_str().unwrap().contains("Alias already taken"));
+    assert!(errors[1].as_str().unwrap().contains("reserved"));
+    assert!(errors[2]
+        .as_str()
+        .unwrap()
+        .starts_with("https://iana.org/repeat"));
+
+    let link_id = links[1]["id"].as_i64().unwrap() as i32;
+    let link = links::Entity::find_by_id(link_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(link.notes.as_deref(), Some("batch"));
+    let tagged = link_tags::Entity::find()
+        .filter(link_tags::Column::LinkId.eq(link_id))
+        .all(&db)
+        .await
+        .unwrap();
+    assert_eq!(tagged.len(), 1);
+    assert_eq!(tagged[0].tag_id as i64, tag_id);
+}
+
+/// Bulk create reports one result per input URL, in input order, so callers
+/// can match failures to their URLs by index.
+#[tokio::test]
+async fn bulk_create_results_keep_input_order_and_indices() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+
+    let urls = [
+        "not a url",
+        "https://iana.org/first",
+        "ftp://iana.org/file",
+        "https://iana.org/second",
+        // A repeated URL is a separate entry with its own index.
+        "https://iana.org/first",
+    ];
+    let res = server
+        .post("/links/bulk")
+        .authorization_bearer(&token)
+        .json(&json!({ "urls": urls }))
+        .await;
+    assert_eq!(res.status_code(), 200, "bulk: {}", res.text());
+    let body: Value = res.json();
+    let results = body["results"].as_array().expect("results array");
+    assert_eq!(results.len(), urls.len(), "{body}");
+
+    for (i, (result, url)) in results.iter().zip(urls).enumerate() {
+        assert_eq!(result["index"], i, "{body}");
+        assert_eq!(result["url"], url, "{body}");
+    }
+    let succeeded: Vec<bool> = results.iter().map(|r| r["link"].is_object()).collect();
+    assert_eq!(succeeded, [false, true, false, true, true]);
+    for result in results {
+        assert_eq!(
+            result["error"].is_string(),
+            result["link"].is_null(),
+            "{result}"
+        );
+    }
+
+    // The flat lists agree with the results, in the same order.
+    let links = body["links"].as_array().unwrap();
+    let created: Vec<&Value> = results
+        .iter()
+        .map(|r| &r["link"])
+        .filter(|l| l.is_object())
+        .collect();
+    assert_eq!(links.iter().collect::<Vec<_>>(), created);
+    let errors = body["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].as_str().unwrap().starts_with("not a url: "));
+    assert!(errors[1]
+        .as_str()
+        .unwrap()
+        .starts_with("ftp://iana.org/file: "));
+    assert_ne!(links[0]["code"], links[2]["code"]);
+}
+
+/// Bulk requests are capped at MAX_BULK_SIZE (default 500) and an id list
+/// with repeats or non-positive ids is refused as a whole.
+#[tokio::test]
+async fn bulk_requests_reject_oversized_and_malformed_id_lists() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let (id, code) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/bulk-guard" }),
+    )
+    .await;
+
+    let oversized: Vec<i64> = (1..=501).collect();
+    let urls: Vec<String> = (0..501).map(|i| format!("https://iana.org/{i}")).collect();
+    for (path, body) in [
+        ("/links/bulk/delete", json!({ "ids": oversized })),
+        (
+            "/links/bulk/update",
+            json!({ "ids": oversized, "remove_expiration": true }),
+        ),
+        ("/links/bulk", json!({ "urls": urls })),
+        ("/links/bulk/delete", json!({ "ids": [id, id] })),
+        ("/links/bulk/delete", json!({ "ids": [id, -1] })),
+        (
+            "/links/bulk/update",
+            json!({ "ids": [id, id], "set_password": "hunter2hunter2" }),
+        ),
+        ("/links/bulk/update", json!({ "ids": [] })),
+    ] {
+        let res = server
+            .post(path)
+            .authorization_bearer(&token)
+            .json(&body)
+            .await;
+        assert_eq!(res.status_code(), 400, "{path}: {}", res.text());
+    }
+
+    // Nothing in the rejected batches was applied.
+    let link = links::Entity::find_by_id(id as i32)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(link.deleted_at.is_none());
+    assert!(link.password_hash.is_none());
+    let res = server.get(&format!("/{code}")).await;
+    assert!(res.status_code().is_redirection(), "{}", res.status_code());
+}
+
+#[tokio::test]
+async fn bulk_update_sets_and_clears_passwords() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let mut ids = Vec::new();
+    for path in ["one", "two"] {
+        let (id, _) = create_link(
+            &server,
+            &token,
+            json!({ "original_url": format!("https://iana.org/{path}") }),
+        )
+        .await;
+        ids.push(id as i32);
+    }
+    let stored = |db: sea_orm::DatabaseConnection, ids: Vec<i32>| async move {
+        links::Entity::find()
+            .filter(links::Column::Id.is_in(ids))
+            .all(&db)
+            .await
+            .unwrap()
+    };
+
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": ids, "set_password": "hunter22" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "bulk: {}", res.text());
+    assert_eq!(res.json::<Value>()["updated"], 2);
+    let protected = stored(db.clone(), ids.clone()).await;
+    assert!(protected.iter().all(|l| l
+        .password_hash
+        .as_deref()
+        .is_some_and(|h| bcrypt::verify("hunter22", h).unwrap())));
+    // Hashed once for the whole batch.
+    assert_eq!(protected[0].password_hash, protected[1].password_hash);
+
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": ids, "remove_password": true }))
+        .await;
+    assert_eq!(res.json::<Value>()["updated"], 2);
+    assert!(stored(db.clone(), ids.clone())
+        .await
+        .iter()
+        .all(|l| l.password_hash.is_none()));
+}
+
+/// Every endpoint returning links shows them the same way: the same fields,
+/// including `api_url` and `title`, with the same values.
+#[tokio::test]
+async fn link_responses_match_across_endpoints() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let tag: Value = server
+        .post("/tags")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "same" }))
+        .await
+        .json();
+    let folder: Value = server
+        .post("/folders")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "same" }))
+        .await
+        .json();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "original_url": "https://iana.org/consistent",
+            "title": "Consistent",
+            "folder_id": folder["id"],
+            "tag_ids": [tag["id"]],
+        }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let created: Value = res.json();
+    let id = created["id"].as_i64().unwrap();
+
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "title": "Consistent" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    let updated: Value = res.json();
+
+    let find = |list: Value| {
+        list.as_array()
+            .unwrap()
+            .iter()
+            .find(|l| l["id"].as_i64() == Some(id))
+            .cloned()
+            .expect("link in list")
+    };
+    let listed = find(
+        server
+            .get("/links")
+            .authorization_bearer(&token)
+            .await
+            .json(),
+    );
+    let in_folder = find(
+        server
+            .get(&format!("/folders/{}/links", folder["id"]))
+            .authorization_bearer(&token)
+            .await
+            .json(),
+    );
+    let by_tag = find(
+        server
+            .get(&format!("/tags/{}/links", tag["id"]))
+            .authorization_bearer(&token)
+            .await
+            .json(),
+    );
+
+    let keys = |link: &Value| {
+        link.as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<std::collections::BTreeSet<_>>()
+    };
+    for (name, link) in [
+        ("update", &updated),
+        ("list", &listed),
+        ("folder", &in_folder),
+        ("tag", &by_tag),
+    ] {
+        assert_eq!(keys(link), keys(&created), "{name} field set");
+        for field in [
+            "short_url",
+            "api_url",
+            "title",
+            "created_at",
+            "folder_id",
+            "tags",
+            "is_active",
+        ] {
+            assert_eq!(link[field], created[field], "{name} {field}");
+        }
+    }
+    assert!(created["api_url"]
+        .as_str()
+        .unwrap()
+        .ends_with(&format!("/{}", created["code"].as_str().unwrap())));
+    assert_eq!(created["title"], "Consistent");
+}
+
+/// Link passwords must meet LINK_PASSWORD_MIN_LENGTH (default 6) wherever
+/// one is set; removing a password needs none.
+#[tokio::test]
+async fn weak_link_passwords_are_rejected_at_the_boundary() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/weak", "password": "12345" }))
+        .await;
+    assert_eq!(res.status_code(), 400, "create: {}", res.text());
+    assert_eq!(
+        res.json::<Value>()["fields"]["password"][0],
+        "Link password must be at least 6 characters"
+    );
+
+    let (id, _) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/strong", "password": "123456" }),
+    )
+    .await;
+
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "password": "1" }))
+        .await;
+    assert_eq!(res.status_code(), 400, "update: {}", res.text());
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "password": "abcdef" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": [id], "set_password": "abcde" }))
+        .await;
+    assert_eq!(res.status_code(), 400, "bulk: {}", res.text());
+    assert!(res.json::<Value>()["fields"]["set_password"].is_array());
+
+    let link = links::Entity::find_by_id(id as i32)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(bcrypt::verify("abcdef", link.password_hash.as_deref().unwrap()).unwrap());
+
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": [id], "remove_password": true, "set_password": "x" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "bulk remove: {}", res.text());
+}
+
+#[tokio::test]
+async fn bulk_update_applies_tags_and_clears_folder() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let mut tag_ids = Vec::new();
+    for name in ["keep", "drop"] {
+        let tag: Value = server
+            .post("/tags")
+            .authorization_bearer(&token)
+            .json(&json!({ "name": name }))
+            .await
+            .json();
+        tag_ids.push(tag["id"].as_i64().expect("tag id") as i32);
+    }
+    let folder: Value = server
+        .post("/folders")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "bulk" }))
+        .await
+        .json();
+    let folder_id = folder["id"].as_i64().expect("folder id");
+
+    let mut ids = Vec::new();
+    for path in ["one", "two"] {
+        let (id, _) = create_link(
+            &server,
+            &token,
+            json!({
+                "original_url": format!("https://iana.org/{path}"),
+                "folder_id": folder_id,
+                "tag_ids": [tag_ids[1]],
+            }),
+        )
+        .await;
+        ids.push(id as i32);
+    }
+
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "ids": ids,
+            "add_tag_ids": [tag_ids[0]],
+            "remove_tag_ids": [tag_ids[1]],
+            "remove_folder": true,
+        }))
+        .await;
+    assert_eq!(res.status_code(), 200, "bulk: {}", res.text());
+    assert_eq!(res.json::<Value>()["updated"], 2);
+
+    for id in &ids {
+        let tagged: Vec<i32> = link_tags::Entity::find()
+            .filter(link_tags::Column::LinkId.eq(*id))
+            .all(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|lt| lt.tag_id)
+            .collect();
+        assert_eq!(tagged, vec![tag_ids[0]]);
+        let link = links::Entity::find_by_id(*id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.folder_id, None);
+    }
+
+    // Re-adding an attached tag is a no-op rather than a duplicate.
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": ids, "add_tag_ids": [tag_ids[0]] }))
+        .await;
+    assert_eq!(res.json::<Value>()["updated"], 2);
+    let count = link_tags::Entity::find()
+        .filter(link_tags::Column::LinkId.is_in(ids.clone()))
+        .all(&db)
+        .await
+        .unwrap()
+        .len();
+    assert_eq!(count, 2);
+
+    // Someone else's tag is rejected per link.
+    let (other, other_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, other_id).await;
+    let foreign: Value = server
+        .post("/tags")
+        .authorization_bearer(&other)
+        .json(&json!({ "name": "foreign" }))
+        .await
+        .json();
+    let res = server
+        .post("/links/bulk/update")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": ids, "add_tag_ids": [foreign["id"]] }))
+        .await;
+    assert_eq!(res.json::<Value>()["updated"], 0);
+}
+
+#[tokio::test]
+async fn links_without_folder_land_in_default_folder() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let mut folder_ids = Vec::new();
+    for name in ["inbox", "other"] {
+        let folder: Value = server
+            .post("/folders")
+            .authorization_bearer(&token)
+            .json(&json!({ "name": name }))
+            .await
+            .json();
+        folder_ids.push(folder["id"].as_i64().expect("folder id"));
+    }
+
+    let res = server
+        .put("/auth/profile")
+        .authorization_bearer(&token)
+        .json(&json!({ "default_folder_id": folder_ids[0] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "profile: {}", res.text());
+    assert_eq!(res.json::<Value>()["default_folder_id"], folder_ids[0]);
+    let me: Value = server
+        .get("/auth/me")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(me["default_folder_id"], folder_ids[0]);
+
+    let (defaulted, _) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/defaulted" }),
+    )
+    .await;
+    let (explicit, _) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/explicit", "folder_id": folder_ids[1] }),
+    )
+    .await;
+    for (id, folder) in [(defaulted, folder_ids[0]), (explicit, folder_ids[1])] {
+        let link = links::Entity::find_by_id(id as i32)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.folder_id, Some(folder as i32));
+    }
+
+    // Someone else's folder can't be the default.
+    let (other, other_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, other_id).await;
+    let res = server
+        .put("/auth/profile")
+        .authorization_bearer(&other)
+        .json(&json!({ "default_folder_id": folder_ids[0] }))
+        .await;
+    assert_eq!(res.status_code(), 400, "{}", res.text());
+
+    // Cleared: back to the root.
+    let res = server
+        .put("/auth/profile")
+        .authorization_bearer(&token)
+        .json(&json!({ "remove_default_folder": true }))
+        .await;
+    assert!(res.json::<Value>()["default_folder_id"].is_null());
+    let (rooted, _) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/rooted" }),
+    )
+    .await;
+    let link = links::Entity::find_by_id(rooted as i32)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(link.folder_id, None);
+}
+
 /// Regression (account takeover, fixed in 5240b6a): passkey enrollment must
 /// require authentication — knowing a victim's email must not be enough to
 /// start registering an authenticator onto their account.
@@ -243,4 +876,888 @@ async fn health_reports_database_connected() {
     let body: Value = res.json();
     assert_eq!(body["status"], "healthy");
     assert_eq!(body["database"], "connected");
+    assert!(body["click_buffer_pending"].is_u64());
+    assert!(body["click_buffer_dropped"].is_u64());
+}
+
+/// Redirect and analytics lookups rely on the hot-column indexes from the
+/// performance-index migration; fail loudly if a migration ever drops them.
+#[tokio::test]
+async fn hot_columns_are_indexed() {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let (_server, db) = common::spawn_real_app().await;
+
+    let rows = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "SELECT tablename, indexdef FROM pg_indexes \
+             WHERE schemaname = current_schema() AND tablename IN ('links', 'click_events')",
+        ))
+        .await
+        .unwrap();
+    let defs: Vec<(String, String)> = rows
+        .iter()
+        .map(|r| {
+            (
+                r.try_get::<String>("", "tablename").unwrap(),
+                r.try_get::<String>("", "indexdef").unwrap(),
+            )
+        })
+        .collect();
+
+    // Leading column is what matters for these equality / range filters.
+    for (table, column) in [
+        ("links", "code"),
+        ("links", "user_id"),
+        ("links", "deleted_at"),
+        ("click_events", "link_id"),
+        ("click_events", "created_at"),
+    ] {
+        let leading = format!("({column}");
+        assert!(
+            defs.iter()
+                .any(|(t, def)| t == table && def.contains(&leading)),
+            "no index leading with {table}.{column}: {defs:?}"
+        );
+    }
+}
+
+/// `?include_qr=true` embeds the QR as a PNG data URI; by default the field
+/// isn't sent at all.
+#[tokio::test]
+async fn create_link_can_embed_qr_code() {
+    use base64::Engine as _;
+
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/plain" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    assert!(res.json::<Value>().get("qr_code").is_none());
+
+    let res = server
+        .post("/links?include_qr=true")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/with-qr" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let body: Value = res.json();
+    let encoded = body["qr_code"]
+        .as_str()
+        .and_then(|uri| uri.strip_prefix("data:image/png;base64,"))
+        .unwrap_or_else(|| panic!("no PNG data URI in {body}"));
+    let png = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .expect("valid base64");
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"), "not a PNG");
+}
+
+/// Create endpoints answer 201 with a `Location` pointing at the new resource.
+#[tokio::test]
+async fn create_endpoints_return_location_header() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+
+    for (collection, body) in [
+        (
+            "links",
+            json!({ "original_url": "https://iana.org/located" }),
+        ),
+        ("folders", json!({ "name": "Located" })),
+        ("tags", json!({ "name": "located" })),
+        (
+            "orgs",
+            json!({ "name": "Located Org", "slug": format!("loc-{}", common::unique_code().to_lowercase()) }),
+        ),
+    ] {
+        let res = server
+            .post(&format!("/{collection}"))
+            .authorization_bearer(&token)
+            .json(&body)
+            .await;
+        assert_eq!(res.status_code(), 201, "{collection}: {}", res.text());
+        let id = res.json::<Value>()["id"].as_i64().expect("id");
+        assert_eq!(res.header("location"), format!("/{collection}/{id}"));
+    }
+}
+
+#[tokio::test]
+async fn bulk_tag_delete_removes_associations_and_skips_foreign_tags() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let (other, other_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, other_id).await;
+
+    let mut tag_ids = Vec::new();
+    for (owner, name) in [
+        (&token, "a"),
+        (&token, "b"),
+        (&token, "keep"),
+        (&other, "theirs"),
+    ] {
+        let tag: Value = server
+            .post("/tags")
+            .authorization_bearer(owner)
+            .json(&json!({ "name": name }))
+            .await
+            .json();
+        tag_ids.push(tag["id"].as_i64().expect("tag id") as i32);
+    }
+    let (link_id, _) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/tagged", "tag_ids": &tag_ids[..3] }),
+    )
+    .await;
+
+    let res = server
+        .post("/tags/bulk/delete")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": [tag_ids[0], tag_ids[1], tag_ids[3], 999_999_999] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "bulk delete: {}", res.text());
+    let body: Value = res.json();
+    assert_eq!(body["deleted"], 2);
+    assert_eq!(body["associations_removed"], 2);
+    assert_eq!(body["skipped"], json!([tag_ids[3], 999_999_999]));
+
+    let remaining: Vec<i32> = link_tags::Entity::find()
+        .filter(link_tags::Column::LinkId.eq(link_id as i32))
+        .all(&db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|lt| lt.tag_id)
+        .collect();
+    assert_eq!(remaining, vec![tag_ids[2]]);
+    let res = server
+        .get(&format!("/tags/{}", tag_ids[3]))
+        .authorization_bearer(&other)
+        .await;
+    assert_eq!(res.status_code(), 200, "foreign tag must survive");
+
+    let res = server
+        .post("/tags/bulk/delete")
+        .authorization_bearer(&token)
+        .json(&json!({ "ids": [] }))
+        .await;
+    assert_eq!(res.status_code(), 400);
+}
+
+#[tokio::test]
+async fn tag_rename_rejects_a_name_already_in_use() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let (other, other_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, other_id).await;
+
+    let mut ids = Vec::new();
+    for (owner, name) in [(&token, "Work"), (&token, "home"), (&other, "Travel")] {
+        let res = server
+            .post("/tags")
+            .authorization_bearer(owner)
+            .json(&json!({ "name": name }))
+            .await;
+        assert_eq!(res.status_code(), 201, "create {name}: {}", res.text());
+        ids.push(res.json::<Value>()["id"].as_i64().unwrap());
+    }
+
+    let res = server
+        .post("/tags")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "WORK" }))
+        .await;
+    assert_eq!(res.status_code(), 409, "duplicate create: {}", res.text());
+
+    let rename = |name: &'static str| {
+        server
+            .put(&format!("/tags/{}", ids[1]))
+            .authorization_bearer(&token)
+            .json(&json!({ "name": name }))
+    };
+    let res = rename("work").await;
+    assert_eq!(res.status_code(), 409, "rename onto Work: {}", res.text());
+
+    // Another user's tag names, a case change of the tag itself and a free
+    // name are all fine.
+    for name in ["travel", "Home", "Errands"] {
+        let res = rename(name).await;
+        assert_eq!(res.status_code(), 200, "rename to {name}: {}", res.text());
+        assert_eq!(res.json::<Value>()["name"], name);
+    }
+}
+
+#[tokio::test]
+async fn tags_without_a_color_take_the_next_palette_color() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+
+    let mut colors = Vec::new();
+    for body in [
+        json!({ "name": "first" }),
+        json!({ "name": "second" }),
+        json!({ "name": "picked", "color": "#ABC" }),
+        json!({ "name": "plain", "color": "" }),
+        json!({ "name": "fifth" }),
+    ] {
+        let res = server
+            .post("/tags")
+            .authorization_bearer(&token)
+            .json(&body)
+            .await;
+        assert_eq!(res.status_code(), 201, "{body}: {}", res.text());
+        colors.push(res.json::<Value>()["color"].clone());
+    }
+    // The default palette opens blue, red, green, amber, violet; the index is
+    // the number of tags the user already has.
+    assert_eq!(
+        colors,
+        [
+            json!("#3b82f6"),
+            json!("#ef4444"),
+            json!("#aabbcc"),
+            Value::Null,
+            json!("#8b5cf6"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn profile_timezone_localizes_link_timestamps_on_request() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/tz" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+
+    let res = server
+        .put("/auth/profile")
+        .authorization_bearer(&token)
+        .json(&json!({ "timezone": "Mars/Olympus" }))
+        .await;
+    assert_eq!(res.status_code(), 400);
+    let res = server
+        .put("/auth/profile")
+        .authorization_bearer(&token)
+        .json(&json!({ "timezone": "+0200" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "profile: {}", res.text());
+    assert_eq!(res.json::<Value>()["timezone"], "+02:00");
+
+    let created_at = |query: &'static str| {
+        let server = &server;
+        let token = token.clone();
+        async move {
+            let res = server
+                .get(&format!("/links{query}"))
+                .authorization_bearer(&token)
+                .await;
+            assert_eq!(res.status_code(), 200, "{query}: {}", res.text());
+            res.json::<Value>()[0]["created_at"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        }
+    };
+
+    // UTC stays the default; `tz=profile` shifts by the saved offset.
+    let utc = created_at("").await;
+    let local = created_at("?tz=profile").await;
+    let utc = chrono::NaiveDateTime::parse_from_str(&utc, "%Y-%m-%d %H:%M:%S%.f").unwrap();
+    let local = chrono::DateTime::parse_from_str(&local, "%Y-%m-%d %H:%M:%S%.f %:z").unwrap();
+    assert_eq!(local.offset().local_minus_utc(), 2 * 3600);
+    assert_eq!(local.naive_utc(), utc);
+
+    // A named zone applies the offset in effect at each timestamp.
+    let res = server
+        .put("/auth/profile")
+        .authorization_bearer(&token)
+        .json(&json!({ "timezone": "Europe/Paris" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "profile: {}", res.text());
+    assert_eq!(res.json::<Value>()["timezone"], "Europe/Paris");
+    let local = created_at("?tz=profile").await;
+    let local = chrono::DateTime::parse_from_str(&local, "%Y-%m-%d %H:%M:%S%.f %:z").unwrap();
+    let paris = utc.and_utc().with_timezone(&chrono_tz::Europe::Paris);
+    assert_eq!(*local.offset(), chrono::Offset::fix(paris.offset()));
+    assert_eq!(local.naive_utc(), utc);
+    let res = server
+        .get("/links?tz=America/New_York")
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+
+    let res = server
+        .get("/links?tz=Mars/Olympus")
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.status_code(), 400);
+}
+
+#[tokio::test]
+async fn health_checks_record_status_and_list_broken_links() {
+    use opn_onl_backend::utils::link_health;
+
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+
+    let mut targets = Vec::new();
+    for path in ["ok", "moved", "gone", "down", "error"] {
+        let url = format!("https://iana.org/{}/{path}", common::unique_code());
+        let (id, _) = create_link(&server, &token, json!({ "original_url": url })).await;
+        targets.push((id as i32, url));
+    }
+
+    // Stand-in for the network: the status each destination answers with.
+    let broken = link_health::check_links(
+        &db,
+        targets.clone(),
+        std::time::Duration::ZERO,
+        |url: String| async move {
+            match url.rsplit('/').next() {
+                Some("ok") => Ok(200),
+                Some("moved") => Ok(301),
+                Some("gone") => Ok(404),
+                Some("error") => Ok(503),
+                _ => Err("connection refused".to_string()),
+            }
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(broken, 3);
+
+    let stored = links::Entity::find_by_id(targets[3].0)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.last_status, Some(link_health::NO_RESPONSE));
+    assert!(stored.last_checked.is_some());
+
+    let res = server
+        .get("/links/broken")
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.status_code(), 200, "broken: {}", res.text());
+    let mut listed: Vec<(i64, i64)> = res
+        .json::<Value>()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| {
+            (
+                l["id"].as_i64().unwrap(),
+                l["last_status"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    listed.sort();
+    let ids: Vec<i64> = targets.iter().map(|(id, _)| i64::from(*id)).collect();
+    assert_eq!(listed, [(ids[2], 404), (ids[3], 0), (ids[4], 503)]);
+
+    // A new destination starts over unchecked.
+    let res = server
+        .put(&format!("/links/{}", ids[2]))
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/fixed" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    let res = server
+        .get("/links/broken")
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.json::<Value>().as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn taken_alias_suggests_available_alternatives() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let alias = common::unique_code();
+
+    // The alias and its first numbered variant are both taken.
+    for code in [alias.clone(), format!("{alias}-2")] {
+        create_link(
+            &server,
+            &token,
+            json!({ "original_url": "https://iana.org/taken", "custom_alias": code }),
+        )
+        .await;
+    }
+
+    let body = json!({ "original_url": "https://iana.org/wanted", "custom_alias": alias });
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&body)
+        .await;
+    assert_eq!(res.status_code(), 409);
+    assert!(res.json::<Value>().get("suggestions").is_none());
+
+    let res = server
+        .post("/links?suggest=true")
+        .authorization_bearer(&token)
+        .json(&body)
+        .await;
+    assert_eq!(res.status_code(), 409);
+    let conflict: Value = res.json();
+    assert!(conflict["error"].is_string());
+    let suggestions: Vec<String> = conflict["suggestions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s.as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(suggestions.len(), 3, "{suggestions:?}");
+    assert_eq!(suggestions[0], format!("{alias}-3"));
+
+    for suggestion in &suggestions {
+        let res = server
+            .get(&format!("/links/check-code?code={suggestion}"))
+            .await;
+        assert_eq!(res.json::<Value>()["available"], true, "{suggestion}");
+    }
+    for suggestion in &suggestions {
+        create_link(
+            &server,
+            &token,
+            json!({ "original_url": "https://iana.org/wanted", "custom_alias": suggestion }),
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn reserved_code_is_held_until_its_owner_sets_a_destination() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let (other, other_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, other_id).await;
+    let alias = common::unique_code();
+
+    let res = server
+        .post("/links/reserve")
+        .authorization_bearer(&token)
+        .json(&json!({ "custom_alias": alias }))
+        .await;
+    assert_eq!(res.status_code(), 201, "reserve: {}", res.text());
+    let reserved: Value = res.json();
+    assert_eq!(reserved["code"], alias.as_str());
+    assert_eq!(reserved["is_reserved"], true);
+    assert_eq!(reserved["is_active"], false);
+    let id = reserved["id"].as_i64().unwrap();
+
+    let res = server.get(&format!("/{alias}")).await;
+    assert_ne!(res.status_code(), 307, "a reserved code must not redirect");
+
+    // Nobody else can take it, by reservation or by creating a link.
+    let res = server
+        .post("/links/reserve")
+        .authorization_bearer(&other)
+        .json(&json!({ "custom_alias": alias }))
+        .await;
+    assert_eq!(res.status_code(), 409);
+    let res = server
+        .post("/links")
+        .authorization_bearer(&other)
+        .json(&json!({ "original_url": "https://iana.org/squat", "custom_alias": alias }))
+        .await;
+    assert_eq!(res.status_code(), 409);
+    let res = server
+        .post("/links/reserve")
+        .authorization_bearer(&token)
+        .json(&json!({ "custom_alias": "dashboard" }))
+        .await;
+    assert_eq!(res.status_code(), 400, "reserved words stay off limits");
+
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&other)
+        .json(&json!({ "original_url": "https://iana.org/squat" }))
+        .await;
+    assert_eq!(res.status_code(), 403);
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/finally" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "finalize: {}", res.text());
+    let finalized: Value = res.json();
+    assert_eq!(finalized["is_reserved"], false);
+    assert_eq!(finalized["is_active"], true);
+
+    let res = server.get(&format!("/{alias}")).await;
+    assert_eq!(res.status_code(), 307);
+    assert_eq!(res.header("location"), "https://iana.org/finally");
+}
+
+#[tokio::test]
+async fn public_profile_lists_only_bio_visible_links() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let username = format!("p{}", common::unique_code().to_lowercase());
+
+    let res = server
+        .put("/auth/profile")
+        .authorization_bearer(&token)
+        .json(&json!({ "display_name": "Ada", "bio": "Writes about engines" }))
+        .await;
+    assert_eq!(res.status_code(), 200, "profile: {}", res.text());
+    let res = server
+        .put("/auth/bio")
+        .authorization_bearer(&token)
+        .json(&json!({ "bio_username": username, "bio_enabled": true }))
+        .await;
+    assert_eq!(res.status_code(), 200, "bio settings: {}", res.text());
+
+    let (shown, shown_code) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/shown", "title": "Shown" }),
+    )
+    .await;
+    create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/private", "title": "Private" }),
+    )
+    .await;
+    let res = server
+        .put(&format!("/links/{shown}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "bio_visible": true }))
+        .await;
+    assert_eq!(res.status_code(), 200, "mark visible: {}", res.text());
+
+    let res = server.get(&format!("/u/{username}")).await;
+    assert_eq!(res.status_code(), 200, "public page: {}", res.text());
+    let page: Value = res.json();
+    assert_eq!(page["username"], username.as_str());
+    assert_eq!(page["display_name"], "Ada");
+    assert_eq!(page["bio"], "Writes about engines");
+    let codes: Vec<&str> = page["links"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(codes, [shown_code.as_str()]);
+    assert_eq!(page["links"][0]["label"], "Shown");
+
+    // Turning the page off hides it without revealing the username exists.
+    server
+        .put("/auth/bio")
+        .authorization_bearer(&token)
+        .json(&json!({ "bio_enabled": false }))
+        .await;
+    assert_eq!(
+        server.get(&format!("/u/{username}")).await.status_code(),
+        404
+    );
+}
+
+#[tokio::test]
+async fn csv_export_streams_every_page_in_order() {
+    use futures::TryStreamExt;
+
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let (_, other_id) = register(&server, &common::unique_email()).await;
+
+    // Five live links, two of them sharing a timestamp, plus rows that must
+    // not appear: a deleted link and someone else's.
+    let base = chrono::Utc::now().naive_utc() - chrono::Duration::hours(1);
+    let mut expected = Vec::new();
+    for (i, minutes) in [0, 1, 2, 2, 3].into_iter().enumerate() {
+        let link = links::ActiveModel {
+            code: Set(format!("x{}", common::unique_code())),
+            original_url: Set(format!("https://iana.org/export/{i}?a=1,2")),
+            user_id: Set(Some(user_id)),
+            created_at: Set(base + chrono::Duration::minutes(minutes)),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        expected.push((link.created_at, link.id));
+    }
+    for (owner, deleted_at) in [(user_id, Some(base)), (other_id, None)] {
+        links::ActiveModel {
+            code: Set(format!("x{}", common::unique_code())),
+            original_url: Set("https://iana.org/hidden".to_string()),
+            user_id: Set(Some(owner)),
+            created_at: Set(base),
+            deleted_at: Set(deleted_at),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+    }
+    expected.sort_by(|a, b| b.cmp(a));
+    let expected_ids: Vec<String> = expected.iter().map(|(_, id)| id.to_string()).collect();
+
+    // Two rows per page: header, three row chunks.
+    let chunks: Vec<String> =
+        opn_onl_backend::handlers::links::links_csv_stream(db.clone(), user_id, 2)
+            .try_collect()
+            .await
+            .unwrap();
+    assert_eq!(chunks.len(), 4);
+    let streamed = chunks.concat();
+
+    let res = server
+        .get("/links/export")
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(res.status_code(), 200);
+    assert_eq!(res.header("content-type"), "text/csv");
+    assert_eq!(res.text(), streamed);
+
+    let mut lines = streamed.lines();
+    assert!(lines.next().unwrap().starts_with("ID,Code,Original URL"));
+    let ids: Vec<&str> = lines
+        .clone()
+        .map(|l| l.split(',').next().unwrap())
+        .collect();
+    assert_eq!(ids, expected_ids);
+    // Fields with commas stay quoted.
+    assert!(lines.all(|l| l.contains("\"https://iana.org/export/")));
+}
+
+#[tokio::test]
+async fn link_notes_are_stored_as_plain_text_within_the_limit() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+
+    let xss = common::test_data::xss_payload();
+    let (id, _) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/notes", "notes": format!("keep {xss}this") }),
+    )
+    .await;
+    let stored = links::Entity::find_by_id(id as i32)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.notes.as_deref(), Some("keep this"));
+
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "notes": format!("<b>bold</b> {xss}") }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    assert_eq!(res.json::<Value>()["notes"], "bold");
+
+    // A note that is nothing but markup clears the field.
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "notes": xss }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    assert!(res.json::<Value>()["notes"].is_null());
+
+    let too_long = "n".repeat(2001);
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/notes-long", "notes": too_long }))
+        .await;
+    assert_eq!(res.status_code(), 400, "create: {}", res.text());
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "notes": too_long }))
+        .await;
+    assert_eq!(res.status_code(), 400, "update: {}", res.text());
+    let stored = links::Entity::find_by_id(id as i32)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.notes.is_none());
+}
+
+#[tokio::test]
+async fn hidden_click_count_is_bucketed_publicly_but_exact_for_owner() {
+    let (server, db) = common::spawn_real_app().await;
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, user_id).await;
+    let username = format!("h{}", common::unique_code().to_lowercase());
+    let res = server
+        .put("/auth/bio")
+        .authorization_bearer(&token)
+        .json(&json!({ "bio_username": username, "bio_enabled": true }))
+        .await;
+    assert_eq!(res.status_code(), 200, "bio settings: {}", res.text());
+
+    let (id, code) = create_link(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/quiet", "hide_click_count": true }),
+    )
+    .await;
+    let mut link: links::ActiveModel = links::Entity::find_by_id(id as i32)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    link.click_count = Set(137);
+    link.bio_visible = Set(true);
+    link.update(&db).await.unwrap();
+
+    let preview: Value = server.get(&format!("/{code}/preview")).await.json();
+    assert!(preview["click_count"].is_null(), "{preview}");
+    assert_eq!(preview["click_count_bucket"], "100+");
+    let page: Value = server.get(&format!("/u/{username}")).await.json();
+    assert!(page["links"][0]["click_count"].is_null(), "{page}");
+    assert_eq!(page["links"][0]["click_count_bucket"], "100+");
+
+    // The owner's own listing keeps the exact number.
+    let listed: Vec<Value> = server
+        .get("/links")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    let owner = listed.iter().find(|l| l["id"] == id).expect("listed");
+    assert_eq!(owner["click_count"], 137);
+    assert_eq!(owner["hide_click_count"], true);
+
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "hide_click_count": false }))
+        .await;
+    assert_eq!(res.status_code(), 200, "update: {}", res.text());
+    let preview: Value = server.get(&format!("/{code}/preview")).await.json();
+    assert_eq!(preview["click_count"], 137);
+    assert!(preview["click_count_bucket"].is_null());
+}
+
+/// Anonymous links come with a creation token; after signing up the creator
+/// can claim them, but only within the claim window and only once.
+#[tokio::test]
+async fn creation_tokens_claim_recent_anonymous_links() {
+    let (server, db) = common::spawn_real_app().await;
+
+    let mut created = Vec::new();
+    for path in ["claim-a", "claim-b", "claim-old"] {
+        let res = server
+            .post("/links")
+            .json(&json!({ "original_url": format!("https://iana.org/{path}") }))
+            .await;
+        assert_eq!(res.status_code(), 201, "create: {}", res.text());
+        let body: Value = res.json();
+        let token = body["creation_token"].as_str().expect("creation token");
+        created.push((body["id"].as_i64().unwrap() as i32, token.to_string()));
+    }
+
+    // The third link was made before the claim window opened.
+    let mut old: links::ActiveModel = links::Entity::find_by_id(created[2].0)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    old.created_at = Set(chrono::Utc::now().naive_utc() - chrono::Duration::hours(73));
+    old.update(&db).await.unwrap();
+
+    let (token, user_id) = register(&server, &common::unique_email()).await;
+    // Signed-in creation doesn't hand out a token.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/claim-owned" }))
+        .await;
+    assert_eq!(res.status_code(), 403, "unverified: {}", res.text());
+    common::mark_email_verified(&db, user_id).await;
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/claim-owned" }))
+        .await;
+    assert_eq!(res.status_code(), 201);
+    assert!(res.json::<Value>().get("creation_token").is_none());
+
+    let tokens: Vec<&str> = created.iter().map(|(_, t)| t.as_str()).collect();
+    let res = server
+        .post("/links/claim-batch")
+        .json(&json!({ "tokens": tokens }))
+        .await;
+    assert_eq!(res.status_code(), 401);
+
+    let res = server
+        .post("/links/claim-batch")
+        .authorization_bearer(&token)
+        .json(&json!({ "tokens": [tokens[0], tokens[1], tokens[2], "not-a-token"] }))
+        .await;
+    assert_eq!(res.status_code(), 200, "claim: {}", res.text());
+    let body: Value = res.json();
+    assert_eq!(body["claimed"], 2);
+    assert_eq!(body["link_ids"], json!([created[0].0, created[1].0]));
+
+    for (i, (id, _)) in created.iter().enumerate() {
+        let link = links::Entity::find_by_id(*id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        if i < 2 {
+            assert_eq!(link.user_id, Some(user_id));
+            assert!(link.creation_token_hash.is_none());
+        } else {
+            assert_eq!(link.user_id, None, "expired token must not transfer");
+        }
+    }
+
+    // A claimed token can't be used again, by anyone.
+    let (other, other_id) = register(&server, &common::unique_email()).await;
+    common::mark_email_verified(&db, other_id).await;
+    let res = server
+        .post("/links/claim-batch")
+        .authorization_bearer(&other)
+        .json(&json!({ "tokens": tokens }))
+        .await;
+    assert_eq!(res.status_code(), 200);
+    assert_eq!(res.json::<Value>()["claimed"], 0);
+}
+
+#[tokio::test]
+async fn version_endpoint_reports_crate_version() {
+    let (server, _db) = common::spawn_real_app().await;
+
+    let res = server.get("/version").await;
+    assert_eq!(res.status_code(), 200);
+    let body: Value = res.json();
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(body.get("git_sha").is_some());
+    assert!(body.get("build_timestamp").is_some());
 }
@@ -0,0 +1,83 @@
+//! SELF_LINK_POLICY: destinations on this service's own hosts are rejected by
+//! default and can be resolved to the final destination instead. Lives in its
+//! own test binary because it sets process-wide env vars.
+
+mod common;
+
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn self_referential_destinations_are_rejected_or_resolved() {
+    std::env::set_var("FRONTEND_URL", "https://opn-selflink.onl");
+    std::env::set_var("BASE_URL", "https://l.opn-selflink.onl");
+    std::env::remove_var("SELF_LINK_POLICY");
+    let (server, db) = common::spawn_real_app().await;
+
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    let body: Value = res.json();
+    common::mark_email_verified(&db, body["user_id"].as_i64().unwrap() as i32).await;
+    let token = body["token"].as_str().unwrap().to_string();
+
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/self-link" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    let target: Value = res.json();
+    let code = target["code"].as_str().unwrap().to_string();
+    let id = target["id"].as_i64().unwrap();
+
+    // Strict (default): either short domain, any spelling, is refused.
+    for url in [
+        format!("https://opn-selflink.onl/{code}"),
+        format!("https://L.OPN-SELFLINK.ONL.:8443/{code}"),
+        "https://opn-selflink.onl/dashboard".to_string(),
+    ] {
+        let res = server
+            .post("/links")
+            .authorization_bearer(&token)
+            .json(&json!({ "original_url": url }))
+            .await;
+        assert_eq!(res.status_code(), 400, "{url}: {}", res.text());
+    }
+    let res = server
+        .put(&format!("/links/{id}"))
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": format!("https://opn-selflink.onl/{code}") }))
+        .await;
+    assert_eq!(res.status_code(), 400, "update: {}", res.text());
+
+    // Resolve: the chain collapses to the final destination.
+    std::env::set_var("SELF_LINK_POLICY", "resolve");
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": format!("https://l.opn-selflink.onl/{code}") }))
+        .await;
+    assert_eq!(res.status_code(), 201, "resolve: {}", res.text());
+    assert_eq!(
+        res.json::<Value>()["original_url"],
+        "https://iana.org/self-link"
+    );
+
+    // A password-protected link is not resolved around its password.
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/locked", "password": "hunter22" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "locked: {}", res.text());
+    let locked = res.json::<Value>()["code"].as_str().unwrap().to_string();
+    let res = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": format!("https://opn-selflink.onl/{locked}") }))
+        .await;
+    assert_eq!(res.status_code(), 400, "locked resolve: {}", res.text());
+
+    std::env::remove_var("SELF_LINK_POLICY");
+}
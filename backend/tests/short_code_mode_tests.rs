@@ -0,0 +1,108 @@
+//! SHORT_CODE_MODE=sequential: auto-generated codes come from the
+//! `link_code_seq` counter in base62. Lives in its own test binary because it
+//! sets a process-wide env var.
+
+mod common;
+
+use common::{mark_email_verified, spawn_real_app};
+use opn_onl_backend::entity::links;
+use opn_onl_backend::utils::short_code::encode_base62;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ConnectionTrait, DatabaseBackend, DatabaseConnection,
+    Statement,
+};
+use serde_json::{json, Value};
+
+async fn register_verified(
+    server: &axum_test::TestServer,
+    db: &DatabaseConnection,
+) -> (String, i32) {
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": common::unique_email(), "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "register: {}", res.text());
+    let body: Value = res.json();
+    let user_id = body["user_id"].as_i64().unwrap() as i32;
+    mark_email_verified(db, user_id).await;
+    (body["token"].as_str().unwrap().to_string(), user_id)
+}
+
+async fn create(server: &axum_test::TestServer, token: &str, payload: Value) -> String {
+    let res = server
+        .post("/links")
+        .authorization_bearer(token)
+        .json(&payload)
+        .await;
+    assert_eq!(res.status_code(), 201, "create: {}", res.text());
+    res.json::<Value>()["code"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn sequential_codes_follow_the_counter_and_skip_taken_codes() {
+    std::env::set_var("SHORT_CODE_MODE", "sequential");
+    let (server, db) = spawn_real_app().await;
+    let (token, user_id) = register_verified(&server, &db).await;
+
+    let first = create(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/s1" }),
+    )
+    .await;
+    let second = create(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/s2" }),
+    )
+    .await;
+    // nginx only proxies codes of 4+ characters to the backend.
+    assert!(first.len() >= 4, "{first} is too short to be routed");
+    assert!(
+        (second.len(), second.as_str()) > (first.len(), first.as_str()),
+        "{second} should follow {first}"
+    );
+
+    // Occupy the next counter value with a pre-existing code (as an old
+    // custom alias or random code would): creation must move past it.
+    let row = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "SELECT last_value FROM link_code_seq",
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    let last: i64 = row.try_get("", "last_value").unwrap();
+    let taken = encode_base62(last as u64 + 1);
+    links::ActiveModel {
+        code: Set(taken.clone()),
+        original_url: Set("https://iana.org/taken".to_string()),
+        user_id: Set(Some(user_id)),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    let third = create(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/s3" }),
+    )
+    .await;
+    assert_ne!(third, taken);
+    assert!((third.len(), third.as_str()) > (taken.len(), taken.as_str()));
+
+    // Custom aliases are unaffected by the mode.
+    let alias = format!("seq{}", common::unique_code());
+    let code = create(
+        &server,
+        &token,
+        json!({ "original_url": "https://iana.org/s4", "custom_alias": alias }),
+    )
+    .await;
+    assert_eq!(code, alias);
+
+    std::env::remove_var("SHORT_CODE_MODE");
+}
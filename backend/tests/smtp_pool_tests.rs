@@ -0,0 +1,102 @@
+//! SMTP_POOL_SIZE / SMTP_TIMEOUT_SECS: the email service builds one pooled
+//! transport and reuses its connection across sends. Runs against a minimal
+//! in-process SMTP server. Lives in its own test binary because it sets
+//! process-wide env vars.
+
+use opn_onl_backend::utils::EmailService;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// Accept connections and answer just enough SMTP for lettre to deliver.
+/// Returns the port, the connection count and the number of messages taken.
+async fn mock_smtp() -> (u16, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let messages = Arc::new(AtomicUsize::new(0));
+    let (conns, msgs) = (connections.clone(), messages.clone());
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            conns.fetch_add(1, Ordering::SeqCst);
+            let msgs = msgs.clone();
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut lines = BufReader::new(read).lines();
+                write.write_all(b"220 mock ESMTP\r\n").await.unwrap();
+                let mut in_data = false;
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let reply: &[u8] = if in_data {
+                        if line != "." {
+                            continue;
+                        }
+                        in_data = false;
+                        msgs.fetch_add(1, Ordering::SeqCst);
+                        b"250 queued\r\n"
+                    } else {
+                        match line.get(..4).map(|c| c.to_ascii_uppercase()).as_deref() {
+                            Some("EHLO") => b"250-mock\r\n250 AUTH PLAIN LOGIN\r\n",
+                            Some("AUTH") => b"235 ok\r\n",
+                            Some("DATA") => {
+                                in_data = true;
+                                b"354 go ahead\r\n"
+                            }
+                            Some("QUIT") => {
+                                let _ = write.write_all(b"221 bye\r\n").await;
+                                break;
+                            }
+                            _ => b"250 ok\r\n",
+                        }
+                    };
+                    if write.write_all(reply).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    (port, connections, messages)
+}
+
+#[tokio::test]
+async fn pooled_transport_reuses_its_connection_across_sends() {
+    let (port, connections, messages) = mock_smtp().await;
+    std::env::set_var("SMTP_HOST", "127.0.0.1");
+    std::env::set_var("SMTP_PORT", port.to_string());
+    std::env::set_var("SMTP_USER", "mailer");
+    std::env::set_var("SMTP_PASS", "secret");
+    std::env::set_var("SMTP_TLS", "none");
+    std::env::set_var("SMTP_POOL_SIZE", "2");
+    std::env::set_var("SMTP_TIMEOUT_SECS", "5");
+
+    let service = EmailService::new();
+    assert!(service.is_configured());
+    service
+        .send_welcome_email("first@opn.test")
+        .await
+        .expect("first send");
+    // The pool takes the connection back on a background task.
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    // A clone shares the pool rather than opening its own connection.
+    service
+        .clone()
+        .send_verification_email("second@opn.test", "token")
+        .await
+        .expect("second send");
+
+    assert_eq!(messages.load(Ordering::SeqCst), 2);
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+    for var in [
+        "SMTP_HOST",
+        "SMTP_PORT",
+        "SMTP_USER",
+        "SMTP_PASS",
+        "SMTP_TLS",
+        "SMTP_POOL_SIZE",
+        "SMTP_TIMEOUT_SECS",
+    ] {
+        std::env::remove_var(var);
+    }
+}
@@ -1,6 +1,7 @@
 //! Comprehensive tag integration tests
 
 /// Mock tag for testing
+#[allow(dead_code)]
 struct MockTag {
     id: i32,
     name: String,
@@ -194,7 +195,7 @@ mod filtering_tests {
         tags: Vec<i32>,
     }
 
-    fn filter_by_tag<'a>(links: &'a [Link], tag_id: i32) -> Vec<&'a Link> {
+    fn filter_by_tag(links: &[Link], tag_id: i32) -> Vec<&Link> {
         links.iter().filter(|l| l.tags.contains(&tag_id)).collect()
     }
 
@@ -0,0 +1,79 @@
+//! PASSWORD_RESET_TTL_MINUTES / EMAIL_VERIFICATION_TTL_HOURS: reset and
+//! verification tokens carry the configured lifetime and stop working once it
+//! has passed. Lives in its own test binary because it sets process-wide env
+//! vars.
+
+mod common;
+
+use chrono::{Duration, Utc};
+use opn_onl_backend::entity::users;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DatabaseConnection, EntityTrait};
+use serde_json::{json, Value};
+
+async fn load(db: &DatabaseConnection, user_id: i32) -> users::Model {
+    users::Entity::find_by_id(user_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+/// Stand in for the clock moving on: shift a stored expiry into the past.
+fn age(expires: chrono::NaiveDateTime, by: Duration) -> Option<chrono::NaiveDateTime> {
+    Some(expires - by)
+}
+
+#[tokio::test]
+async fn short_configured_ttls_expire_tokens() {
+    std::env::set_var("PASSWORD_RESET_TTL_MINUTES", "5");
+    std::env::set_var("EMAIL_VERIFICATION_TTL_HOURS", "2");
+    let (server, db) = common::spawn_real_app().await;
+
+    let email = common::unique_email();
+    let before = Utc::now().naive_utc();
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": email, "password": "password123" }))
+        .await;
+    let user_id = res.json::<Value>()["user_id"].as_i64().unwrap() as i32;
+
+    // Verification: two hours, not the default 24.
+    let user = load(&db, user_id).await;
+    let expires = user.verification_token_expires.unwrap();
+    assert!(expires >= before + Duration::hours(2) && expires < before + Duration::hours(3));
+    let token = user.verification_token.clone().unwrap();
+    let mut active: users::ActiveModel = user.into();
+    active.verification_token_expires = Set(age(expires, Duration::hours(2)));
+    active.update(&db).await.unwrap();
+    let res = server
+        .post("/auth/verify-email")
+        .json(&json!({ "token": token }))
+        .await;
+    assert_eq!(res.status_code(), 400, "stale verification: {}", res.text());
+    assert!(!load(&db, user_id).await.email_verified);
+
+    // Password reset: five minutes, not the default hour.
+    let before = Utc::now().naive_utc();
+    let res = server
+        .post("/auth/forgot-password")
+        .json(&json!({ "email": email }))
+        .await;
+    assert_eq!(res.status_code(), 200, "forgot: {}", res.text());
+    let user = load(&db, user_id).await;
+    let expires = user.password_reset_expires.unwrap();
+    assert!(expires >= before + Duration::minutes(5) && expires < before + Duration::minutes(6));
+    let token = user.password_reset_token.clone().unwrap();
+
+    let mut active: users::ActiveModel = user.into();
+    active.password_reset_expires = Set(age(expires, Duration::minutes(5)));
+    active.update(&db).await.unwrap();
+    let res = server
+        .post("/auth/reset-password")
+        .json(&json!({ "token": token, "password": "new-password1" }))
+        .await;
+    assert_eq!(res.status_code(), 400, "stale reset: {}", res.text());
+    assert!(res.text().contains("expired"), "{}", res.text());
+
+    std::env::remove_var("PASSWORD_RESET_TTL_MINUTES");
+    std::env::remove_var("EMAIL_VERIFICATION_TTL_HOURS");
+}
@@ -5,7 +5,7 @@ mod common;
 #[cfg(test)]
 mod jwt_comprehensive_tests {
     use chrono::{Duration, Utc};
-    use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+    use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
     use serde::{Deserialize, Serialize};
 
     #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -217,6 +217,7 @@ mod click_buffer_tests {
         max_size: usize,
     }
 
+    #[allow(dead_code)]
     #[derive(Clone)]
     struct ClickData {
         link_id: i32,
@@ -366,6 +367,7 @@ mod cache_tests {
     use std::collections::HashMap;
     use std::time::{Duration, Instant};
 
+    #[allow(dead_code)]
     struct CachedLink {
         id: i32,
         original_url: String,
@@ -516,6 +518,7 @@ mod email_token_tests {
 
 #[cfg(test)]
 mod geoip_tests {
+    #[allow(dead_code)]
     #[derive(Default)]
     struct GeoInfo {
         country: Option<String>,
@@ -0,0 +1,254 @@
+//! Webhook registration: CRUD, event validation, and admin-only events.
+//! Real router + real Postgres via `common::spawn_real_app`.
+
+mod common;
+
+use common::{mark_email_verified, spawn_real_app, unique_email};
+use opn_onl_backend::entity::users;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DatabaseConnection};
+use serde_json::{json, Value};
+
+async fn register_verified(
+    server: &axum_test::TestServer,
+    db: &DatabaseConnection,
+) -> (i32, String) {
+    let res = server
+        .post("/auth/register")
+        .json(&json!({ "email": unique_email(), "password": "password123" }))
+        .await;
+    assert_eq!(res.status_code(), 201, "register: {}", res.text());
+    let body: Value = res.json();
+    let user_id = body["user_id"].as_i64().unwrap() as i32;
+    mark_email_verified(db, user_id).await;
+    (user_id, body["token"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn webhook_create_list_delete() {
+    let (server, db) = spawn_real_app().await;
+    let (_, token) = register_verified(&server, &db).await;
+
+    let created = server
+        .post("/webhooks")
+        .authorization_bearer(&token)
+        .json(&json!({ "url": "https://hooks.example.com/opn", "events": ["link.milestone"] }))
+        .await;
+    assert_eq!(created.status_code(), 201, "create: {}", created.text());
+    let body: Value = created.json();
+    let id = body["id"].as_i64().unwrap();
+    assert!(body["secret"].as_str().unwrap().starts_with("whsec_"));
+    assert_eq!(body["events"], json!(["link.milestone"]));
+
+    let list: Value = server
+        .get("/webhooks")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    let hooks = list.as_array().unwrap();
+    assert_eq!(hooks.len(), 1);
+    assert_eq!(hooks[0]["id"].as_i64(), Some(id));
+    assert!(
+        hooks[0].get("secret").is_none(),
+        "secret must not be listed"
+    );
+
+    let del = server
+        .delete(&format!("/webhooks/{id}"))
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(del.status_code(), 200, "delete: {}", del.text());
+
+    let again = server
+        .delete(&format!("/webhooks/{id}"))
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(again.status_code(), 404);
+}
+
+#[tokio::test]
+async fn webhook_rejects_unknown_events_and_bad_urls() {
+    let (server, db) = spawn_real_app().await;
+    let (_, token) = register_verified(&server, &db).await;
+
+    let unknown = server
+        .post("/webhooks")
+        .authorization_bearer(&token)
+        .json(&json!({ "url": "https://hooks.example.com/opn", "events": ["link.deleted"] }))
+        .await;
+    assert_eq!(unknown.status_code(), 400, "{}", unknown.text());
+
+    let empty = server
+        .post("/webhooks")
+        .authorization_bearer(&token)
+        .json(&json!({ "url": "https://hooks.example.com/opn", "events": [] }))
+        .await;
+    assert_eq!(empty.status_code(), 400, "{}", empty.text());
+
+    let bad_url = server
+        .post("/webhooks")
+        .authorization_bearer(&token)
+        .json(&json!({ "url": "ftp://hooks.example.com", "events": ["link.milestone"] }))
+        .await;
+    assert_eq!(bad_url.status_code(), 400, "{}", bad_url.text());
+}
+
+#[tokio::test]
+async fn only_admins_subscribe_to_user_registered() {
+    let (server, db) = spawn_real_app().await;
+    let (user_id, token) = register_verified(&server, &db).await;
+
+    let denied = server
+        .post("/webhooks")
+        .authorization_bearer(&token)
+        .json(&json!({ "url": "https://hooks.example.com/ops", "events": ["user.registered"] }))
+        .await;
+    assert_eq!(denied.status_code(), 403, "{}", denied.text());
+
+    users::ActiveModel {
+        id: Set(user_id),
+        is_admin: Set(true),
+        ..Default::default()
+    }
+    .update(&db)
+    .await
+    .unwrap();
+
+    let allowed = server
+        .post("/webhooks")
+        .authorization_bearer(&token)
+        .json(&json!({ "url": "https://hooks.example.com/ops", "events": ["user.registered", "link.milestone"] }))
+        .await;
+    assert_eq!(allowed.status_code(), 201, "{}", allowed.text());
+
+    // The test DB is shared: don't leave an admin signup hook behind for every
+    // later registration to fan out to.
+    let id = allowed.json::<Value>()["id"].as_i64().unwrap();
+    let del = server
+        .delete(&format!("/webhooks/{id}"))
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(del.status_code(), 200, "{}", del.text());
+}
+
+#[tokio::test]
+async fn failed_delivery_is_logged_and_retry_resends_payload() {
+    let (server, db) = spawn_real_app().await;
+    let (_, token) = register_verified(&server, &db).await;
+    let (_, other_token) = register_verified(&server, &db).await;
+
+    // `.invalid` never resolves, so every attempt fails without a response.
+    let created = server
+        .post("/webhooks")
+        .authorization_bearer(&token)
+        .json(&json!({ "url": "https://hooks.opn.invalid/in", "events": ["link.created"] }))
+        .await;
+    assert_eq!(created.status_code(), 201, "{}", created.text());
+    let hook_id = created.json::<Value>()["id"].as_i64().unwrap();
+
+    let link = server
+        .post("/links")
+        .authorization_bearer(&token)
+        .json(&json!({ "original_url": "https://iana.org/hooked" }))
+        .await;
+    assert_eq!(link.status_code(), 201, "{}", link.text());
+
+    let deliveries_url = format!("/webhooks/{hook_id}/deliveries");
+    let mut deliveries = Vec::new();
+    for _ in 0..50 {
+        deliveries = server
+            .get(&deliveries_url)
+            .authorization_bearer(&token)
+            .await
+            .json::<Vec<Value>>();
+        if !deliveries.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(deliveries.len(), 1, "delivery recorded: {deliveries:?}");
+    let first = &deliveries[0];
+    assert_eq!(first["event"], "link.created");
+    assert_eq!(first["success"], false);
+    assert!(first["status_code"].is_null());
+    assert!(first["error"].as_str().is_some_and(|e| !e.is_empty()));
+    let payload: Value = serde_json::from_str(first["payload"].as_str().unwrap()).unwrap();
+    assert_eq!(payload["data"]["original_url"], "https://iana.org/hooked");
+
+    // Someone else's webhook and deliveries are invisible.
+    let res = server
+        .get(&deliveries_url)
+        .authorization_bearer(&other_token)
+        .await;
+    assert_eq!(res.status_code(), 404);
+    let retry_url = format!("/webhooks/deliveries/{}/retry", first["id"]);
+    let res = server
+        .post(&retry_url)
+        .authorization_bearer(&other_token)
+        .await;
+    assert_eq!(res.status_code(), 404);
+
+    let res = server.post(&retry_url).authorization_bearer(&token).await;
+    assert_eq!(res.status_code(), 200, "{}", res.text());
+    let retried: Value = res.json();
+    assert_ne!(retried["id"], first["id"]);
+    assert_eq!(retried["event"], "link.created");
+    assert_eq!(retried["payload"], first["payload"]);
+    assert_eq!(retried["success"], false);
+
+    let deliveries: Vec<Value> = server
+        .get(&deliveries_url)
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(deliveries.len(), 2);
+    assert_eq!(deliveries[0]["id"], retried["id"], "newest first");
+}
+
+#[tokio::test]
+async fn webhooks_require_auth() {
+    let (server, _db) = spawn_real_app().await;
+    assert_eq!(server.get("/webhooks").await.status_code(), 401);
+}
+
+#[tokio::test]
+async fn chat_integration_validates_platform_url() {
+    let (server, db) = spawn_real_app().await;
+    let (_, token) = register_verified(&server, &db).await;
+
+    let wrong_host = server
+        .post("/integrations")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "platform": "slack",
+            "webhook_url": "https://discord.com/api/webhooks/1/abc",
+            "events": ["link.created"]
+        }))
+        .await;
+    assert_eq!(wrong_host.status_code(), 400, "{}", wrong_host.text());
+
+    let created = server
+        .post("/integrations")
+        .authorization_bearer(&token)
+        .json(&json!({
+            "platform": "discord",
+            "webhook_url": "https://discord.com/api/webhooks/1/abc",
+            "events": ["link.created", "link.milestone"]
+        }))
+        .await;
+    assert_eq!(created.status_code(), 201, "{}", created.text());
+    let id = created.json::<Value>()["id"].as_i64().unwrap();
+
+    let list: Value = server
+        .get("/integrations")
+        .authorization_bearer(&token)
+        .await
+        .json();
+    assert_eq!(list.as_array().unwrap().len(), 1);
+    assert_eq!(list[0]["platform"], "discord");
+
+    let del = server
+        .delete(&format!("/integrations/{id}"))
+        .authorization_bearer(&token)
+        .await;
+    assert_eq!(del.status_code(), 200, "{}", del.text());
+}
@@ -9,16 +9,22 @@
 //!     (the `token_version` revocation the audit added), and
 //!   * a connected `/ws` subscriber receives broadcast click events for its own
 //!     links but NOT another user's (the per-user filter in `handle_socket`).
+//!
+//! It also checks that a `/ws` client that stops answering pings is closed and
+//! its subscription dropped.
 
 mod common;
 
 use common::{
-    mark_email_verified, spawn_real_app_ws, spawn_real_app_ws_with_interval, unique_email,
+    mark_email_verified, spawn_real_app_ws, spawn_real_app_ws_with_interval,
+    spawn_real_app_ws_with_ping, unique_email,
 };
 use futures_util::StreamExt;
-use opn_onl_backend::handlers::websocket::{ClickEvent, WsState};
+use opn_onl_backend::handlers::websocket::{
+    ClickCoalescer, ClickEvent, Coalesced, Heartbeat, WsState,
+};
 use serde_json::{json, Value};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Register a user and return `(jwt, user_id)`.
 async fn register(server: &axum_test::TestServer, email: &str) -> (String, i32) {
@@ -47,6 +53,7 @@ fn click_for(user_id: i32, code: &str) -> ClickEvent {
         device: Some("Desktop".to_string()),
         browser: Some("Firefox".to_string()),
         timestamp: "2026-07-11T00:00:00Z".to_string(),
+        clicks: 1,
     }
 }
 
@@ -209,6 +216,29 @@ async fn ws_connection_closes_after_token_is_revoked() {
     );
 }
 
+#[tokio::test]
+async fn ws_connection_without_pongs_is_closed_and_unsubscribed() {
+    let (server, _db, ws) = spawn_real_app_ws_with_ping(Duration::from_millis(50)).await;
+    let (token, _) = register(&server, &unique_email()).await;
+
+    // Never read from the socket, so the client never answers a ping.
+    let _socket = server
+        .get_websocket("/ws")
+        .add_query_param("token", &token)
+        .await
+        .into_websocket()
+        .await;
+    wait_for_subscriber(&ws).await;
+
+    for _ in 0..200 {
+        if ws.click_tx.receiver_count() == 0 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("silent WebSocket client was never disconnected");
+}
+
 #[tokio::test]
 async fn sse_stream_ends_after_token_is_revoked() {
     use opn_onl_backend::entity::users;
@@ -249,3 +279,98 @@ async fn sse_stream_ends_after_token_is_revoked() {
     .await
     .expect("revoked SSE stream stayed open");
 }
+
+/// Offer `count` clicks on one link, `spacing` apart starting at `start`;
+/// returns what was sent right away and the flush delay that was requested.
+fn offer_burst(
+    coalescer: &ClickCoalescer,
+    start: Instant,
+    spacing: Duration,
+    count: u32,
+) -> (Vec<ClickEvent>, Vec<Duration>) {
+    let (mut sent, mut flushes) = (Vec::new(), Vec::new());
+    for i in 0..count {
+        let mut event = click_for(1, "HOT");
+        event.click_count = i as i32 + 1;
+        match coalescer.offer(event, start + spacing * i) {
+            Coalesced::Send(event) => sent.push(event),
+            Coalesced::Held {
+                flush_in: Some(delay),
+            } => flushes.push(delay),
+            Coalesced::Held { flush_in: None } => {}
+        }
+    }
+    (sent, flushes)
+}
+
+#[test]
+fn clicks_below_threshold_are_sent_individually() {
+    let coalescer = ClickCoalescer::new(5, Duration::from_millis(100));
+    let start = Instant::now();
+    let (sent, flushes) = offer_burst(&coalescer, start, Duration::from_millis(250), 8);
+    assert_eq!(sent.len(), 8);
+    assert!(sent.iter().all(|e| e.clicks == 1));
+    assert!(flushes.is_empty());
+}
+
+#[test]
+fn burst_above_threshold_is_coalesced_without_losing_clicks() {
+    let coalescer = ClickCoalescer::new(3, Duration::from_millis(100));
+    let start = Instant::now();
+
+    // Ten clicks 10ms apart: the first three go straight out, the rest are
+    // folded into one trailing update.
+    let (sent, flushes) = offer_burst(&coalescer, start, Duration::from_millis(10), 10);
+    assert_eq!(sent.len(), 3);
+    assert_eq!(flushes, [Duration::from_millis(90)]);
+
+    let trailing = coalescer
+        .flush(1, start + Duration::from_millis(120))
+        .expect("held clicks are flushed");
+    assert_eq!(trailing.clicks, 7);
+    assert_eq!(trailing.click_count, 10, "carries the latest total");
+    assert!(coalescer
+        .flush(1, start + Duration::from_millis(130))
+        .is_none());
+
+    // Once the burst is over, clicks are exact again.
+    let mut quiet = click_for(1, "HOT");
+    quiet.click_count = 11;
+    match coalescer.offer(quiet, start + Duration::from_secs(2)) {
+        Coalesced::Send(event) => assert_eq!(event.clicks, 1),
+        other => panic!("expected an immediate send, got {other:?}"),
+    }
+}
+
+#[test]
+fn sustained_burst_sends_at_most_once_per_interval() {
+    let interval = Duration::from_millis(100);
+    let coalescer = ClickCoalescer::new(3, interval);
+    let start = Instant::now();
+
+    // 1000 clicks/s for one second.
+    let (sent, flushes) = offer_burst(&coalescer, start, Duration::from_millis(1), 1000);
+    assert!(sent.len() <= 3 + 10, "{} events sent", sent.len());
+    assert_eq!(flushes.len(), 1);
+
+    let trailing = coalescer.flush(1, start + Duration::from_secs(2));
+    let delivered: u32 = sent
+        .iter()
+        .chain(trailing.iter())
+        .map(|event| event.clicks)
+        .sum();
+    assert_eq!(delivered, 1000);
+}
+
+#[test]
+fn heartbeat_expires_only_after_a_silent_timeout() {
+    let start = Instant::now();
+    let mut heartbeat = Heartbeat::new(Duration::from_secs(60), start);
+    assert!(!heartbeat.expired(start + Duration::from_secs(60)));
+    assert!(heartbeat.expired(start + Duration::from_secs(61)));
+
+    // A pong (or any other frame) resets the clock.
+    heartbeat.seen(start + Duration::from_secs(50));
+    assert!(!heartbeat.expired(start + Duration::from_secs(100)));
+    assert!(heartbeat.expired(start + Duration::from_secs(111)));
+}
@@ -1,13 +1,12 @@
 //! WebSocket and real-time event tests
 
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
 
 // ============= Click Event Tests =============
 
 mod click_event_tests {
-    use super::*;
 
+    #[allow(dead_code)]
     #[derive(Debug, Clone)]
     struct ClickEvent {
         link_id: i32,
@@ -218,6 +217,7 @@ mod subscription_tests {
 // ============= Message Routing Tests =============
 
 mod routing_tests {
+    #[allow(dead_code)]
     #[derive(Debug, Clone)]
     struct BroadcastMessage {
         link_id: i32,
@@ -297,6 +297,7 @@ mod routing_tests {
 // ============= Connection State Tests =============
 
 mod connection_tests {
+    #[allow(dead_code)]
     #[derive(Debug, Clone, Copy, PartialEq)]
     enum ConnectionState {
         Connecting,
@@ -312,6 +313,7 @@ mod connection_tests {
         max_reconnect_attempts: u32,
     }
 
+    #[allow(dead_code)]
     impl Connection {
         fn new() -> Self {
             Self {
@@ -10,7 +10,8 @@ interface BioLink {
     code: string;
     short_url: string;
     label: string;
-    click_count: number;
+    click_count: number | null;
+    click_count_bucket?: string | null;
 }
 
 interface BioProfile {
@@ -15,7 +15,8 @@ interface LinkPreview {
     has_password: boolean;
     is_expired: boolean;
     created_at: string;
-    click_count: number;
+    click_count: number | null;
+    click_count_bucket?: string | null;
     reputation?: { verdict: string; source: string };
     interstitial_enabled?: boolean;
     safe_link_interstitial?: boolean;
@@ -150,7 +151,7 @@ export default function Preview() {
                                     <MousePointer className="h-4 w-4" />
                                     Clicks
                                 </div>
-                                <p className="text-2xl font-bold text-slate-900">{preview.click_count.toLocaleString()}</p>
+                                <p className="text-2xl font-bold text-slate-900">{preview.click_count?.toLocaleString() ?? preview.click_count_bucket}</p>
                             </div>
                             <div className="p-4 bg-slate-50 rounded-xl">
                                 <div className="flex items-center gap-2 text-slate-500 text-sm mb-1">